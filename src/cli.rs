@@ -1,23 +1,50 @@
-mod index;
-mod server;
-
-pub use index::*;
-use server::model::model_cli::create_model;
-use server::model::configure_storages;
-
-pub fn run() -> Option<impl std::future::Future<Output = Result<(), std::io::Error>>> {
-    let cli: Result<Cli, ClapError> = get_validated_args();
-    if let Err(err) = cli {
-        if err.print().is_err() {
-            eprintln!("{err}", err=err.render());
-        }
-        return None;
-    }
-    match cli.unwrap().command {
-        Commands::Start(args) => return Some(server::start(args.port, args.bind)),
-        Commands::CreateModel(args) => create_model(args),
-        Commands::ConfigureStorages(args) => configure_storages(args)
-    }
-
-    None
+mod index;
+mod server;
+
+pub use index::*;
+use std::fs::File;
+use server::model::model_cli::{
+    create_model,
+    check_models,
+    warn_model_issues
+};
+use server::model::{
+    configure_storages,
+    warn_pending_migrations,
+    lock_storages,
+    migrate
+};
+
+pub fn run() -> Option<impl std::future::Future<Output = Result<(), std::io::Error>>> {
+    let cli: Result<Cli, ClapError> = get_validated_args();
+    if let Err(err) = cli {
+        if err.print().is_err() {
+            eprintln!("{err}", err=err.render());
+        }
+        return None;
+    }
+    match cli.unwrap().command {
+        Commands::Start(args) => {
+            warn_model_issues(args.modelspath.as_path());
+            warn_pending_migrations(args.modelspath.as_path(), args.storage_definitions.clone());
+            // held while the server runs, so the storages can't be migrated meanwhile
+            let locks: Vec<File> = match lock_storages(args.modelspath.as_path(), args.storage_definitions.clone()) {
+                Ok(locks) => locks,
+                Err(err) => {
+                    eprintln!("{err}");
+                    return None;
+                }
+            };
+            return Some(async move {
+                let _locks: Vec<File> = locks;
+                server::start(args.port, args.bind, args.purge_interval).await
+            });
+        },
+        Commands::CreateModel(args) => create_model(args),
+        Commands::CheckModels(args) => check_models(args),
+        Commands::Migrate(args) => migrate(args),
+        Commands::ConfigureStorages(args) => configure_storages(args)
+    }
+
+    None
 }
\ No newline at end of file
@@ -1,464 +1,1096 @@
-pub mod model;
-mod view;
-
-
-// used types
-use std::str::Utf8Error;
-use std::net::Ipv4Addr;
-use model::Record;
-use std::io::Error;
-use actix_web::{
-    HttpResponse,
-    HttpServer,
-    App
-};
-use actix_web::web::{
-    Bytes as BodyBytes,
-    Path as UriParam
-};
-use model::GraphQLReturn;
-
-// used functions
-use std::str::from_utf8;
-use view::get_view_file;
-use model::{
-    create_one,
-    read_one,
-    update_one,
-    delete_one,
-    handle_gql_post_body,
-    handle_gql_query_arg
-};
-
-// used derive macros
-use serde_derive::{
-    Deserialize,
-    Serialize
-};
-use actix_web::{
-    post,
-    get,
-    put,
-    delete
-};
-
-pub async fn start(port: u16, ip: Ipv4Addr) -> Result<(), Error> {
-    let server = HttpServer::new(|| 
-        App::new().service(uri_handler_post)
-                  .service(uri_handler_get)
-                  .service(uri_handler_put)
-                  .service(uri_handler_delete)
-                  )
-                  .bind(format!("{ip}:{port}"))?;
-    println!("Listening on {ip}:{port}");
-    server.run().await
-}
-
-fn not_found() -> HttpResponse {
-    HttpResponse::NotFound().json(JsonError{
-        error: "This page does not exist".to_string()
-    })
-}
-
-fn bad_request(message: String) -> HttpResponse {
-    HttpResponse::BadRequest().json(JsonError {
-        error: message
-    })
-}
-
-fn bad_endpoint() -> HttpResponse {
-    bad_request("This endpoint does not exist".to_string())
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-struct JsonError {
-    error: String
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-struct JsonData {
-    data: Record
-}
-
-#[get("/{uri:.*}")]
-async fn uri_handler_get(uri: UriParam<String>) -> HttpResponse {
-    let subroutes: &str = &uri.into_inner();
-
-    let mut segments: Vec<&str> = subroutes.split('/').collect();
-    if segments.len() == 1 {
-        match subroutes {
-            "" => return send_view_file("index.html"),
-            "robots.txt" => return send_view_file("robots.txt"),
-            _ => return not_found()
-        }
-    }
-    
-    match segments.remove(0) {
-        "view" => send_view_file(subroutes),
-        "api" => {
-            match segments.remove(0) {
-                "rest" => rest_api_get(&segments.join("/")),
-                "graphql" => send_view_file("graphql-gui.html"),
-                _ => not_found()
-            }
-        },
-        _ => not_found()
-    }
-}
-
-fn send_view_file(subroutes: &str) -> HttpResponse {
-    let view_file = get_view_file(subroutes);
-    match view_file {
-        Some((file, content_type)) => HttpResponse::Ok().insert_header(("content-type", content_type.as_str())).body(file),
-        None => not_found()
-    }
-}
-
-fn rest_api_get(uri: &str) -> HttpResponse {
-    let mut segments: Vec<&str> = uri.split('/').collect();
-    if segments.len() != 2 {
-        return bad_endpoint();
-    }
-    let model_name: &str = segments.remove(0);
-    let id: &str = segments.remove(0);
-    match read_one(model_name, id) {
-        Ok(record) => HttpResponse::Ok().json(JsonData {
-            data: record
-        }),
-        Err(err) => bad_request(err.to_string())
-    }
-}
-
-
-
-#[post("/{uri:.*}")]
-async fn uri_handler_post(body: BodyBytes, uri: UriParam<String>) -> HttpResponse {
-    let subroutes: &str = &uri.into_inner();
-    let mut segments: Vec<&str> = subroutes.split('/').collect();
-
-    if segments.len() == 1 {
-        return bad_endpoint();
-    }
-
-    match segments.remove(0) {
-        "api" => {
-            match segments.remove(0) {
-                "rest" => rest_api_post(&segments.join("/"), &body),
-                "graphql" => {
-                    if segments.is_empty() {
-                        return graphql_api_post(&body);
-                    }
-                    bad_endpoint()
-                },
-                _ => bad_endpoint()
-            }
-        },
-        _ => bad_endpoint()
-    }
-}
-
-fn rest_api_post(uri: &str, body: &BodyBytes) -> HttpResponse {
-    let body_str: Result<&str, Utf8Error> = from_utf8(body);
-    if body_str.is_err() {
-        return bad_request("Invalid body, accepting utf-8 only".to_string())
-    }
-    let mut segments: Vec<&str> = uri.split('/').collect();
-    if segments.len() != 1 {
-        return bad_endpoint();
-    }
-    match create_one(segments.remove(0), body_str.unwrap()) {
-        Ok(record) => HttpResponse::Created().json(JsonData {
-            data: record
-        }),
-        Err(err) => bad_request(err.to_string())
-    }
-}
-
-fn graphql_api_post(body: &BodyBytes) -> HttpResponse {
-    let body_str: Result<&str, Utf8Error> = from_utf8(body);
-    if body_str.is_err() {
-        return bad_request("Invalid body, accepting utf-8 only".to_string())
-    }
-    let handled: GraphQLReturn = handle_gql_post_body(body_str.unwrap());
-    if handled.data.is_none() {
-        return HttpResponse::BadRequest().json(handled);
-    }
-    HttpResponse::Ok().json(handled)
-}
-
-
-
-#[put("/{uri:.*}")]
-async fn uri_handler_put(body: BodyBytes, uri: UriParam<String>) -> HttpResponse {
-    let subroutes: &str = &uri.into_inner();
-    let mut segments: Vec<&str> = subroutes.split('/').collect();
-
-    if segments.len() == 1 {
-        return bad_endpoint();
-    }
-
-    match segments.remove(0) {
-        "api" => {
-            match segments.remove(0) {
-                "rest" => rest_api_put(&segments.join("/"), &body),
-                "graphql" => bad_endpoint(),
-                _ => bad_endpoint()
-            }
-        },
-        _ => bad_endpoint()
-    }
-}
-
-fn rest_api_put(uri: &str, body: &BodyBytes) -> HttpResponse {
-    let body_str: Result<&str, Utf8Error> = from_utf8(body);
-    if body_str.is_err() {
-        return bad_request("Invalid body, accepting utf-8 only".to_string())
-    }
-    let mut segments: Vec<&str> = uri.split('/').collect();
-    if segments.len() != 2 {
-        return bad_endpoint();
-    }
-    let model_name: &str = segments.remove(0);
-    let id: &str = segments.remove(0);
-    match update_one(model_name, id, body_str.unwrap()) {
-        Ok(record) => HttpResponse::Ok().json(JsonData {
-            data: record
-        }),
-        Err(err) => bad_request(err.to_string())
-    }
-}
-
-
-
-#[delete("/{uri:.*}")]
-async fn uri_handler_delete(uri: UriParam<String>) -> HttpResponse {
-    let subroutes: &str = &uri.into_inner();
-    let mut segments: Vec<&str> = subroutes.split('/').collect();
-
-    if segments.len() == 1 {
-        return bad_endpoint();
-    }
-
-    match segments.remove(0) {
-        "api" => {
-            match segments.remove(0) {
-                "rest" => rest_api_delete(&segments.join("/")),
-                "graphql" => bad_endpoint(),
-                _ => bad_endpoint()
-            }
-        },
-        _ => bad_endpoint()
-    }
-}
-
-fn rest_api_delete(uri: &str) -> HttpResponse {
-    let mut segments: Vec<&str> = uri.split('/').collect();
-    if segments.len() != 2 {
-        return bad_endpoint();
-    }
-    let model_name: &str = segments.remove(0);
-    let id: &str = segments.remove(0);
-    match delete_one(model_name, id) {
-        Ok(record) => HttpResponse::Ok().json(JsonData {
-            data: record
-        }),
-        Err(err) => bad_request(err.to_string())
-    }
-}
-
-
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    use actix_web::dev::ServiceResponse;
-    use actix_web::test::TestRequest;
-    use actix_web::body::MessageBody;
-
-    use serde_json::from_str;
-    use std::fs::write;
-    use actix_web::test::{
-        init_service,
-        call_service
-    };
-
-    fn pre_test() {
-        let file = "./testing/server/server.data.test.json";
-        assert!(write(file, r#"
-            {
-                "movie": {
-                    "\"get\"": {"id": "get"},
-                    "\"put\"": {"id": "put"},
-                    "\"delete\"": {"id": "delete"}
-                }
-            }
-            "#).is_ok(), "Unable to write storage file for tests");
-    }
-
-    fn post_test() {
-        pre_test();
-    }
-
-    #[actix_web::test]
-    async fn test_rest_api_post() {
-        pre_test();
-
-        let app = init_service(App::new().service(uri_handler_post)).await;
-
-        // test valid request
-        let valid_input = r#"
-            {
-                "id": "post",
-                "name": "Natural Born Killers",
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        let req = TestRequest::post().uri("/api/rest/movie")
-                                     .set_payload(valid_input)
-                                     .to_request();
-        let res: ServiceResponse = call_service(&app, req).await;
-        assert!(res.status().is_success(), "Unexpected error when creating a valid record");
-
-        let expected: Record = from_str(valid_input).unwrap();
-        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
-        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
-        assert_eq!(res_data.data, expected, "Sent data doesn't match the response");
-
-        // test invalid endpoints
-        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/1"] {
-            let req = TestRequest::post().uri(endpoint)
-                                         .set_payload("")
-                                         .to_request();
-            let res: ServiceResponse = call_service(&app, req).await;
-            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
-        }
-
-        // test invalid body
-        let req = TestRequest::post().uri("/api/rest/movie")
-                                     .set_payload("")
-                                     .to_request();
-        let res: ServiceResponse = call_service(&app, req).await;
-        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to request with invalid body");
-
-        post_test();
-    }
-
-    #[actix_web::test]
-    async fn test_rest_api_get() {
-        pre_test();
-
-        let app = init_service(App::new().service(uri_handler_get)).await;
-
-        // test valid request
-        let expected: Record = from_str(r#"
-            {
-                "id": "get",
-                "name": null,
-                "year": null,
-                "actors": null,
-                "recommended": null
-            }
-        "#).unwrap();
-        let req = TestRequest::get().uri("/api/rest/movie/get")
-                                    .to_request();
-        let res: ServiceResponse = call_service(&app, req).await;
-        assert!(res.status().is_success(), "Unexpected error when fetching a record");
-
-        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
-        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
-        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
-
-        // test invalid endpoints
-        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record"] {
-            let req = TestRequest::get().uri(endpoint)
-                                         .to_request();
-            let res: ServiceResponse = call_service(&app, req).await;
-            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
-        }
-
-        post_test();
-    }
-
-    #[actix_web::test]
-    async fn test_rest_api_put() {
-        pre_test();
-
-        let app = init_service(App::new().service(uri_handler_put)).await;
-
-        // test valid request
-        let valid_input = r#"
-            {
-                "id": "doesn't matter",
-                "name": "test"
-            }
-        "#;
-        let req = TestRequest::put().uri("/api/rest/movie/put")
-                                    .set_payload(valid_input)
-                                    .to_request();
-        let res: ServiceResponse = call_service(&app, req).await;
-        assert!(res.status().is_success(), "Unexpected error when updating a valid record");
-
-        let expected: Record = from_str(r#"
-            {
-                "id": "put",
-                "name": "test",
-                "year": null,
-                "actors": null,
-                "recommended": null
-            }
-        "#).unwrap();
-        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
-        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
-        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
-
-        // test invalid endpoints
-        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record"] {
-            let req = TestRequest::put().uri(endpoint)
-                                        .to_request();
-            let res: ServiceResponse = call_service(&app, req).await;
-            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
-        }
-
-        post_test();
-    }
-
-    #[actix_web::test]
-    async fn test_rest_api_delete() {
-        pre_test();
-
-        let app = init_service(App::new().service(uri_handler_delete)).await;
-
-        // test valid request
-        let expected: Record = from_str(r#"
-            {
-                "id": "delete",
-                "name": null,
-                "year": null,
-                "actors": null,
-                "recommended": null
-            }
-        "#).unwrap();
-        let req = TestRequest::delete().uri("/api/rest/movie/delete")
-                                    .to_request();
-        let res: ServiceResponse = call_service(&app, req).await;
-        assert!(res.status().is_success(), "Unexpected error when deleting a record");
-
-        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
-        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
-        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
-
-        // test invalid endpoints
-        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record"] {
-            let req = TestRequest::delete().uri(endpoint)
-                                         .to_request();
-            let res: ServiceResponse = call_service(&app, req).await;
-            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
-        }
-
-        post_test();
-    }
+pub mod model;
+mod view;
+
+
+// used types
+use std::str::Utf8Error;
+use std::time::Duration;
+use std::net::Ipv4Addr;
+use model::Record;
+use std::io::Error;
+use actix_web::{
+    HttpRequest,
+    HttpResponse,
+    HttpResponseBuilder,
+    HttpServer,
+    App
+};
+use actix_web::http::header::{
+    ETAG,
+    IF_MATCH
+};
+use actix_web::web::{
+    Bytes as BodyBytes,
+    Path as UriParam,
+    Query as QueryParams
+};
+use std::collections::HashMap;
+use model::GraphQLReturn;
+use model::{
+    TruePrimitiveType,
+    AttrName,
+    TrueType,
+    VersionMismatch,
+    Conflict,
+    HISTORY,
+    NULL,
+    VERSION
+};
+
+// used functions
+use std::str::from_utf8;
+use actix_web::rt::spawn;
+use actix_web::web::block;
+use actix_web::rt::time::interval;
+use view::get_view_file;
+use model::{
+    create_one,
+    read_one,
+    read_one_with_deleted,
+    read_one_as_of,
+    read_history,
+    update_one,
+    delete_one,
+    key_length,
+    read_linked,
+    link_one,
+    unlink_one,
+    restore_one,
+    purge_one,
+    purge_expired,
+    verify_one,
+    handle_gql_post_body,
+    handle_gql_query_arg
+};
+
+// used derive macros
+use serde_derive::{
+    Deserialize,
+    Serialize
+};
+use actix_web::{
+    post,
+    get,
+    put,
+    delete
+};
+
+pub async fn start(port: u16, ip: Ipv4Addr, purge_interval: u64) -> Result<(), Error> {
+    let server = HttpServer::new(|| 
+        App::new().service(uri_handler_post)
+                  .service(uri_handler_get)
+                  .service(uri_handler_put)
+                  .service(uri_handler_delete)
+                  )
+                  .bind(format!("{ip}:{port}"))?;
+    println!("Listening on {ip}:{port}");
+
+    // expired records are invisible to reads anyway, so they are purged from the storages in the background,
+    // on the thread pool for blocking calls to not stall the workers
+    spawn(async move {
+        let mut interval = interval(Duration::from_secs(purge_interval));
+        loop {
+            interval.tick().await;
+            match block(purge_expired).await {
+                Ok(Ok(_)) => (),
+                Ok(Err(err)) => eprintln!("warning: {err}"),
+                Err(err) => eprintln!("warning: expired records couldn't be purged, {err}")
+            }
+        }
+    });
+    server.run().await
+}
+
+fn not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(JsonError{
+        error: "This page does not exist".to_string()
+    })
+}
+
+fn bad_request(message: String) -> HttpResponse {
+    HttpResponse::BadRequest().json(JsonError {
+        error: message
+    })
+}
+
+fn bad_endpoint() -> HttpResponse {
+    bad_request("This endpoint does not exist".to_string())
+}
+
+fn conflict(message: String) -> HttpResponse {
+    HttpResponse::Conflict().json(JsonError {
+        error: message
+    })
+}
+
+fn precondition_failed(message: String) -> HttpResponse {
+    HttpResponse::PreconditionFailed().json(JsonError {
+        error: message
+    })
+}
+
+fn error_response(err: Error) -> HttpResponse {
+    match err.get_ref() {
+        Some(inner) if inner.is::<VersionMismatch>() => precondition_failed(err.to_string()),
+        Some(inner) if inner.is::<Conflict>() => conflict(err.to_string()),
+        _ => bad_request(err.to_string())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct JsonError {
+    error: String
+}
+
+/*
+    json_record:
+        Sends a single record as json data,
+        the ETag header holds the version of the record if its model is versioned
+
+    returns:
+        the response
+*/
+fn json_record(mut response: HttpResponseBuilder, record: Record) -> HttpResponse {
+    if let Some(version) = record.get(&AttrName(VERSION.to_string())).filter(|version| **version != NULL) {
+        response.insert_header((ETAG, format!("\"{version}\"")));
+    }
+    response.json(JsonData {
+        data: record
+    })
+}
+
+/*
+    if_match:
+        Reads the expected version of a record from the If-Match header,
+        a missing header or * matches every version,
+        weak ETags never match because If-Match requires the strong comparison
+
+    returns:
+        the expected version or an error message if the header can't match a version
+*/
+fn if_match(req: &HttpRequest) -> Result<Option<i64>, String> {
+    let Some(value) = req.headers().get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let value: &str = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    if value.starts_with("W/") {
+        return Err(format!("If-Match {value:?} is a weak ETag, which never matches"));
+    }
+    value.trim_matches('"').parse::<i64>()
+        .map(Some)
+        .map_err(|_| format!("If-Match {value:?} doesn't match a version"))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct JsonData {
+    data: Record
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct JsonDataList {
+    data: Vec<Record>
+}
+
+#[get("/{uri:.*}")]
+async fn uri_handler_get(uri: UriParam<String>, query: QueryParams<HashMap<String, String>>) -> HttpResponse {
+    let subroutes: &str = &uri.into_inner();
+
+    let mut segments: Vec<&str> = subroutes.split('/').collect();
+    if segments.len() == 1 {
+        match subroutes {
+            "" => return send_view_file("index.html"),
+            "robots.txt" => return send_view_file("robots.txt"),
+            _ => return not_found()
+        }
+    }
+    
+    match segments.remove(0) {
+        "view" => send_view_file(subroutes),
+        "api" => {
+            match segments.remove(0) {
+                "rest" => rest_api_get(&segments.join("/"), &query),
+                "graphql" => send_view_file("graphql-gui.html"),
+                _ => not_found()
+            }
+        },
+        _ => not_found()
+    }
+}
+
+fn send_view_file(subroutes: &str) -> HttpResponse {
+    let view_file = get_view_file(subroutes);
+    match view_file {
+        Some((file, content_type)) => HttpResponse::Ok().insert_header(("content-type", content_type.as_str())).body(file),
+        None => not_found()
+    }
+}
+
+fn rest_api_get(uri: &str, query: &HashMap<String, String>) -> HttpResponse {
+    // the model name is followed by one segment for every primary key attribute,
+    // optionally followed by history or the name of a manyToMany association to get the linked records,
+    // soft deleted records are only found with the query parameter withDeleted=true,
+    // past states of records are read with the query parameter asOf=<DateTime>
+    let mut segments: Vec<&str> = uri.split('/').collect();
+    if segments.len() < 2 {
+        return bad_endpoint();
+    }
+    let model_name: &str = segments.remove(0);
+    let key_length: usize = match key_length(model_name) {
+        Ok(key_length) => key_length,
+        Err(err) => return error_response(err)
+    };
+    if segments.len() == key_length + 1 {
+        let association_name: &str = segments.pop().unwrap();
+        let records: Result<Vec<Record>, Error> = if association_name == HISTORY {
+            read_history(model_name, &segments)
+        } else {
+            read_linked(model_name, &segments, association_name)
+        };
+        return match records {
+            Ok(records) => HttpResponse::Ok().json(JsonDataList {
+                data: records
+            }),
+            Err(err) => error_response(err)
+        };
+    }
+    let record: Result<Record, Error> = if let Some(as_of) = query.get("asOf") {
+        read_one_as_of(model_name, &segments, as_of)
+    } else if query.get("withDeleted").is_some_and(|value| value == "true") {
+        read_one_with_deleted(model_name, &segments)
+    } else {
+        read_one(model_name, &segments)
+    };
+    match record {
+        Ok(record) => json_record(HttpResponse::Ok(), record),
+        Err(err) => error_response(err)
+    }
+}
+
+
+
+#[post("/{uri:.*}")]
+async fn uri_handler_post(body: BodyBytes, uri: UriParam<String>) -> HttpResponse {
+    let subroutes: &str = &uri.into_inner();
+    let mut segments: Vec<&str> = subroutes.split('/').collect();
+
+    if segments.len() == 1 {
+        return bad_endpoint();
+    }
+
+    match segments.remove(0) {
+        "api" => {
+            match segments.remove(0) {
+                "rest" => rest_api_post(&segments.join("/"), &body),
+                "graphql" => {
+                    if segments.is_empty() {
+                        return graphql_api_post(&body);
+                    }
+                    bad_endpoint()
+                },
+                _ => bad_endpoint()
+            }
+        },
+        _ => bad_endpoint()
+    }
+}
+
+fn rest_api_post(uri: &str, body: &BodyBytes) -> HttpResponse {
+    let body_str: Result<&str, Utf8Error> = from_utf8(body);
+    if body_str.is_err() {
+        return bad_request("Invalid body, accepting utf-8 only".to_string())
+    }
+    let mut segments: Vec<&str> = uri.split('/').collect();
+    let model_name: &str = segments.remove(0);
+    if !segments.is_empty() {
+        let key_length: usize = match key_length(model_name) {
+            Ok(key_length) => key_length,
+            Err(err) => return error_response(err)
+        };
+        // the primary key of the record followed by verify checks the values of hashed attributes given in the body
+        if segments.len() == key_length + 1 && segments.last() == Some(&"verify") {
+            segments.pop();
+            return match verify_one(model_name, &segments, body_str.unwrap()) {
+                Ok(verified) => HttpResponse::Ok().json(JsonData {
+                    data: Record::from([(AttrName("verified".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Boolean(verified))))])
+                }),
+                Err(err) => error_response(err)
+            };
+        }
+        return rest_api_record_action(model_name, segments, true);
+    }
+    match create_one(model_name, body_str.unwrap()) {
+        Ok(record) => json_record(HttpResponse::Created(), record),
+        Err(err) => error_response(err)
+    }
+}
+
+fn rest_api_record_action(model_name: &str, mut segments: Vec<&str>, post: bool) -> HttpResponse {
+    // the primary key of the record is followed by restore (POST) or purge (DELETE) for soft deleted records,
+    // or by the name of a manyToMany association and the primary key of the record to link (POST) or unlink (DELETE)
+    let key_length: usize = match key_length(model_name) {
+        Ok(key_length) => key_length,
+        Err(err) => return error_response(err)
+    };
+    if segments.len() == key_length + 1 {
+        let result: Result<Record, Error> = match (segments.pop().unwrap(), post) {
+            ("restore", true) => restore_one(model_name, &segments),
+            ("purge", false) => purge_one(model_name, &segments),
+            _ => return bad_endpoint()
+        };
+        return match result {
+            Ok(record) => json_record(HttpResponse::Ok(), record),
+            Err(err) => error_response(err)
+        };
+    }
+    if segments.len() != key_length + 2 {
+        return bad_endpoint();
+    }
+    let other_id: &str = segments.pop().unwrap();
+    let association_name: &str = segments.pop().unwrap();
+    if post {
+        match link_one(model_name, &segments, association_name, other_id) {
+            Ok(record) => json_record(HttpResponse::Created(), record),
+            Err(err) => error_response(err)
+        }
+    } else {
+        match unlink_one(model_name, &segments, association_name, other_id) {
+            Ok(record) => json_record(HttpResponse::Ok(), record),
+            Err(err) => error_response(err)
+        }
+    }
+}
+
+fn graphql_api_post(body: &BodyBytes) -> HttpResponse {
+    let body_str: Result<&str, Utf8Error> = from_utf8(body);
+    if body_str.is_err() {
+        return bad_request("Invalid body, accepting utf-8 only".to_string())
+    }
+    let handled: GraphQLReturn = handle_gql_post_body(body_str.unwrap());
+    if handled.data.is_none() {
+        return HttpResponse::BadRequest().json(handled);
+    }
+    HttpResponse::Ok().json(handled)
+}
+
+
+
+#[put("/{uri:.*}")]
+async fn uri_handler_put(req: HttpRequest, body: BodyBytes, uri: UriParam<String>) -> HttpResponse {
+    let subroutes: &str = &uri.into_inner();
+    let mut segments: Vec<&str> = subroutes.split('/').collect();
+
+    if segments.len() == 1 {
+        return bad_endpoint();
+    }
+
+    match segments.remove(0) {
+        "api" => {
+            match segments.remove(0) {
+                "rest" => match if_match(&req) {
+                    Ok(expected_version) => rest_api_put(&segments.join("/"), &body, expected_version),
+                    Err(message) => precondition_failed(message)
+                },
+                "graphql" => bad_endpoint(),
+                _ => bad_endpoint()
+            }
+        },
+        _ => bad_endpoint()
+    }
+}
+
+fn rest_api_put(uri: &str, body: &BodyBytes, expected_version: Option<i64>) -> HttpResponse {
+    let body_str: Result<&str, Utf8Error> = from_utf8(body);
+    if body_str.is_err() {
+        return bad_request("Invalid body, accepting utf-8 only".to_string())
+    }
+    // the model name is followed by one segment for every primary key attribute
+    let mut segments: Vec<&str> = uri.split('/').collect();
+    if segments.len() < 2 {
+        return bad_endpoint();
+    }
+    let model_name: &str = segments.remove(0);
+    match update_one(model_name, &segments, body_str.unwrap(), expected_version) {
+        Ok(record) => json_record(HttpResponse::Ok(), record),
+        Err(err) => error_response(err)
+    }
+}
+
+
+
+#[delete("/{uri:.*}")]
+async fn uri_handler_delete(req: HttpRequest, uri: UriParam<String>) -> HttpResponse {
+    let subroutes: &str = &uri.into_inner();
+    let mut segments: Vec<&str> = subroutes.split('/').collect();
+
+    if segments.len() == 1 {
+        return bad_endpoint();
+    }
+
+    match segments.remove(0) {
+        "api" => {
+            match segments.remove(0) {
+                "rest" => match if_match(&req) {
+                    Ok(expected_version) => rest_api_delete(&segments.join("/"), expected_version),
+                    Err(message) => precondition_failed(message)
+                },
+                "graphql" => bad_endpoint(),
+                _ => bad_endpoint()
+            }
+        },
+        _ => bad_endpoint()
+    }
+}
+
+fn rest_api_delete(uri: &str, expected_version: Option<i64>) -> HttpResponse {
+    // the model name is followed by one segment for every primary key attribute,
+    // further segments purge soft deleted records or unlink records of a manyToMany association
+    let mut segments: Vec<&str> = uri.split('/').collect();
+    if segments.len() < 2 {
+        return bad_endpoint();
+    }
+    let model_name: &str = segments.remove(0);
+    match key_length(model_name) {
+        Ok(key_length) if segments.len() > key_length => return rest_api_record_action(model_name, segments, false),
+        Ok(_) => (),
+        Err(err) => return error_response(err)
+    }
+    match delete_one(model_name, &segments, expected_version) {
+        Ok(record) => json_record(HttpResponse::Ok(), record),
+        Err(err) => error_response(err)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::dev::ServiceResponse;
+    use actix_web::test::TestRequest;
+    use actix_web::body::MessageBody;
+
+    use serde_json::from_str;
+    use std::fs::write;
+    use actix_web::test::{
+        init_service,
+        call_service
+    };
+
+    fn pre_test() {
+        let file = "./testing/server/server.data.test.json";
+        assert!(write(file, r#"
+            {
+                "movie": {
+                    "\"get\"": {"id": "get"},
+                    "\"put\"": {"id": "put"},
+                    "\"delete\"": {"id": "delete"}
+                },
+                "casting": {
+                    "[\"get\",\"Woody Harrelson\"]": {"movieId": "get", "actor": "Woody Harrelson", "role": "Mickey Knox"},
+                    "[\"delete\",\"Juliette Lewis\"]": {"movieId": "delete", "actor": "Juliette Lewis", "role": "Mallory Knox"}
+                },
+                "review": {
+                    "1": {"id": 1, "movieId": "put", "text": "Too violent"},
+                    "2": {"id": 2, "movieId": "get", "text": "Masterpiece", "deletedAt": null}
+                },
+                "award": {
+                    "1": {"id": 1, "name": "Best Picture", "movieId": "delete", "version": 1},
+                    "2": {"id": 2, "name": "Best Director", "movieId": "put", "version": 1}
+                },
+                "actor": {
+                    "\"Woody Harrelson\"": {"name": "Woody Harrelson", "born": 1961},
+                    "\"Juliette Lewis\"": {"name": "Juliette Lewis", "born": 1973}
+                },
+                "actorHistory": {
+                    "1": {"sequence": 1, "key": ["Woody Harrelson"], "operation": "create", "at": "2020-01-01T00:00:00Z", "previous": null, "record": {"name": "Woody Harrelson", "born": 1960}},
+                    "2": {"sequence": 2, "key": ["Woody Harrelson"], "operation": "update", "at": "2021-01-01T00:00:00Z", "previous": {"name": "Woody Harrelson", "born": 1960}, "record": {"name": "Woody Harrelson", "born": 1961}}
+                },
+                "actorMovie": {
+                    "[\"Woody Harrelson\",\"get\"]": {"actorName": "Woody Harrelson", "movieId": "get"},
+                    "[\"Juliette Lewis\",\"delete\"]": {"actorName": "Juliette Lewis", "movieId": "delete"}
+                },
+                "session": {
+                    "\"active\"": {"id": "active", "account": 1, "validUntil": "2999-01-01T00:00:00Z"},
+                    "\"expired\"": {"id": "expired", "account": 1, "validUntil": "2000-01-01T00:00:00Z"}
+                },
+                "device": {
+                    "1": {"id": 1, "sessionId": "expired"}
+                },
+                "profile": {
+                    "1": {"id": 1, "displayName": "Ada", "birthYear": 1990, "homeAddress": {"zipCode": "12345"}}
+                },
+                "festival": {
+                    "\"Cannes\"": {"name": "Cannes"}
+                },
+                "screening": {
+                    "[\"Cannes\",\"get\"]": {"festivalName": "Cannes", "movieId": "get"}
+                }
+            }
+            "#).is_ok(), "Unable to write storage file for tests");
+    }
+
+    fn post_test() {
+        pre_test();
+    }
+
+    #[actix_web::test]
+    async fn test_rest_api_post() {
+        pre_test();
+
+        let app = init_service(App::new().service(uri_handler_post)).await;
+
+        // test valid request
+        let valid_input = r#"
+            {
+                "id": "post",
+                "name": "Natural Born Killers",
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        let req = TestRequest::post().uri("/api/rest/movie")
+                                     .set_payload(valid_input)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a valid record");
+
+        let expected: Record = from_str(valid_input).unwrap();
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Sent data doesn't match the response");
+
+        // test conflict with existing record
+        let req = TestRequest::post().uri("/api/rest/movie")
+                                     .set_payload(valid_input)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to create an already existing record");
+
+        // test keys of expired records, which are replaced before they get purged
+        for (input, is_success) in [
+            (r#"{"id": "expired", "account": 2}"#, true),
+            (r#"{"id": "active", "account": 2}"#, false)
+        ] {
+            let req = TestRequest::post().uri("/api/rest/session")
+                                         .set_payload(input)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status().is_success(), is_success, "Unexpected status code {status} when creating {input}", status=res.status());
+        }
+        assert!(read_one("device", &["1"]).is_err(), "Dependents of a replaced expired record weren't deleted by cascade");
+
+        // test rules
+        let req = TestRequest::post().uri("/api/rest/movie")
+                                     .set_payload(r#"{"id": "post_rule", "name": "Natural Born Killers", "recommended": true}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to create a record violating a rule");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        assert!(from_utf8(&res_body).unwrap().contains("recommendedNeedsYear"), "Expected the error to name the violated rule");
+
+        // test references of associations
+        let req = TestRequest::post().uri("/api/rest/casting")
+                                     .set_payload(r#"{"movieId": "post", "actor": "Juliette Lewis"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a record referencing an existing record");
+        let req = TestRequest::post().uri("/api/rest/casting")
+                                     .set_payload(r#"{"movieId": "not_existing_record", "actor": "Juliette Lewis"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to create a record referencing a not existing record");
+
+        // test linking records of many to many associations
+        let req = TestRequest::post().uri("/api/rest/movie/post/cast/Juliette%20Lewis")
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when linking records");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, from_str(r#"{"name": "Juliette Lewis", "born": 1973}"#).unwrap(), "Responded data doesn't match the linked record");
+        let req = TestRequest::post().uri("/api/rest/movie/post/cast/Juliette%20Lewis")
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), conflict("".to_string()).status(), "Mismatching status code when trying to link already linked records");
+        let req = TestRequest::post().uri("/api/rest/actor")
+                                     .set_payload(r#"{"name": "verify"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a record with the key verify");
+        let req = TestRequest::post().uri("/api/rest/movie/post/cast/verify")
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when linking a record with the key verify");
+        for endpoint in ["/api/rest/movie/post/cast/not_existing_record", "/api/rest/movie/not_existing_record/cast/Juliette%20Lewis", "/api/rest/movie/post/actors/Juliette%20Lewis", "/api/rest/movie/post/cast"] {
+            let req = TestRequest::post().uri(endpoint)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to link via the invalid endpoint {endpoint:?}");
+        }
+
+        // test write-only and hashed attributes
+        let req = TestRequest::post().uri("/api/rest/account")
+                                     .set_payload(r#"{"id": 1, "email": "mallory@example.com", "password": "n4tur4l b0rn", "apiToken": "k1ll3rs"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a record with secret attributes");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, from_str(r#"{"id": 1, "email": "mallory@example.com"}"#).unwrap(), "Secret attributes must not be returned");
+        let storage: String = std::fs::read_to_string("./testing/server/server.data.test.json").unwrap();
+        assert!(!storage.contains("n4tur4l b0rn"), "The plaintext of hashed attributes must not be stored");
+
+        for (candidate, status, verified) in [
+            (r#"{"password": "n4tur4l b0rn"}"#, HttpResponse::Ok().finish().status(), Some(true)),
+            (r#"{"password": "wrong"}"#, HttpResponse::Ok().finish().status(), Some(false)),
+            (r#"{"apiToken": "k1ll3rs"}"#, bad_request("".to_string()).status(), None),
+            (r#"{}"#, bad_request("".to_string()).status(), None)
+        ] {
+            let req = TestRequest::post().uri("/api/rest/account/1/verify")
+                                         .set_payload(candidate)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), status, "Mismatching status code when verifying {candidate}");
+            if let Some(verified) = verified {
+                let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+                let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+                assert_eq!(res_data.data.get(&AttrName::try_from("verified").unwrap()), Some(&TrueType::Primitive(Some(TruePrimitiveType::Boolean(verified)))), "Unexpected verification result for {candidate}");
+            }
+        }
+
+        // test invalid endpoints
+        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/1"] {
+            let req = TestRequest::post().uri(endpoint)
+                                         .set_payload("")
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
+        }
+
+        // test invalid body
+        let req = TestRequest::post().uri("/api/rest/movie")
+                                     .set_payload("")
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to request with invalid body");
+
+        post_test();
+    }
+
+    #[actix_web::test]
+    async fn test_rest_api_get() {
+        pre_test();
+
+        let app = init_service(App::new().service(uri_handler_get)).await;
+
+        // test valid request
+        let expected: Record = from_str(r#"
+            {
+                "id": "get",
+                "name": null,
+                "year": null,
+                "actors": null,
+                "recommended": null
+            }
+        "#).unwrap();
+        let req = TestRequest::get().uri("/api/rest/movie/get")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when fetching a record");
+
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
+
+        // test composite primary key
+        let expected: Record = from_str(r#"
+            {
+                "movieId": "get",
+                "actor": "Woody Harrelson",
+                "role": "Mickey Knox"
+            }
+        "#).unwrap();
+        let req = TestRequest::get().uri("/api/rest/casting/get/Woody%20Harrelson")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when fetching a record with composite key");
+
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected for composite key");
+
+        // test linked records of many to many associations from both sides
+        for (endpoint, expected) in [
+            ("/api/rest/movie/get/cast", r#"[{"name": "Woody Harrelson", "born": 1961}]"#),
+            ("/api/rest/actor/Woody%20Harrelson/movies", r#"[{"id": "get", "name": null, "year": null, "actors": null, "recommended": null}]"#)
+        ] {
+            let req = TestRequest::get().uri(endpoint)
+                                        .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert!(res.status().is_success(), "Unexpected error when fetching linked records via {endpoint:?}");
+
+            let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+            let res_data: JsonDataList = from_str(from_utf8(&res_body).unwrap()).unwrap();
+            assert_eq!(res_data.data, from_str::<Vec<Record>>(expected).unwrap(), "Responded linked records via {endpoint:?} don't match the expected");
+        }
+
+        // test history and reading past states
+        let req = TestRequest::get().uri("/api/rest/actor/Woody%20Harrelson/history")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when fetching the history of a record");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonDataList = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        let operations: Vec<String> = res_data.data.iter().map(|entry| entry.get(&AttrName::try_from("operation").unwrap()).unwrap().to_string()).collect();
+        assert_eq!(operations, vec!("create", "update"), "Unexpected history of the record");
+
+        for (as_of, born) in [
+            ("2020-06-01T00:00:00Z", Some(1960)),
+            ("2021-01-01T00:00:00%2B00:00", Some(1961)),
+            ("2019-12-31T23:59:59Z", None)
+        ] {
+            let req = TestRequest::get().uri(format!("/api/rest/actor/Woody%20Harrelson?asOf={as_of}").as_str())
+                                        .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status().is_success(), born.is_some(), "Unexpected status code {status} when reading the record as of {as_of}", status=res.status());
+            if let Some(born) = born {
+                let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+                let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+                assert_eq!(res_data.data.get(&AttrName::try_from("born").unwrap()), Some(&TrueType::Primitive(Some(TruePrimitiveType::Integer(born)))), "Unexpected record as of {as_of}");
+            }
+        }
+
+        // test invalid endpoints
+        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record", "/api/rest/movie/get/get", "/api/rest/casting/get", "/api/rest/movie/get/history", "/api/rest/actor/Woody%20Harrelson?asOf=yesterday"] {
+            let req = TestRequest::get().uri(endpoint)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
+        }
+
+        post_test();
+    }
+
+    #[actix_web::test]
+    async fn test_rest_api_put() {
+        pre_test();
+
+        let app = init_service(App::new().service(uri_handler_put)).await;
+
+        // test valid request
+        let valid_input = r#"
+            {
+                "id": "doesn't matter",
+                "name": "test"
+            }
+        "#;
+        let req = TestRequest::put().uri("/api/rest/movie/put")
+                                    .set_payload(valid_input)
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when updating a valid record");
+
+        let expected: Record = from_str(r#"
+            {
+                "id": "put",
+                "name": "test",
+                "year": null,
+                "actors": null,
+                "recommended": null
+            }
+        "#).unwrap();
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
+
+        // test rules on the merged record
+        for (input, is_valid) in [
+            (r#"{"recommended": true}"#, false),
+            (r#"{"recommended": true, "year": 1994}"#, true),
+            (r#"{"recommended": true, "year": null}"#, false)
+        ] {
+            let req = TestRequest::put().uri("/api/rest/movie/put")
+                                        .set_payload(input)
+                                        .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status().is_success(), is_valid, "Unexpected status code {status} when updating with {input}", status=res.status());
+        }
+
+        // test history of changes
+        let req = TestRequest::put().uri("/api/rest/actor/Juliette%20Lewis")
+                                    .set_payload(r#"{"born": 1975}"#)
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when updating a record with history");
+        let history: Vec<Record> = read_history("actor", &["Juliette Lewis"]).unwrap();
+        let expected: Record = from_str(r#"
+            {
+                "operation": "update",
+                "previous": {"name": "Juliette Lewis", "born": 1973},
+                "record": {"name": "Juliette Lewis", "born": 1975}
+            }
+        "#).unwrap();
+        assert!(expected.iter().all(|(attr, value)| history.last().and_then(|entry| entry.get(attr)) == Some(value)), "The update wasn't recorded in the history");
+
+        // test versions
+        for (if_match, status, etag) in [
+            ("\"2\"", precondition_failed("".to_string()).status(), None),
+            ("not a version", precondition_failed("".to_string()).status(), None),
+            ("\"1\"", HttpResponse::Ok().finish().status(), Some("\"2\"")),
+            ("W/\"2\"", precondition_failed("".to_string()).status(), None),
+            ("*", HttpResponse::Ok().finish().status(), Some("\"3\""))
+        ] {
+            let req = TestRequest::put().uri("/api/rest/award/2")
+                                        .insert_header((IF_MATCH, if_match))
+                                        .set_payload(r#"{"name": "Best Director"}"#)
+                                        .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), status, "Mismatching status code when updating with If-Match {if_match:?}");
+            assert_eq!(res.headers().get(ETAG).map(|value| value.to_str().unwrap()), etag, "Mismatching ETag when updating with If-Match {if_match:?}");
+        }
+        let req = TestRequest::put().uri("/api/rest/movie/put")
+                                    .insert_header((IF_MATCH, "\"1\""))
+                                    .set_payload(r#"{"name": "Natural Born Killers"}"#)
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), precondition_failed("".to_string()).status(), "Mismatching status code when updating a record of an unversioned model with If-Match");
+
+        // test invalid endpoints
+        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record"] {
+            let req = TestRequest::put().uri(endpoint)
+                                        .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
+        }
+
+        post_test();
+    }
+
+    #[actix_web::test]
+    async fn test_rest_api_naming() {
+        pre_test();
+
+        let app = init_service(App::new().service(uri_handler_get).service(uri_handler_post)).await;
+
+        // test the attribute names of the naming policy in responses
+        let req = TestRequest::get().uri("/api/rest/profile/1")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when reading a record of a model with naming policy");
+        let expected: Record = from_str(r#"{"id": 1, "display_name": "Ada", "birth_year": 1990, "home_address": {"zip_code": "12345"}}"#).unwrap();
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Expected the attributes to be named by the naming policy");
+
+        // test the attribute names of the naming policy in requests
+        let req = TestRequest::post().uri("/api/rest/profile")
+                                     .set_payload(r#"{"id": 2, "display_name": "Grace", "home_address": {"zip_code": "54321"}}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a record with the attribute names of the naming policy");
+        let expected: Record = from_str(r#"{"id": 2, "display_name": "Grace", "birth_year": null, "home_address": {"zip_code": "54321"}}"#).unwrap();
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Responded data doesn't match the created record");
+
+        // test the attribute names of the naming policy in errors
+        for (input, expected) in [
+            (r#"{"id": 3, "displayName": "Grace"}"#, r#"Attribute "displayName" has to be named "display_name""#),
+            (r#"{"id": 3}"#, r#"Missing attribute: "display_name""#),
+            (r#"{"id": 3, "display_name": "Grace", "birth_year": "1990"}"#, r#"Wrong type of attribute "birth_year""#),
+            (r#"{"id": 3, "display_name": "Grace", "birth_year": 1800}"#, r#"Constraint violation of attribute "birth_year""#),
+            (r#"{"id": 3, "display_name": "Grace", "home_address": {"zip_code": 12345}}"#, r#"Invalid object attribute "home_address", Wrong type of attribute "zip_code""#),
+            // rule expressions are shown as they are defined
+            (r#"{"id": 3, "display_name": "Grace", "birth_year": 2200}"#, r#"Rule "bornInThePast" violated: birthYear <= 2100"#)
+        ] {
+            let req = TestRequest::post().uri("/api/rest/profile")
+                                         .set_payload(input)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when creating {input}");
+            let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+            let res_error: JsonError = from_str(from_utf8(&res_body).unwrap()).unwrap();
+            assert!(res_error.error.starts_with(expected), "Expected the Error to start with {expected}, got {error}", error=res_error.error);
+        }
+        let req = TestRequest::post().uri("/api/rest/profile")
+                                     .set_payload(r#"{"id": 3, "display_name": "Ada"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), conflict("".to_string()).status(), "Mismatching status code when violating a unique attribute");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_error: JsonError = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert!(res_error.error.contains(r#"["display_name"]"#), "Expected the unique attributes to be named like they are exposed, got {error}", error=res_error.error);
+
+        post_test();
+    }
+
+    #[actix_web::test]
+    async fn test_rest_api_delete() {
+        pre_test();
+
+        let app = init_service(App::new().service(uri_handler_delete).service(uri_handler_get).service(uri_handler_post)).await;
+
+        // test valid request
+        let expected: Record = from_str(r#"
+            {
+                "id": "delete",
+                "name": null,
+                "year": null,
+                "actors": null,
+                "recommended": null
+            }
+        "#).unwrap();
+        let req = TestRequest::delete().uri("/api/rest/movie/delete/cast/Juliette%20Lewis")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when unlinking records");
+        let req = TestRequest::delete().uri("/api/rest/movie/delete/cast/Juliette%20Lewis")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to unlink not linked records");
+        let req = TestRequest::post().uri("/api/rest/movie/delete/cast/Woody%20Harrelson")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when linking records");
+
+        // explicit join records are deleted like other records, invisible ones don't link anything
+        let req = TestRequest::delete().uri("/api/rest/festival/Cannes/program/get")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when unlinking records via an explicit join model");
+        let screening: Record = read_one_with_deleted("screening", &["Cannes", "get"]).unwrap();
+        assert_ne!(screening.get(&AttrName::try_from("deletedAt").unwrap()), Some(&NULL), "The join record wasn't soft deleted");
+        let history: Vec<Record> = read_history("screening", &["Cannes", "get"]).unwrap();
+        assert_eq!(history.last().and_then(|entry| entry.get(&AttrName::try_from("operation").unwrap())), Some(&from_str("\"delete\"").unwrap()), "The unlinking wasn't recorded in the history of the join model");
+        let req = TestRequest::get().uri("/api/rest/festival/Cannes/program")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonDataList = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert!(res_data.data.is_empty(), "Soft deleted join records must not link records");
+        let req = TestRequest::delete().uri("/api/rest/festival/Cannes/program/get")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to unlink records linked by a soft deleted join record");
+        let req = TestRequest::post().uri("/api/rest/festival/Cannes/program/get")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when linking records of a soft deleted join record again");
+        let screening: Record = read_one("screening", &["Cannes", "get"]).unwrap();
+        assert_eq!(screening.get(&AttrName::try_from("deletedAt").unwrap()), Some(&NULL), "The soft deleted join record wasn't restored");
+
+        let req = TestRequest::delete().uri("/api/rest/movie/delete")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when deleting a record");
+
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, expected, "Responded data doesn't match the expected");
+
+        // test referential actions
+        let req = TestRequest::get().uri("/api/rest/casting/delete/Juliette%20Lewis")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Referencing record wasn't deleted by cascade");
+
+        let req = TestRequest::get().uri("/api/rest/award/1")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_eq!(res_data.data, from_str(r#"{"id": 1, "name": "Best Picture", "movieId": null, "version": 2}"#).unwrap(), "Foreign key of referencing record wasn't cleared by setNull");
+
+        // links of the deleted record don't apply to a new record with the same key
+        let req = TestRequest::post().uri("/api/rest/movie")
+                                     .set_payload(r#"{"id": "delete", "name": "Natural Born Killers", "recommended": false}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when creating a record with the key of a deleted one");
+        let req = TestRequest::get().uri("/api/rest/movie/delete/cast")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonDataList = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert!(res_data.data.is_empty(), "Links of the deleted record weren't removed");
+
+        let req = TestRequest::delete().uri("/api/rest/movie/put")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), conflict("".to_string()).status(), "Mismatching status code when trying to delete a record restricted by a referencing record");
+
+        // test soft delete
+        let req = TestRequest::delete().uri("/api/rest/review/2")
+                                    .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert!(res.status().is_success(), "Unexpected error when soft deleting a record");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        let res_data: JsonData = from_str(from_utf8(&res_body).unwrap()).unwrap();
+        assert_ne!(res_data.data.get(&AttrName::try_from("deletedAt").unwrap()), Some(&NULL), "Soft deleted record isn't marked as deleted");
+        let req = TestRequest::post().uri("/api/rest/review")
+                                     .set_payload(r#"{"id": 2, "movieId": "get", "text": "Masterpiece"}"#)
+                                     .to_request();
+        let res: ServiceResponse = call_service(&app, req).await;
+        assert_eq!(res.status(), bad_request("".to_string()).status(), "Mismatching status code when trying to create a record with the key of a soft deleted one");
+        let res_body: BodyBytes = res.into_body().try_into_bytes().unwrap();
+        assert!(from_utf8(&res_body).unwrap().contains("soft deleted"), "Expected the error to name the soft deleted record");
+
+        for (method, endpoint, is_success) in [
+            ("GET", "/api/rest/review/2", false),
+            ("GET", "/api/rest/review/2?withDeleted=true", true),
+            ("DELETE", "/api/rest/review/2", false),
+            ("POST", "/api/rest/review/2/restore", true),
+            ("POST", "/api/rest/review/2/restore", false),
+            ("GET", "/api/rest/review/2", true),
+            ("DELETE", "/api/rest/review/2/purge", false),
+            ("DELETE", "/api/rest/review/2", true),
+            ("DELETE", "/api/rest/review/2/purge", true),
+            ("GET", "/api/rest/review/2?withDeleted=true", false),
+            ("POST", "/api/rest/movie/get/restore", false),
+            // soft deleted records don't restrict the deletion of the records they reference
+            ("DELETE", "/api/rest/movie/put", false),
+            ("DELETE", "/api/rest/review/1", true),
+            ("DELETE", "/api/rest/movie/put", true)
+        ] {
+            let req = match method {
+                "GET" => TestRequest::get(),
+                "POST" => TestRequest::post(),
+                _ => TestRequest::delete()
+            };
+            let res: ServiceResponse = call_service(&app, req.uri(endpoint).to_request()).await;
+            assert_eq!(res.status().is_success(), is_success, "Unexpected status code {status} for {method} {endpoint:?}", status=res.status());
+        }
+
+        // test expired records, which are invisible until they get purged
+        for (method, endpoint, is_success) in [
+            ("GET", "/api/rest/session/active", true),
+            ("GET", "/api/rest/session/expired", false),
+            ("DELETE", "/api/rest/session/expired", false)
+        ] {
+            let req = match method {
+                "GET" => TestRequest::get(),
+                _ => TestRequest::delete()
+            };
+            let res: ServiceResponse = call_service(&app, req.uri(endpoint).to_request()).await;
+            assert_eq!(res.status().is_success(), is_success, "Unexpected status code {status} for {method} {endpoint:?}", status=res.status());
+        }
+        assert_eq!(purge_expired().ok(), Some(1), "Expected the expired record to be purged");
+        assert_eq!(purge_expired().ok(), Some(0), "Expected no expired records after purging");
+
+        // test invalid endpoints
+        for endpoint in ["/api/rest", "/api/rest/", "/api/rest/movie/", "/api/rest/movie/not_existing_record"] {
+            let req = TestRequest::delete().uri(endpoint)
+                                         .to_request();
+            let res: ServiceResponse = call_service(&app, req).await;
+            assert_eq!(res.status(), bad_endpoint().status(), "Mismatching status code when trying to request the invalid endpoint {endpoint:?}");
+        }
+
+        post_test();
+    }
 }
\ No newline at end of file
@@ -1,548 +1,2347 @@
-mod types;
-mod graphql;
-mod storage_handler;
-
-pub mod model_cli;
-
-//used modules
-use crate::cli;
-
-pub use types::*;
-use storage_handler::*;
-
-// used types
-pub use graphql::GraphQLReturn;
-use std::collections::HashMap;
-use graphql::GraphQLPost;
-use serde_json::Value;
-use std::fs::ReadDir;
-use std::path::Path;
-use std::io::{
-    ErrorKind,
-    Result,
-    Error
-};
-use ErrorKind::{
-    InvalidData,
-    NotFound
-};
-
-// used functions
-pub use storage_handler::configure_storages;
-use graphql::handle_gql_post;
-use std::fs::{
-    read_to_string,
-    read_dir
-};
-
-pub fn handle_gql_post_body(body: &str) -> GraphQLReturn {
-    match GraphQLPost::try_from(body) {
-        Ok(post) => handle_gql_post(post),
-        Err(ret) => ret
-    }
-}
-
-pub fn handle_gql_query_arg(query: &str) -> GraphQLReturn {
-    handle_gql_post(GraphQLPost::from(query.to_string()))
-}
-
-pub fn create_one(model_name: &str, json: &str) -> Result<Record> {
-    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
-    name.assert_singularity()?;
-    let model: ModelDefinition = parse_model(name)?;
-    let storage_handler = get_handler(&model)?;
-    let record: Record = add_null_values(parse_record(json, &model)?, &model);
-    storage_handler.create_one(&record)
-}
-
-pub fn read_one(model_name: &str, id: &str) -> Result<Record> {
-    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
-    name.assert_singularity()?;
-    let model: ModelDefinition = parse_model(name)?;
-    let storage_handler = get_handler(&model)?;
-    let true_id: &TrueType = &parse_uri_id(id, &model)?;
-    
-    Ok(add_null_values(storage_handler.read_one(true_id)?, &model))  // for consistency, but may not add any null value because create_one adds them before creation
-}
-
-pub fn update_one(model_name: &str, id: &str, json: &str) -> Result<Record> {
-    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
-    name.assert_singularity()?;
-    let mut model: ModelDefinition = parse_model(name)?;
-    let storage_handler = get_handler(&model)?;
-    let mut required: Vec<AttrName> = model.required;
-
-    // parse record to get its attributes
-    model.required = vec!();
-    let record: Record = parse_record(json, &model)?;
-
-    // update models' required attributes to the necessary
-    required.retain(|a| record.get(a).is_some());
-    model.required.append(&mut required);
-
-    // parse the record again, this time with correct requirement check
-    let mut valid_record: Record = parse_record(json, &model)?;
-    let true_id: TrueType = parse_uri_id(id, &model)?;
-    valid_record.insert(model.primary_key.clone(), true_id);
-    
-    Ok(add_null_values(storage_handler.update_one(&valid_record)?, &model))
-}
-
-pub fn delete_one(model_name: &str, id: &str) -> Result<Record> {
-    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
-    name.assert_singularity()?;
-    let model: ModelDefinition = parse_model(name)?;
-    let storage_handler = get_handler(&model)?;
-    let true_id: &TrueType = &parse_uri_id(id, &model)?;
-    
-    Ok(add_null_values(storage_handler.delete_one(true_id)?, &model))
-}
-
-
-/*
-    parse_uri_id: 
-        Parses the id fetched as &str from URI in server to the respective key type.
-
-    returns:
-        A TrueType representation of the id
-        or an Error if its type doesn't fit to the definition
-*/
-fn parse_uri_id(id: &str, model: &ModelDefinition) -> Result<TrueType> {
-    let key: &AttrName = &model.primary_key;
-    let key_type: &AttrType = model.attributes.get(key).unwrap();
-
-    match key_type {
-        AttrType::Primitive(PrimitiveType::String) => { 
-            Ok(TrueType::Primitive(Some(TruePrimitiveType::String(id.to_string()))))
-        },
-        AttrType::Primitive(other) => {
-            if let Ok(val) = parse::<Value>(id) {
-                Ok(TrueType::Primitive(to_true_prim_type(&val, other, true)?))
-            } else {
-                Err(Error::new(ErrorKind::InvalidData, "Invalid value for primary key"))
-            }
-        },
-
-        // this shouldn't occur, since the model definition should be validated before
-        _ => unreachable!("Model definition has to be valid")
-    }
-}
-
-
-/*
-    parse_model: 
-        Parses a valid model in the given path with the given name.
-
-        What happens exactly:
-            1. fetch all model definitions via parse_models(),
-               this is necessary because of the duplicate filtering
-            2. filter the gotten vector of definitions to matches on the given model name (can be only one)
-            3. return the matching model, else Error when there are none
-
-    returns:
-        A valid model definition with the given model name
-        or an Error if there isn't such model defined
-*/
-pub fn parse_model(model_name: &ModelName) -> Result<ModelDefinition>{
-    if let Some(args) = cli::get_valid_start_args() {
-        let mut models: Vec<ModelDefinition> = parse_models(args.modelspath.as_path())?;
-        models.retain(|m| m.model_name.plural().camel() == model_name.plural().camel());
-        if models.is_empty() {
-            return Err(Error::new(NotFound, format!("model {name:?} not found", name=model_name.0.0)));
-        }
-        return Ok(models.remove(0));
-    }
-    todo!("parsing records is currently only possible when the server is running")
-}
-
-
-/*
-    parse_models: 
-        Parses all valid models in the given path into a vector.
-
-        What happens exactly:
-            1. read the directory structure
-            2. iterate through the directory's entries
-            3. if an entry can be parsed to a valid model definition,
-               push it to the returning vector and memorize the model's name for duplicate checking
-            4. remove all models from the returning vector whose name occurs multiple times
-            5. return the vector of definitions if there are some, else Error
-
-    returns:
-        A vector of valid model definitions, unique by their names
-        or an Error if there aren't some
-*/
-pub fn parse_models(model_path: &Path) -> Result<Vec<ModelDefinition>>{
-    // read directory structure
-    let model_paths: Result<ReadDir> = read_dir(model_path);
-    if model_paths.is_err() {
-        return Err(Error::new(NotFound, "No valid models defined"));
-    }
-
-    let mut models: Vec<ModelDefinition> = vec!();  // stores the parsed valid models
-    let mut model_names: Vec<ModelName> = vec!();  // stores the names of the valid models, just for simpler duplicate checking
-    let mut duplicates: Vec<ModelName> = vec!();  // stores all names of duplicate valid models
-
-    // parse the models
-    for path in model_paths.unwrap().flatten() {
-        // going to parse the file
-        // ignore occuring errors, invalid files will be just ignored
-        if let Ok(data) = read_to_string(&path.path()) {
-            if let Ok(model) = ModelDefinition::try_from(data.as_str()) {
-                if model_names.contains(&model.model_name.plural().camel()) && !duplicates.contains(&model.model_name.plural().camel()) {
-                    duplicates.push(model.model_name.plural().camel());
-                }
-                model_names.push(model.model_name.plural().camel());
-                models.push(model);
-            }
-        }
-    }
-
-    // remove duplicates
-    for dup in &duplicates {
-        models.retain(|m| &m.model_name.plural().camel() != dup);
-    }
-
-    if models.is_empty() {
-        return Err(Error::new(NotFound, "No valid models defined"));
-    }
-    Ok(models)
-}
-
-
-/*
-    parse_record: 
-        Parses a given JSON-String to a Record, appropriate to its given model definition.
-
-        What happens exactly:
-            1. check if JSON-String is valid JSON and parse it
-            2. check if any required attributes are missing,
-               keep in mind that the model definition is valid (impl TryFrom),
-               so the primary key is required as well
-            3. translate the parsed values to their respective type as defined in the model,
-               else return Error
-            4. check if the Record fits the constraints
-            5. return Record
-
-    returns:
-        a valid Record for the definition or an Error
-        if the given String is not a valid JSON representation of such Record
-*/
-fn parse_record(json: &str, model: &ModelDefinition) -> Result<Record> {
-    let parsed_json = parse::<HashMap<AttrName, Value>>(json);
-    
-    // check json
-    if parsed_json.is_err() {
-        return Err(Error::new(InvalidData, "Given JSON-String is not valid JSON"));
-    }
-
-    // check for missing required attributes
-    for key in &model.required {
-        if !parsed_json.as_ref().unwrap().contains_key(key) {
-            return Err(Error::new(InvalidData, format!("Missing attribute: {name:?}", name=key.0)));
-        };
-    }
-
-    let mut record = Record::new();
-
-    // convert parsed_json to Record
-    for (key, value) in parsed_json.unwrap() {
-        let is_required: bool = model.required.contains(&key);
-        if let Some(ty) = model.attributes.get(&key) {
-            match ty {
-                AttrType::Primitive(prim_type) => {
-                    match to_true_prim_type(&value, prim_type, is_required) {
-                        Ok(true_prim_value) => record.insert(key, TrueType::Primitive(true_prim_value)),
-                        Err(err) => return Err(Error::new(InvalidData, format!("Wrong type of attribute {attr:?}, {err}", attr=key.0)))
-                    };
-                },
-                AttrType::Array(arr_type) => {
-                    match value.as_array() {
-                        Some(arr) => {
-                            let mut true_arr: Vec<TruePrimitiveType> = vec!();
-                            for val in arr {
-                                match to_true_prim_type(val, &arr_type[0], true) {
-                                    Ok(true_prim_value) => true_arr.push(true_prim_value.unwrap()),
-                                    Err(err) => return Err(Error::new(InvalidData, format!("Wrong type of array attribute {attr:?}, {err}", attr=key.0)))
-                                };
-                            }
-                            record.insert(key, TrueType::Array(Some(true_arr)));
-                        },
-                        None => {
-                            if !is_required && value.is_null() {
-                                record.insert(key, NULL);
-                            } else {
-                                return Err(Error::new(InvalidData, format!("Wrong type of attribute {attr:?}, expected: Array", attr=key.0)));
-                            }
-                        }
-                    };
-                },
-            }
-        } else {
-            return Err(Error::new(InvalidData, format!("Unknown attribute: {attr:?}", attr=key.0)));
-        }
-    }
-
-    check_constraints(&record)?;
-
-    Ok(record)
-}
-
-fn add_null_values(mut record: Record, model: &ModelDefinition) -> Record {
-    for attr_name in model.attributes.keys() {
-        if !record.contains_key(attr_name) {
-            record.insert(attr_name.clone(), NULL);
-        }
-    }
-    record
-}
-
-fn check_constraints(record: &Record) -> Result<()> {
-    Ok(())
-}
-
-
-
-
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_record() {
-        let valid_input = r#"
-            {
-                "id": 1,
-                "name": "Natural Born Killers",
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        let expected_record = Record::from([
-            (AttrName("id".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
-            (AttrName("name".to_string()),TrueType::Primitive(Some(TruePrimitiveType::String("Natural Born Killers".to_string())))),
-            (AttrName("year".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Integer(1994)))),
-            (AttrName("actors".to_string()),TrueType::Array(Some(vec!(TruePrimitiveType::String("Woody Harrelson".to_string()), TruePrimitiveType::String("Juliette Lewis".to_string()))))),
-            (AttrName("recommended".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Boolean(true))))
-        ]);
-
-        let movie_model = ModelDefinition {
-            model_name: ModelName(AttrName("movie".to_string())),
-            storage_type: StorageType::json,
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
-                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
-                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
-                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
-                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
-            ]),
-            primary_key: AttrName("id".to_string()),
-            required: vec!(
-                AttrName("id".to_string()),
-                AttrName("name".to_string()),
-                AttrName("recommended".to_string())
-            ),
-            constraints: None
-        };
-        let parsed_record: Record = parse_record(valid_input, &movie_model).unwrap();
-        
-        assert_eq!(&parsed_record, &expected_record);
-        
-        // test null values
-        let valid_input = r#"
-            {
-                "id": 1,
-                "name": "Natural Born Killers",
-                "year": null,
-                "actors": null,
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(valid_input, &movie_model).is_ok(),
-            "Unexpected Error for parsing null-values for non-required attributes"
-        );
-
-        // test errors
-        // test String instead of Integer
-        let invalid_input = r#"
-            {
-                "id": "1",
-                "name": "Natural Born Killers",
-                "year": "1994",
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for parsing String-Value to Integer"
-        );
-
-        // test String instead of Boolean 
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "name": "Natural Born Killers",
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": "true"
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for parsing String-Value to Boolean"
-        );
-
-        // test Integer instead of String 
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "name": 1994,
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for parsing Integer-Value to String"
-        );
-
-        // test Array(Integer) instead of Array(String)
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "name": "Natural Born Killers",
-                "year": 1994,
-                "actors": [1, 2],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for parsing Array(Integer)-Value to Array(String)"
-        );
-
-        // test missing attribute
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for missing required attributes"
-        );
-
-        // test null value for required attribute
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "name": null,
-                "year": 1994,
-                "actors": ["Woody Harrelson", "Juliette Lewis"],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for null-valued required attributes"
-        );
-
-        // test null values as array entries
-        let invalid_input = r#"
-            {
-                "id": 1,
-                "name": "Natural Born Killers",
-                "year": 1994,
-                "actors": ["Woody Harrelson", null],
-                "recommended": true
-            }
-        "#;
-        assert!(
-            parse_record(invalid_input, &movie_model).is_err(),
-            "Expected Error for array storing null values"
-        );
-
-        // test invalid json
-        assert!(
-            parse_record("invalid json", &movie_model).is_err(),
-            "Expected Error for parsing invalid JSON input"
-        );
-    }
-
-    #[test]
-    fn test_parse_model() {
-        let movie_model = ModelDefinition {
-            model_name: ModelName(AttrName("movie".to_string())),
-            storage_type: StorageType::json,
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
-                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
-                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
-                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
-                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
-            ]),
-            primary_key: AttrName("id".to_string()),
-            required: vec!(
-                AttrName("id".to_string()),
-                AttrName("name".to_string()),
-                AttrName("recommended".to_string())
-            ),
-            constraints: None
-        };
-
-        let expected_result: ModelDefinition = movie_model;
-        assert_eq!(&parse_model(&ModelName(AttrName("movie".to_string()))).unwrap(), &expected_result);
-
-        // test errors
-        assert!(
-            parse_model(&ModelName(AttrName("not_existing_model".to_string()))).is_err(),
-            "Expected error for no matching model definitions"
-        );
-    }
-
-    #[test]
-    fn test_parse_models() {
-        let movie_model = ModelDefinition {
-            model_name: ModelName(AttrName("movie".to_string())),
-            storage_type: StorageType::json,
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
-                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
-                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
-                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
-                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
-            ]),
-            primary_key: AttrName("id".to_string()),
-            required: vec!(
-                AttrName("id".to_string()),
-                AttrName("name".to_string()),
-                AttrName("recommended".to_string())
-            ),
-            constraints: None
-        };
-
-        let expected_result: Vec<ModelDefinition> = vec![movie_model];
-        assert_eq!(&parse_models(Path::new("./testing/model")).unwrap(), &expected_result);
-
-        // test errors
-        assert!(
-            parse_models(Path::new("./testing/model/not_existing_dir")).is_err(),
-            // test a not existing directory
-            "Expected error for not existing models' path"
-        );
-        assert!(
-            parse_models(Path::new("./testing/model/dummy_dir")).is_err(),
-            // test a directory without any valid model definitions
-            "Expected error for no existing valid model definitions"
-        );
-    }
-}
+mod types;
+mod graphql;
+mod storage_handler;
+mod migration;
+mod rules;
+
+pub mod model_cli;
+
+//used modules
+use crate::cli;
+
+pub use types::*;
+use storage_handler::*;
+
+// used types
+pub use graphql::GraphQLReturn;
+use std::collections::HashMap;
+use graphql::GraphQLPost;
+use rules::Expression;
+use serde_json::Value;
+use serde_json::Map;
+use std::fs::ReadDir;
+use std::sync::{
+    OnceLock,
+    Mutex,
+    Arc
+};
+use std::path::{
+    PathBuf,
+    Path
+};
+use std::fmt::{
+    Formatter,
+    Display
+};
+use uuid::Uuid;
+use ulid::Ulid;
+use chrono::{
+    DateTime,
+    FixedOffset,
+    Duration,
+    SecondsFormat,
+    Utc
+};
+use argon2::Argon2;
+use argon2::password_hash::{
+    rand_core::OsRng,
+    PasswordHasher,
+    PasswordVerifier,
+    PasswordHash,
+    SaltString
+};
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+use ErrorKind::{
+    InvalidData,
+    NotFound
+};
+
+// used functions
+pub use storage_handler::configure_storages;
+pub use migration::{
+    warn_pending_migrations,
+    lock_storages,
+    migrate
+};
+pub use storage_handler::{
+    VersionMismatch,
+    Conflict
+};
+use graphql::handle_gql_post;
+use serde_json::from_value;
+use std::slice::from_ref;
+use std::fs::{
+    read_to_string,
+    read_dir
+};
+
+pub fn handle_gql_post_body(body: &str) -> GraphQLReturn {
+    match GraphQLPost::try_from(body) {
+        Ok(post) => handle_gql_post(post),
+        Err(ret) => ret
+    }
+}
+
+pub fn handle_gql_query_arg(query: &str) -> GraphQLReturn {
+    handle_gql_post(GraphQLPost::from(query.to_string()))
+}
+
+pub fn create_one(model_name: &str, json: &str) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let json: String = internal_names(json, &model)?;
+    create_stored(&model, &json)
+}
+
+// creates a record given with the stored attribute names
+fn create_stored(model: &ModelDefinition, json: &str) -> Result<Record> {
+    let storage_handler = get_handler(model)?;
+    let mut json: String = add_default_values(json, model)?;
+    if let Some(generator) = &model.primary_key_generator {
+        json = add_generated_key(&json, model, generator, &storage_handler)?;
+    }
+    let record: Record = add_expiry(add_timestamps(add_null_values(parse_record(&json, model)?, model), model, None), model);
+    check_rules(&record, model)?;
+
+    // expired records which aren't purged yet don't block their key, soft deleted records do until they get purged,
+    // the expired record is purged right away, so its dependents don't get attached to the new record
+    if model.ttl.is_some() || model.soft_delete {
+        let true_key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| record.get(key_attr).cloned().unwrap_or(NULL)).collect();
+        if let Ok(stored) = storage_handler.read_one(&true_key) {
+            if is_expired(&stored, model) {
+                purge(model, &true_key, stored, None)?;
+            } else if model.soft_delete && is_deleted(&stored) {
+                return Err(Error::new(ErrorKind::AlreadyExists, "A soft deleted record for the given key exists, read it with withDeleted and restore or purge it instead"));
+            }
+        }
+    }
+    let created: Record = storage_handler.create_one(&hash_secrets(record, model)?)?;
+    commit_history(model, "create", None, Some(&created))?;
+    Ok(expose(created, model))
+}
+
+pub fn read_one(model_name: &str, ids: &[&str]) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let storage_handler = get_handler(&model)?;
+    let true_key: &[TrueType] = &parse_uri_key(ids, &model)?;
+    
+    Ok(expose(add_null_values(read_visible(&storage_handler, true_key, &model)?, &model), &model))  // for consistency, but may not add any null value because create_one adds them before creation
+}
+
+pub fn read_one_with_deleted(model_name: &str, ids: &[&str]) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let storage_handler = get_handler(&model)?;
+    let true_key: &[TrueType] = &parse_uri_key(ids, &model)?;
+    let record: Record = storage_handler.read_one(true_key)?;
+    if is_expired(&record, &model) {
+        return Err(Error::new(NotFound, "The record is expired"));
+    }
+    
+    Ok(expose(add_null_values(record, &model), &model))
+}
+
+// reads the visible records of a parsed model matching the filter, which uses the stored attribute names
+fn read_many(model: &ModelDefinition, filter: &Record) -> Result<Vec<Record>> {
+    Ok(read_many_stored(model, filter)?.into_iter().map(|record| expose(add_null_values(record, model), model)).collect())
+}
+
+// like read_many, but returns the records as stored
+fn read_many_stored(model: &ModelDefinition, filter: &Record) -> Result<Vec<Record>> {
+    Ok(
+        get_handler(model)?.read_many(filter)?.into_iter()
+            .filter(|record| (!model.soft_delete || !is_deleted(record)) && !is_expired(record, model))
+            .collect()
+    )
+}
+
+pub fn update_one(model_name: &str, ids: &[&str], json: &str, expected_version: Option<i64>) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    check_versioned(&model, expected_version)?;
+    let storage_handler = get_handler(&model)?;
+    let json: &str = &internal_names(json, &model)?;
+    let true_key: Vec<TrueType> = parse_uri_key(ids, &model)?;
+
+    // the creation time is kept from the stored record and rules apply to the merged record, soft deleted and expired records can't be updated
+    let stored: Option<Record> = if model.timestamps || model.soft_delete || model.history || model.rules.is_some() || model.ttl.is_some() {
+        Some(read_visible(&storage_handler, &true_key, &model)?)
+    } else {
+        None
+    };
+
+    let updated: Record = storage_handler.update_one(&prepare_update(&model, stored.as_ref(), true_key, json, expected_version)?)?;
+    commit_history(&model, "update", stored.as_ref(), Some(&updated))?;
+    Ok(expose(add_null_values(updated, &model), &model))
+}
+
+// records of unversioned models never match an expected version
+fn check_versioned(model: &ModelDefinition, expected_version: Option<i64>) -> Result<()> {
+    match expected_version {
+        Some(expected) if !model.versioned => Err(Error::new(ErrorKind::InvalidInput, VersionMismatch {
+            expected,
+            stored: None
+        })),
+        _ => Ok(())
+    }
+}
+
+/*
+    prepare_update: 
+        Builds the record written by an update of the record with the given key, without writing it.
+        The JSON-String uses the stored attribute names, the stored record is needed for models
+        with timestamps, soft delete, history, rules or time to live.
+
+    returns:
+        the record to pass to the storage handler
+        or an Error if the update isn't valid for the model
+*/
+fn prepare_update(model: &ModelDefinition, stored: Option<&Record>, true_key: Vec<TrueType>, json: &str, expected_version: Option<i64>) -> Result<Record> {
+    let mut model: ModelDefinition = model.clone();
+    let mut required: Vec<AttrName> = model.required;
+
+    // parse record to get its attributes
+    model.required = vec!();
+    let record: Record = parse_record(json, &model)?;
+
+    // update models' required attributes to the necessary
+    required.retain(|a| record.get(a).is_some());
+    model.required.append(&mut required);
+
+    // parse the record again, this time with correct requirement check
+    let mut valid_record: Record = parse_record(json, &model)?;
+    let created_at: Option<TrueType> = stored.and_then(|stored| stored.get(&AttrName(CREATED_AT.to_string())).cloned());
+
+    for (key_attr, true_id) in model.primary_key.attrs().into_iter().zip(true_key) {
+        valid_record.insert(key_attr, true_id);
+    }
+    valid_record = add_timestamps(valid_record, &model, created_at);
+
+    // rules apply to the merged result of the update,
+    // hashed attributes are only known if they are given, the stored ones are hashes,
+    // so rules using hashed attributes which aren't given don't apply
+    if let Some(stored) = stored {
+        let unknown: Vec<AttrName> = model.hashed.iter().flatten()
+            .filter(|hashed| valid_record.get(hashed).is_none_or(|value| value == &TrueType::Primitive(None)))
+            .cloned()
+            .collect();
+        model.rules = rules_without(&model, &unknown)?;
+        let mut merged: Record = stored.iter()
+            .filter(|(attr, _)| !unknown.contains(attr))
+            .map(|(attr, value)| (attr.clone(), value.clone()))
+            .collect();
+        merged.extend(valid_record.clone());
+        check_rules(&merged, &model)?;
+    }
+
+    // the storage handler compares the expected version to the stored one
+    if let (true, Some(version)) = (model.versioned, expected_version) {
+        valid_record.insert(AttrName(VERSION.to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(version))));
+    }
+
+    hash_secrets(valid_record, &model)
+}
+
+pub fn delete_one(model_name: &str, ids: &[&str], expected_version: Option<i64>) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    check_versioned(&model, expected_version)?;
+    delete_stored(&model, &parse_uri_key(ids, &model)?, expected_version)
+}
+
+// deletes the record of a parsed model with the given key
+fn delete_stored(model: &ModelDefinition, true_key: &[TrueType], expected_version: Option<i64>) -> Result<Record> {
+    let storage_handler = get_handler(model)?;
+    let mut record: Record = read_visible(&storage_handler, true_key, model)?;
+
+    // soft deleted records are just marked, referential actions are applied when they get purged
+    if model.soft_delete {
+        let previous: Record = record.clone();
+        let created_at: Option<TrueType> = record.get(&AttrName(CREATED_AT.to_string())).cloned();
+        record = add_timestamps(record, model, created_at);
+        record.insert(AttrName(DELETED_AT.to_string()), now());
+        if let (true, Some(version)) = (model.versioned, expected_version) {
+            record.insert(AttrName(VERSION.to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(version))));
+        }
+        let deleted: Record = storage_handler.update_one(&record)?;
+        commit_history(model, "delete", Some(&previous), None)?;
+        return Ok(expose(add_null_values(deleted, model), model));
+    }
+
+    purge(model, true_key, record, expected_version)
+}
+
+pub fn restore_one(model_name: &str, ids: &[&str]) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    restore_stored(&model, &parse_uri_key(ids, &model)?)
+}
+
+// restores the soft deleted record of a parsed model with the given key
+fn restore_stored(model: &ModelDefinition, true_key: &[TrueType]) -> Result<Record> {
+    let storage_handler = get_handler(model)?;
+    let mut record: Record = storage_handler.read_one(true_key)?;
+
+    if !model.soft_delete || !is_deleted(&record) {
+        return Err(Error::new(InvalidData, "Only soft deleted records can be restored"));
+    }
+    let previous: Record = record.clone();
+    let created_at: Option<TrueType> = record.get(&AttrName(CREATED_AT.to_string())).cloned();
+    record = add_timestamps(record, model, created_at);
+    record.insert(AttrName(DELETED_AT.to_string()), NULL);
+    let restored: Record = storage_handler.update_one(&record)?;
+    commit_history(model, "update", Some(&previous), Some(&restored))?;
+    Ok(expose(add_null_values(restored, model), model))
+}
+
+pub fn purge_one(model_name: &str, ids: &[&str]) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let storage_handler = get_handler(&model)?;
+    let true_key: &[TrueType] = &parse_uri_key(ids, &model)?;
+    let record: Record = storage_handler.read_one(true_key)?;
+
+    if !model.soft_delete || !is_deleted(&record) {
+        return Err(Error::new(InvalidData, "Only soft deleted records can be purged"));
+    }
+    purge(&model, true_key, record, None)
+}
+
+/*
+    purge: 
+        Deletes a record permanently after applying the referential actions of the associations referencing it.
+
+        What happens exactly:
+            1. collect the referential actions, a restriction prevents any changes
+            2. prepare the updates of setNull like other updates, so their rules, timestamps and history apply
+            3. delete the record itself, a version mismatch prevents any changes too
+            4. apply the actions, if one fails the already applied ones are reverted
+            5. add the history entries of all changed records, if one fails all changes are reverted
+
+    returns:
+        the deleted record or an Error if it is restricted by a referencing record,
+        its version doesn't match the expected one or the storage fails
+*/
+fn purge(model: &ModelDefinition, true_key: &[TrueType], record: Record, expected_version: Option<i64>) -> Result<Record> {
+    let models: Vec<ModelDefinition> = parse_all_models()?;
+    let mut actions: Vec<DeleteAction> = vec!(DeleteAction::Delete(model.clone(), true_key.to_vec(), record.clone()));
+    collect_delete_actions(model, &record, &models, &mut actions)?;
+
+    let mut staged: Vec<(&ModelDefinition, &Record, Option<Record>)> = vec!();  // the model, the stored record and the updated one, None for deletions
+    for action in &actions {
+        match action {
+            DeleteAction::Delete(dependent, _, stored) => staged.push((dependent, stored, None)),
+            DeleteAction::SetNull(dependent, key, stored, foreign_keys) => {
+                // records which get deleted anyway don't need to be cleared
+                if actions.iter().any(|other| matches!(other, DeleteAction::Delete(deleted, deleted_key, _) if deleted.model_name == dependent.model_name && deleted_key == key)) {
+                    continue;
+                }
+                let cleared: Map<String, Value> = foreign_keys.iter().map(|foreign_key| (foreign_key.0.clone(), Value::Null)).collect();
+                staged.push((dependent, stored, Some(prepare_update(dependent, Some(stored), key.clone(), &Value::Object(cleared).to_string(), None)?)));
+            }
+        }
+    }
+
+    let mut applied: Vec<(&ModelDefinition, &Record, Option<Record>)> = vec!();
+    for (index, (dependent, stored, update)) in staged.iter().enumerate() {
+        let key: Vec<TrueType> = dependent.primary_key.attrs().iter().map(|key_attr| stored.get(key_attr).cloned().unwrap_or(NULL)).collect();
+        let result: Result<Option<Record>> = get_handler(dependent).and_then(|handler| match update {
+            Some(update) => handler.update_one(update).map(Some),
+            None => handler.delete_one(&key, if index == 0 { expected_version } else { None }).map(|_| None)
+        });
+        match result {
+            Ok(updated) => applied.push((dependent, stored, updated)),
+            Err(err) => return Err(revert(&applied, &[], err))
+        }
+    }
+
+    let mut entries: Vec<(&ModelDefinition, Record)> = vec!();
+    for (dependent, stored, updated) in &applied {
+        let operation: &str = if updated.is_some() { "update" } else { "delete" };
+        match add_history_entry(dependent, operation, Some(stored), updated.as_ref()) {
+            Ok(entry) => entries.extend(entry.map(|entry| (*dependent, entry))),
+            Err(err) => return Err(revert(&applied, &entries, err))
+        }
+    }
+
+    Ok(expose(add_null_values(record, model), model))
+}
+
+/*
+    revert: 
+        Reverts the applied changes of a failed purge in reverse order, after removing their history entries.
+
+    returns:
+        the Error of the purge, extended by the first Error of reverting if the changes can't be reverted
+*/
+fn revert(applied: &[(&ModelDefinition, &Record, Option<Record>)], entries: &[(&ModelDefinition, Record)], err: Error) -> Error {
+    let reverted: Result<()> = entries.iter().try_for_each(|(model, entry)| remove_history_entry(model, entry))
+        .and_then(|_| applied.iter().rev().try_for_each(|(model, stored, _)| {
+            let key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| stored.get(key_attr).cloned().unwrap_or(NULL)).collect();
+            revert_change(model, &key, Some(stored))
+        }));
+    match reverted {
+        Ok(_) => err,
+        Err(revert_err) => Error::other(format!("{err}, reverting the applied changes failed: {revert_err}"))
+    }
+}
+
+/*
+    revert_change: 
+        Reverts a written change of the record with the given key,
+        the previous record is stored again or the record is deleted if there wasn't one.
+        Attributes added by the change are cleared, the versions of reverted records of versioned models keep counting,
+        re-created records continue from their previous version, so ETags of the deleted record don't become valid again.
+
+    returns:
+        Empty tuple if the change is reverted, else Error
+*/
+fn revert_change(model: &ModelDefinition, true_key: &[TrueType], previous: Option<&Record>) -> Result<()> {
+    let handler = get_handler(model)?;
+    let Some(previous) = previous else {
+        return handler.delete_one(true_key, None).map(|_| ());
+    };
+    match handler.read_one(true_key) {
+        Ok(current) => {
+            let mut previous: Record = previous.clone();
+            previous.remove(&AttrName(VERSION.to_string()));  // no expected version
+            for attr in current.into_keys().filter(|attr| attr.0 != VERSION) {
+                previous.entry(attr).or_insert(NULL);
+            }
+            handler.update_one(&previous)?;
+        },
+        Err(err) if err.kind() == NotFound => { handler.create_one(previous)?; },
+        Err(err) => return Err(err)
+    }
+    Ok(())
+}
+
+/*
+    purge_expired: 
+        Deletes the expired records of all models with a time to live permanently,
+        applying the referential actions of the associations referencing them.
+        Records which can't be purged, e.g. because of a restriction, are kept until the next run.
+
+    returns:
+        the number of purged records or an Error listing the records which couldn't be purged
+*/
+pub fn purge_expired() -> Result<usize> {
+    let mut purged: usize = 0;
+    let mut failures: Vec<String> = vec!();
+    for model in parse_all_models()?.into_iter().filter(|model| model.ttl.is_some()) {
+        for record in get_handler(&model)?.read_many(&Record::new())? {
+            if !is_expired(&record, &model) {
+                continue;
+            }
+            let true_key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| record.get(key_attr).cloned().unwrap_or(NULL)).collect();
+            match purge(&model, &true_key, record, None) {
+                Ok(_) => purged += 1,
+                // records purged as dependents of an other expired record are already gone
+                Err(err) if err.kind() == NotFound => (),
+                Err(err) => failures.push(format!("{model} {key}: {err}", model=model.model_name.0.0, key=true_key.iter().map(TrueType::to_string).collect::<Vec<String>>().join("/")))
+            }
+        }
+    }
+    if !failures.is_empty() {
+        return Err(Error::other(format!("Expired records couldn't be purged, {failures}", failures=failures.join(", "))));
+    }
+    Ok(purged)
+}
+
+/*
+    read_visible: 
+        Reads a record which is neither soft deleted nor expired.
+
+    returns:
+        the record or an Error with ErrorKind::NotFound if it doesn't exist, is soft deleted or expired
+*/
+fn read_visible(storage_handler: &impl StorageHandler, true_key: &[TrueType], model: &ModelDefinition) -> Result<Record> {
+    let record: Record = storage_handler.read_one(true_key)?;
+    if model.soft_delete && is_deleted(&record) {
+        return Err(Error::new(NotFound, "The record is deleted"));
+    }
+    if is_expired(&record, model) {
+        return Err(Error::new(NotFound, "The record is expired"));
+    }
+    Ok(record)
+}
+
+fn is_deleted(record: &Record) -> bool {
+    record.get(&AttrName(DELETED_AT.to_string())).is_some_and(|deleted_at| deleted_at != &NULL)
+}
+
+// records without expiry time never expire
+fn is_expired(record: &Record, model: &ModelDefinition) -> bool {
+    match model.expiry_attr().and_then(|expiry_attr| record.get(&expiry_attr)) {
+        Some(TrueType::Primitive(Some(TruePrimitiveType::String(expiry)))) => DateTime::parse_from_rfc3339(expiry).is_ok_and(|expiry| expiry <= Utc::now()),
+        _ => false
+    }
+}
+
+/*
+    read_history: 
+        Reads the history of a record, which is kept even after the record got deleted.
+
+    returns:
+        the history entries in the order of the changes,
+        or an Error if the model has no history or there are no entries for the key
+*/
+pub fn read_history(model_name: &str, ids: &[&str]) -> Result<Vec<Record>> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    read_history_of(&model, &parse_uri_key(ids, &model)?)
+}
+
+// like read_history, but for a parsed model and the key of the record
+fn read_history_of(model: &ModelDefinition, key: &[TrueType]) -> Result<Vec<Record>> {
+    let mut entries: Vec<Record> = history_entries(model, key)?;
+    for entry in &mut entries {
+        for field in ["previous", "record"] {
+            if let Some(TrueType::Object(Some(record))) = entry.remove(&AttrName(field.to_string())) {
+                entry.insert(AttrName(field.to_string()), TrueType::Object(Some(expose(record, model))));
+            } else {
+                entry.insert(AttrName(field.to_string()), TrueType::Object(None));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/*
+    read_one_as_of: 
+        Reads a record like it was at the given time, using the history of its model.
+
+    returns:
+        the record or an Error if the time isn't a valid DateTime, the model has no history
+        or the record didn't exist at that time
+*/
+pub fn read_one_as_of(model_name: &str, ids: &[&str], as_of: &str) -> Result<Record> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let as_of: DateTime<FixedOffset> = DateTime::parse_from_rfc3339(as_of)
+        .map_err(|_| Error::new(InvalidData, "expected: DateTime, formatted as RFC 3339 with timezone"))?;
+
+    // the latest change up to the given time holds the record, unless it got deleted
+    let latest: Option<Record> = history_entries(&model, &parse_uri_key(ids, &model)?)?.into_iter()
+        .rev()
+        .find(|entry| entry_time(entry).is_some_and(|at| at <= as_of));
+    match latest.and_then(|mut entry| entry.remove(&AttrName("record".to_string()))) {
+        Some(TrueType::Object(Some(record))) => Ok(expose(add_null_values(record, &model), &model)),
+        _ => Err(Error::new(NotFound, format!("The record didn't exist at {as_of}")))
+    }
+}
+
+fn history_entries(model: &ModelDefinition, true_key: &[TrueType]) -> Result<Vec<Record>> {
+    if !model.history {
+        return Err(Error::new(InvalidData, format!("The model {name:?} has no history", name=model.model_name.0.0)));
+    }
+    let filter: Record = Record::from([(AttrName("key".to_string()), history_key(true_key))]);
+    let mut entries: Vec<Record> = get_handler(&model.history_model())?.read_many(&filter)?;
+    if entries.is_empty() {
+        return Err(Error::new(NotFound, "The record has no history"));
+    }
+    entries.sort_by_key(|entry| match entry.get(&AttrName("sequence".to_string())) {
+        Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(sequence)))) => *sequence,
+        _ => 0
+    });
+    Ok(entries)
+}
+
+fn entry_time(entry: &Record) -> Option<DateTime<FixedOffset>> {
+    match entry.get(&AttrName("at".to_string())) {
+        Some(TrueType::Primitive(Some(TruePrimitiveType::String(at)))) => DateTime::parse_from_rfc3339(at).ok(),
+        _ => None
+    }
+}
+
+// keys of history entries are stored as Strings, so they don't depend on the types of the key attributes
+fn history_key(true_key: &[TrueType]) -> TrueType {
+    TrueType::Array(Some(true_key.iter().map(|value| TruePrimitiveType::String(value.to_string())).collect()))
+}
+
+/*
+    add_history_entry: 
+        Appends a change of a record to the history of its model, if history is enabled,
+        the previous record is None for creations and the record is None for deletions.
+
+    returns:
+        the stored entry, None without history, or an Error if the entry can't be stored
+*/
+fn add_history_entry(model: &ModelDefinition, operation: &str, previous: Option<&Record>, record: Option<&Record>) -> Result<Option<Record>> {
+    if !model.history {
+        return Ok(None);
+    }
+    add_history_entry_to(&get_handler(&model.history_model())?, model, operation, previous, record).map(Some)
+}
+
+// like add_history_entry, but with the storage handler of the history model, so it works without running server too
+fn add_history_entry_to(storage_handler: &impl StorageHandler, model: &ModelDefinition, operation: &str, previous: Option<&Record>, record: Option<&Record>) -> Result<Record> {
+    let changed: &Record = record.or(previous).unwrap();
+    let true_key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| changed.get(key_attr).cloned().unwrap_or(NULL)).collect();
+    let entry: Record = Record::from([
+        (AttrName("sequence".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(storage_handler.next_sequence()?)))),
+        (AttrName("key".to_string()), history_key(&true_key)),
+        (AttrName("operation".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String(operation.to_string())))),
+        (AttrName("at".to_string()), now()),
+        (AttrName("previous".to_string()), TrueType::Object(previous.cloned())),
+        (AttrName("record".to_string()), TrueType::Object(record.cloned()))
+    ]);
+    storage_handler.create_one(&entry)
+}
+
+fn remove_history_entry(model: &ModelDefinition, entry: &Record) -> Result<()> {
+    let sequence: &TrueType = entry.get(&AttrName("sequence".to_string())).unwrap_or(&NULL);
+    get_handler(&model.history_model())?.delete_one(from_ref(sequence), None).map(|_| ())
+}
+
+/*
+    commit_history: 
+        Adds the history entry of a written change like add_history_entry,
+        if that fails the change is reverted, so no change is stored without its history entry.
+
+    returns:
+        Empty tuple or the Error of the history entry
+*/
+fn commit_history(model: &ModelDefinition, operation: &str, previous: Option<&Record>, record: Option<&Record>) -> Result<()> {
+    if let Err(err) = add_history_entry(model, operation, previous, record) {
+        let changed: &Record = record.or(previous).unwrap();
+        let true_key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| changed.get(key_attr).cloned().unwrap_or(NULL)).collect();
+        return match revert_change(model, &true_key, previous) {
+            Ok(_) => Err(err),
+            Err(revert_err) => Err(Error::other(format!("{err}, reverting the change failed: {revert_err}")))
+        };
+    }
+    Ok(())
+}
+
+/*
+    verify_one: 
+        Verifies the given values of hashed attributes against the hashes stored for a record.
+
+    returns:
+        true if all values match, false if any doesn't,
+        or an Error if the record doesn't exist or the values aren't Strings of hashed attributes
+*/
+pub fn verify_one(model_name: &str, ids: &[&str], json: &str) -> Result<bool> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let model: ModelDefinition = parse_model(name)?;
+    let storage_handler = get_handler(&model)?;
+    let true_key: &[TrueType] = &parse_uri_key(ids, &model)?;
+    let candidates: HashMap<AttrName, Value> = match parse::<HashMap<AttrName, Value>>(&internal_names(json, &model)?) {
+        Ok(candidates) => candidates,
+        Err(_) => return Err(Error::new(InvalidData, "Given JSON-String is not valid JSON"))
+    };
+    if candidates.is_empty() {
+        return Err(Error::new(InvalidData, "Expected the values of hashed attributes to verify"));
+    }
+    let record: Record = read_visible(&storage_handler, true_key, &model)?;
+
+    for (attr, candidate) in candidates {
+        if !model.hashed.iter().flatten().any(|hashed| hashed == &attr) {
+            return Err(Error::new(InvalidData, format!("Attribute {attr:?} isn't hashed", attr=model.exposed_name(&attr).0)));
+        }
+        let Some(candidate) = candidate.as_str() else {
+            return Err(Error::new(InvalidData, format!("expected: String for attribute {attr:?}", attr=model.exposed_name(&attr).0)));
+        };
+        let verified: bool = match record.get(&attr) {
+            Some(TrueType::Primitive(Some(TruePrimitiveType::String(hash)))) => PasswordHash::new(hash)
+                .is_ok_and(|hash| Argon2::default().verify_password(candidate.as_bytes(), &hash).is_ok()),
+            _ => false
+        };
+        if !verified {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/*
+    key_length: 
+        Gets the number of primary key attributes of a model,
+        which is the number of URI segments needed to address one of its records.
+
+    returns:
+        the number of key attributes or an Error if the model doesn't exist
+*/
+pub fn key_length(model_name: &str) -> Result<usize> {
+    let model: ModelDefinition = parse_model(&ModelName(AttrName::try_from(model_name)?))?;
+    Ok(model.primary_key.attrs().len())
+}
+
+pub fn read_linked(model_name: &str, ids: &[&str], association_name: &str) -> Result<Vec<Record>> {
+    read_link(&parse_link(model_name, ids, association_name)?)
+}
+
+fn read_link(link: &Link) -> Result<Vec<Record>> {
+    let other_handler = get_handler(&link.other)?;
+    let filter: Record = Record::from([(link.association.foreign_key.clone(), link.key.clone())]);
+
+    let mut records: Vec<Record> = vec!();
+    // soft deleted and expired join records don't link anything
+    for join_record in read_many_stored(&link.join, &filter)? {
+        let other_key: &TrueType = join_record.get(&link.other_key).unwrap_or(&NULL);
+        // links to deleted records are skipped
+        if let Ok(record) = read_visible(&other_handler, from_ref(other_key), &link.other) {
+            records.push(expose(add_null_values(record, &link.other), &link.other));
+        }
+    }
+    Ok(records)
+}
+
+pub fn link_one(model_name: &str, ids: &[&str], association_name: &str, other_id: &str) -> Result<Record> {
+    let link: Link = parse_link(model_name, ids, association_name)?;
+    let join_handler = get_handler(&link.join)?;
+    let other_handler = get_handler(&link.other)?;
+    let other_key: Vec<TrueType> = parse_uri_key(&[other_id], &link.other)?;
+    let other_record: Record = read_visible(&other_handler, &other_key, &link.other)?;
+
+    let join_record: Record = Record::from([
+        (link.association.foreign_key.clone(), link.key.clone()),
+        (link.other_key.clone(), other_key[0].clone())
+    ]);
+    if !read_many_stored(&link.join, &join_record)?.is_empty() {
+        return Err(Error::new(ErrorKind::AlreadyExists, Conflict("The records are already linked".to_string())));
+    }
+    // a soft deleted join record of the same records is restored instead of blocking its key
+    let deleted: Option<Record> = join_handler.read_many(&join_record)?.into_iter().find(|record| link.join.soft_delete && is_deleted(record) && !is_expired(record, &link.join));
+    match (&link.association.through, deleted) {
+        (_, Some(deleted)) => {
+            let join_key: Vec<TrueType> = link.join.primary_key.attrs().iter().map(|key_attr| deleted.get(key_attr).cloned().unwrap_or(NULL)).collect();
+            restore_stored(&link.join, &join_key)?;
+        },
+        // explicit join models are created like any other record, so their definition applies
+        (Some(through), None) => { create_one(&through.singular().0.0, &serde_json::to_string(&expose_names(join_record, link.join.naming.as_ref()))?)?; },
+        (None, None) => { join_handler.create_one(&join_record)?; }
+    }
+
+    Ok(expose(add_null_values(other_record, &link.other), &link.other))
+}
+
+pub fn unlink_one(model_name: &str, ids: &[&str], association_name: &str, other_id: &str) -> Result<Record> {
+    let link: Link = parse_link(model_name, ids, association_name)?;
+    let join_handler = get_handler(&link.join)?;
+    let other_handler = get_handler(&link.other)?;
+    let other_key: Vec<TrueType> = parse_uri_key(&[other_id], &link.other)?;
+    let other_record: Record = read_visible(&other_handler, &other_key, &link.other)?;
+
+    let filter: Record = Record::from([
+        (link.association.foreign_key.clone(), link.key.clone()),
+        (link.other_key.clone(), other_key[0].clone())
+    ]);
+    let join_records: Vec<Record> = read_many_stored(&link.join, &filter)?;
+    if join_records.is_empty() {
+        return Err(Error::new(NotFound, "The records aren't linked"));
+    }
+    for join_record in join_records {
+        let join_key: Vec<TrueType> = link.join.primary_key.attrs().iter().map(|key_attr| join_record.get(key_attr).cloned().unwrap_or(NULL)).collect();
+        // explicit join models are deleted like any other record, so their history, soft delete and referential actions apply
+        match &link.association.through {
+            Some(_) => { delete_stored(&link.join, &join_key, None)?; },
+            None => { join_handler.delete_one(&join_key, None)?; }
+        }
+    }
+
+    Ok(expose(add_null_values(other_record, &link.other), &link.other))
+}
+
+
+/*
+    Link: 
+        The parsed manyToMany association of a specific record.
+*/
+struct Link {
+    association: Association,
+    other_key: AttrName,
+    other: ModelDefinition,
+    join: ModelDefinition,
+    key: TrueType
+}
+
+/*
+    parse_link: 
+        Parses the manyToMany association with the given name of the record with the given key.
+
+        What happens exactly:
+            1. parse the model and check if the record exists
+            2. find the association, it has to be a manyToMany association
+            3. parse the associated model, it needs a single primary key
+            4. parse the explicit join model and check if it contains the foreign and the other key,
+               or build the implicit join model
+
+    returns:
+        the Link of the record
+        or an Error if the record, the association or one of the models doesn't exist or doesn't fit
+*/
+fn parse_link(model_name: &str, ids: &[&str], association_name: &str) -> Result<Link> {
+    let name: &ModelName = &ModelName(AttrName::try_from(model_name)?);
+    name.assert_singularity()?;
+    let models: Vec<ModelDefinition> = parse_all_models()?;
+    let model: &ModelDefinition = find_model(&models, name)?;
+    let true_key: Vec<TrueType> = parse_uri_key(ids, model)?;
+    read_visible(&get_handler(model)?, &true_key, model)?;
+
+    link_of(&models, model, true_key.into_iter().next().unwrap(), &AttrName(association_name.to_string()))
+}
+
+// like parse_link, but for parsed models and without checking if the record exists
+fn link_of(models: &[ModelDefinition], model: &ModelDefinition, key: TrueType, association_name: &AttrName) -> Result<Link> {
+    let association: Association = match model.associations.as_ref().and_then(|associations| associations.get(association_name)) {
+        Some(association) if association.kind == AssociationKind::ManyToMany => association.clone(),
+        _ => return Err(Error::new(NotFound, format!("manyToMany association {name:?} not found", name=association_name.0)))
+    };
+    let other_key: AttrName = association.other_key.clone().unwrap();  // the model definition is valid
+
+    let other: ModelDefinition = find_model(models, &association.model)?.clone();
+    if other.primary_key.attrs().len() > 1 {
+        return Err(Error::new(InvalidData, "Models with composite primary key can't be linked"));
+    }
+
+    let join: ModelDefinition = match &association.through {
+        Some(through) => {
+            let join: ModelDefinition = find_model(models, through)?.clone();
+            if !join.attributes.contains_key(&association.foreign_key) || !join.attributes.contains_key(&other_key) {
+                return Err(Error::new(InvalidData, format!("The join model {through:?} needs the attributes {foreign_key:?} and {other_key:?}", through=through.0.0, foreign_key=association.foreign_key.0, other_key=other_key.0)));
+            }
+            join
+        },
+        None => association.implicit_join_model(model, &other)
+    };
+
+    Ok(Link {
+        association,
+        other_key,
+        other,
+        join,
+        key
+    })
+}
+
+/*
+    read_association: 
+        Reads the records associated to a record of the given model by the association with the given name,
+        the value is the foreign key of the record for belongsTo associations, else its primary key.
+        The models are parsed already, so resolving the associations of many records doesn't parse them again.
+
+    returns:
+        the associated records, at most one for belongsTo associations,
+        or an Error if the association or one of the models doesn't exist
+*/
+fn read_association(models: &[ModelDefinition], model: &ModelDefinition, association_name: &AttrName, value: &TrueType) -> Result<Vec<Record>> {
+    let association: &Association = match model.associations.as_ref().and_then(|associations| associations.get(association_name)) {
+        Some(association) => association,
+        None => return Err(Error::new(NotFound, format!("association {name:?} not found", name=association_name.0)))
+    };
+    if value == &NULL {
+        return Ok(vec!());
+    }
+    match association.kind {
+        AssociationKind::BelongsTo => {
+            let other: &ModelDefinition = find_model(models, &association.model)?;
+            match read_visible(&get_handler(other)?, from_ref(value), other) {
+                Ok(record) => Ok(vec!(expose(add_null_values(record, other), other))),
+                Err(err) if err.kind() == NotFound => Ok(vec!()),
+                Err(err) => Err(err)
+            }
+        },
+        AssociationKind::HasMany => read_many(find_model(models, &association.model)?, &Record::from([(association.foreign_key.clone(), value.clone())])),
+        AssociationKind::ManyToMany => read_link(&link_of(models, model, value.clone(), association_name)?)
+    }
+}
+
+
+/*
+    DeleteAction: 
+        A change of a referencing record, caused by the deletion of the referenced record.
+        Both hold the model, the key and the stored record, setNull the foreign keys to clear as well.
+*/
+enum DeleteAction {
+    Delete(ModelDefinition, Vec<TrueType>, Record),
+    SetNull(ModelDefinition, Vec<TrueType>, Record, Vec<AttrName>)
+}
+
+/*
+    collect_delete_actions: 
+        Collects the referential actions needed to delete the given record.
+
+        What happens exactly:
+            1. find all belongsTo associations of all models referencing the record's model with an on_delete action
+            2. read the records referencing the given record via the association's foreign key
+            3. refuse if visible ones exist and the action is restrict,
+               clear their foreign keys if it is setNull,
+               delete them if it is cascade and collect their actions recursively
+            4. find all manyToMany associations from or to the record's model
+               and delete the records of their join models linking the given record
+
+    returns:
+        Empty tuple if the record may be deleted
+        or an Error containing Conflict if a referencing record restricts the deletion
+*/
+fn collect_delete_actions(model: &ModelDefinition, record: &Record, models: &[ModelDefinition], actions: &mut Vec<DeleteAction>) -> Result<()> {
+    // belongsTo associations always reference a single primary key
+    let key_value: &TrueType = match model.primary_key.attrs().as_slice() {
+        [key_attr] => record.get(key_attr).unwrap_or(&NULL),
+        _ => return Ok(())
+    };
+
+    for dependent in models {
+        for (name, association) in dependent.associations.iter().flatten() {
+            let on_delete: &OnDelete = match &association.on_delete {
+                Some(on_delete) if association.kind == AssociationKind::BelongsTo && association.model.plural().camel() == model.model_name.plural().camel() => on_delete,
+                _ => continue
+            };
+            let filter: Record = Record::from([(association.foreign_key.clone(), key_value.clone())]);
+            for dependent_record in get_handler(dependent)?.read_many(&filter)? {
+                let dependent_key: Vec<TrueType> = dependent.primary_key.attrs().iter().map(|key_attr| dependent_record.get(key_attr).cloned().unwrap_or(NULL)).collect();
+                match on_delete {
+                    // invisible dependents don't restrict, they keep their reference until they get purged
+                    OnDelete::Restrict if (dependent.soft_delete && is_deleted(&dependent_record)) || is_expired(&dependent_record, dependent) => (),
+                    OnDelete::Restrict => return Err(Error::other(Conflict(format!("The record is still referenced by association {name:?} of model {dependent_name:?}", name=name.0, dependent_name=dependent.model_name.0.0)))),
+                    OnDelete::SetNull => {
+                        // a record referencing via multiple associations gets cleared at once
+                        let cleared: Option<&mut Vec<AttrName>> = actions.iter_mut().find_map(|action| match action {
+                            DeleteAction::SetNull(other, key, _, foreign_keys) if other.model_name == dependent.model_name && key == &dependent_key => Some(foreign_keys),
+                            _ => None
+                        });
+                        match cleared {
+                            Some(foreign_keys) => foreign_keys.push(association.foreign_key.clone()),
+                            None => actions.push(DeleteAction::SetNull(dependent.clone(), dependent_key, dependent_record, vec!(association.foreign_key.clone())))
+                        }
+                    },
+                    OnDelete::Cascade => collect_delete(dependent, dependent_record, models, actions)?
+                }
+            }
+        }
+
+        // links to the record are removed, implicit join models are shared by both sides
+        for association in dependent.associations.iter().flatten().map(|(_, association)| association).filter(|association| association.kind == AssociationKind::ManyToMany) {
+            let other: &ModelDefinition = find_model(models, &association.model)?;
+            let join: ModelDefinition = match &association.through {
+                Some(through) => find_model(models, through)?.clone(),
+                None => association.implicit_join_model(dependent, other)
+            };
+            let mut join_attrs: Vec<&AttrName> = vec!();
+            if dependent.model_name.plural().camel() == model.model_name.plural().camel() {
+                join_attrs.push(&association.foreign_key);
+            }
+            if other.model_name.plural().camel() == model.model_name.plural().camel() {
+                join_attrs.extend(association.other_key.as_ref());
+            }
+            for join_attr in join_attrs {
+                for join_record in get_handler(&join)?.read_many(&Record::from([(join_attr.clone(), key_value.clone())]))? {
+                    collect_delete(&join, join_record, models, actions)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// collects the deletion of a record and its referential actions, records referenced in circles are deleted only once, including the initially deleted one
+fn collect_delete(model: &ModelDefinition, record: Record, models: &[ModelDefinition], actions: &mut Vec<DeleteAction>) -> Result<()> {
+    let key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| record.get(key_attr).cloned().unwrap_or(NULL)).collect();
+    if actions.iter().any(|action| matches!(action, DeleteAction::Delete(other, other_key, _) if other.model_name == model.model_name && other_key == &key)) {
+        return Ok(());
+    }
+    actions.push(DeleteAction::Delete(model.clone(), key, record.clone()));
+    collect_delete_actions(model, &record, models, actions)
+}
+
+/*
+    parse_uri_key: 
+        Parses the ids fetched as &str from URI in server to the respective key types,
+        there is one id for every attribute of the primary key in the same order.
+
+    returns:
+        A TrueType representation of every id
+        or an Error if their number or types don't fit to the definition
+*/
+fn parse_uri_key(ids: &[&str], model: &ModelDefinition) -> Result<Vec<TrueType>> {
+    let key_attrs: Vec<AttrName> = model.primary_key.attrs();
+    if ids.len() != key_attrs.len() {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Invalid primary key, expected {count} values", count=key_attrs.len())));
+    }
+    let mut true_key: Vec<TrueType> = vec!();
+    for (id, key_attr) in ids.iter().zip(&key_attrs) {
+        true_key.push(parse_uri_id(id, model.attributes.get(key_attr).unwrap())?);
+    }
+    Ok(true_key)
+}
+
+fn parse_uri_id(id: &str, key_type: &AttrType) -> Result<TrueType> {
+    match key_type {
+        AttrType::Primitive(PrimitiveType::String) => { 
+            Ok(TrueType::Primitive(Some(TruePrimitiveType::String(id.to_string()))))
+        },
+        AttrType::Primitive(temporal @ (PrimitiveType::Date | PrimitiveType::DateTime | PrimitiveType::Time)) => {
+            match to_true_prim_type(&Value::String(id.to_string()), temporal, true) {
+                Ok(val) => Ok(TrueType::Primitive(val)),
+                Err(_) => Err(Error::new(ErrorKind::InvalidData, "Invalid value for primary key"))
+            }
+        },
+        AttrType::Primitive(other) => {
+            if let Ok(val) = parse::<Value>(id) {
+                Ok(TrueType::Primitive(to_true_prim_type(&val, other, true)?))
+            } else {
+                Err(Error::new(ErrorKind::InvalidData, "Invalid value for primary key"))
+            }
+        },
+
+        // this shouldn't occur, since the model definition should be validated before
+        _ => unreachable!("Model definition has to be valid")
+    }
+}
+
+
+/*
+    parse_model: 
+        Parses a valid model in the given path with the given name.
+
+        What happens exactly:
+            1. fetch all model definitions via parse_models(),
+               this is necessary because of the duplicate filtering
+            2. filter the gotten vector of definitions to matches on the given model name (can be only one)
+            3. return the matching model, else Error when there are none
+
+    returns:
+        A valid model definition with the given model name
+        or an Error if there isn't such model defined
+*/
+pub fn parse_model(model_name: &ModelName) -> Result<ModelDefinition>{
+    if let Some(args) = cli::get_valid_start_args() {
+        let models: Vec<ModelDefinition> = parse_models(args.modelspath.as_path())?;
+        return find_model(&models, model_name).cloned();
+    }
+    todo!("parsing records is currently only possible when the server is running")
+}
+
+// finds the model with the given name among the parsed models, like parse_model does among all models
+fn find_model<'a>(models: &'a [ModelDefinition], model_name: &ModelName) -> Result<&'a ModelDefinition> {
+    match models.iter().find(|model| model.model_name.plural().camel() == model_name.plural().camel()) {
+        Some(model) => Ok(model),
+        None => Err(Error::new(NotFound, format!("model {name:?} not found", name=model_name.0.0)))
+    }
+}
+
+
+fn parse_all_models() -> Result<Vec<ModelDefinition>> {
+    if let Some(args) = cli::get_valid_start_args() {
+        return parse_models(args.modelspath.as_path());
+    }
+    todo!("parsing models is currently only possible when the server is running")
+}
+
+
+/*
+    ModelIssue: 
+        A reason why a file in the models' path doesn't provide a model.
+        Rejected files can't be parsed or validated, duplicates collide on the same plural camel-case name.
+*/
+#[derive(Debug, PartialEq)]
+pub enum ModelIssue {
+    Rejected(PathBuf, String),
+    Duplicate(ModelName, Vec<PathBuf>)
+}
+
+impl Display for ModelIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelIssue::Rejected(path, reason) => write!(f, "{path}: rejected, {reason}", path=path.display()),
+            ModelIssue::Duplicate(name, paths) => write!(
+                f,
+                "{paths}: ignored, all of them define the model {name:?}",
+                name=name.0.0,
+                paths=paths.iter().map(|path| path.display().to_string()).collect::<Vec<String>>().join(", ")
+            )
+        }
+    }
+}
+
+
+/*
+    parse_models: 
+        Parses all valid models in the given path into a vector.
+
+    returns:
+        A vector of valid model definitions, unique by their names
+        or an Error if there aren't some
+*/
+pub fn parse_models(model_path: &Path) -> Result<Vec<ModelDefinition>>{
+    let (models, _) = diagnose_models(model_path)?;
+    if models.is_empty() {
+        return Err(Error::new(NotFound, "No valid models defined"));
+    }
+    Ok(models)
+}
+
+
+/*
+    diagnose_models: 
+        Parses all valid models in the given path and memorizes why other files don't provide a model.
+
+        What happens exactly:
+            1. read the directory structure, sorted by the paths
+            2. iterate through the directory's entries
+            3. resolve the base models and attribute sets of all entries in the format of their extensions,
+               if an entry results in a valid model definition,
+               push it to the returning vector and memorize the model's name and path for duplicate checking,
+               else memorize the reason if the entry has the extension of a model file,
+               attribute sets don't result in a model without being an issue
+            4. remove all models from the returning vector whose name occurs multiple times
+            5. remove all models with history whose history model has the name of another model
+            6. return the vector of definitions and the issues
+
+    returns:
+        A vector of valid model definitions, unique by their names, and the issues of the other files
+        or an Error if the path can't be read
+*/
+pub fn diagnose_models(model_path: &Path) -> Result<(Vec<ModelDefinition>, Vec<ModelIssue>)>{
+    // read directory structure
+    let model_paths: Result<ReadDir> = read_dir(model_path);
+    if model_paths.is_err() {
+        return Err(Error::new(NotFound, "No valid models defined"));
+    }
+    let mut paths: Vec<PathBuf> = model_paths.unwrap().flatten().map(|entry| entry.path()).filter(|path| path.is_file()).collect();
+    paths.sort();
+
+    let mut models: Vec<ModelDefinition> = vec!();  // stores the parsed valid models
+    let mut model_paths: Vec<(ModelName, PathBuf)> = vec!();  // stores the names and paths of the valid models, just for simpler duplicate checking
+    let mut issues: Vec<ModelIssue> = vec!();
+
+    // read the files, other files in the models' path are ignored without an issue
+    let mut contents: Vec<(PathBuf, String, ModelFormat)> = vec!();
+    for path in paths {
+        let reported: bool = ModelFormat::from_extension(&path).is_some();
+        match read_to_string(&path) {
+            Ok(data) => {
+                let format: ModelFormat = ModelFormat::from_path(&path);
+                contents.push((path, data, format));
+            },
+            Err(err) if reported => issues.push(ModelIssue::Rejected(path, err.to_string())),
+            Err(_) => ()
+        }
+    }
+
+    // parse the models, resolving their base models and attribute sets
+    let resolved = resolve_model_definitions(&contents.iter().map(|(_, data, format)| (data.clone(), *format)).collect::<Vec<(String, ModelFormat)>>());
+    for ((path, _, _), parsed) in contents.into_iter().zip(resolved) {
+        match parsed {
+            Some(Ok(model)) => {
+                model_paths.push((model.model_name.plural().camel(), path));
+                models.push(model);
+            },
+            Some(Err(err)) if ModelFormat::from_extension(&path).is_some() => issues.push(ModelIssue::Rejected(path, err.to_string())),
+            _ => ()
+        }
+    }
+
+    // remove duplicates
+    let mut duplicates: Vec<ModelName> = vec!();  // stores all names of duplicate valid models
+    for (name, _) in &model_paths {
+        if !duplicates.contains(name) && model_paths.iter().filter(|(other, _)| other == name).count() > 1 {
+            duplicates.push(name.clone());
+        }
+    }
+    for dup in duplicates {
+        models.retain(|m| m.model_name.plural().camel() != dup);
+        let paths: Vec<PathBuf> = model_paths.iter().filter(|(name, _)| name == &dup).map(|(_, path)| path.clone()).collect();
+        issues.push(ModelIssue::Duplicate(dup, paths));
+    }
+
+    // history models are stored like other models, so their names must not collide
+    let names: Vec<ModelName> = models.iter().map(|model| model.model_name.plural().camel()).collect();
+    let colliding: Vec<(ModelName, ModelName)> = models.iter()
+        .filter(|model| model.history)
+        .map(|model| (model.model_name.plural().camel(), model.history_model().model_name))
+        .filter(|(_, history_name)| names.contains(&history_name.plural().camel()))
+        .collect();
+    for (name, history_name) in colliding {
+        models.retain(|m| m.model_name.plural().camel() != name);
+        let path: PathBuf = model_paths.iter().find(|(other, _)| other == &name).map(|(_, path)| path.clone()).unwrap();
+        issues.push(ModelIssue::Rejected(path, format!("the history model {history_name:?} collides with another model", history_name=history_name.0.0)));
+    }
+
+    Ok((models, issues))
+}
+
+
+/*
+    parse_record: 
+        Parses a given JSON-String to a Record, appropriate to its given model definition.
+
+        What happens exactly:
+            1. check if JSON-String is valid JSON and parse it
+            2. check if any required attributes are missing,
+               keep in mind that the model definition is valid (impl TryFrom),
+               so the primary key is required as well
+            3. translate the parsed values to their respective type as defined in the model,
+               else return Error
+            4. check if the Record fits the constraints
+            5. check if the records referenced by belongsTo associations exist
+            6. return Record
+
+    returns:
+        a valid Record for the definition or an Error
+        if the given String is not a valid JSON representation of such Record
+*/
+fn parse_record(json: &str, model: &ModelDefinition) -> Result<Record> {
+    let parsed_json = parse::<HashMap<AttrName, Value>>(json);
+    
+    // check json
+    if parsed_json.is_err() {
+        return Err(Error::new(InvalidData, "Given JSON-String is not valid JSON"));
+    }
+
+    // timestamps and the soft delete marker are managed by the server only
+    if let Some(attr) = model.managed_attrs().iter().find(|attr| parsed_json.as_ref().unwrap().contains_key(attr)) {
+        return Err(Error::new(InvalidData, format!("Attribute {attr:?} is read-only", attr=model.exposed_name(attr).0)));
+    }
+
+    let record: Record = parse_attributes(parsed_json.unwrap(), &model.attributes, &model.required, model.naming.as_ref())?;
+
+    check_constraints(&record, model)?;
+    check_references(&record, model)?;
+
+    Ok(record)
+}
+
+/*
+    parse_attributes: 
+        Translates parsed JSON values to their respective type as defined in the given attributes,
+        values of nested object attributes get translated recursively.
+        Errors name the attributes like the given naming policy exposes them, unknown ones as given.
+
+    returns:
+        the translated Record or an Error
+        if a required attribute is missing or a value doesn't fit to its definition
+*/
+fn parse_attributes(values: HashMap<AttrName, Value>, attributes: &Attributes, required: &[AttrName], naming: Option<&Naming>) -> Result<Record> {
+    // check for missing required attributes
+    for key in required {
+        if !values.contains_key(key) {
+            return Err(Error::new(InvalidData, format!("Missing attribute: {name:?}", name=key.named(naming).0)));
+        };
+    }
+
+    let mut record = Record::new();
+
+    // convert values to Record
+    for (key, value) in values {
+        let is_required: bool = required.contains(&key);
+        if let Some(ty) = attributes.get(&key) {
+            match ty {
+                AttrType::Primitive(prim_type) => {
+                    match to_true_prim_type(&value, prim_type, is_required) {
+                        Ok(true_prim_value) => record.insert(key, TrueType::Primitive(true_prim_value)),
+                        Err(err) => return Err(Error::new(InvalidData, format!("Wrong type of attribute {attr:?}, {err}", attr=key.named(naming).0)))
+                    };
+                },
+                AttrType::Enum(enum_def) => {
+                    match to_true_enum_value(&value, enum_def, is_required) {
+                        Ok(true_prim_value) => record.insert(key, TrueType::Primitive(true_prim_value)),
+                        Err(err) => return Err(Error::new(InvalidData, format!("Wrong value of enum attribute {attr:?}, {err}", attr=key.named(naming).0)))
+                    };
+                },
+                AttrType::Array(arr_type) => {
+                    match value.as_array() {
+                        Some(arr) => {
+                            let mut true_arr: Vec<TruePrimitiveType> = vec!();
+                            for val in arr {
+                                match to_true_prim_type(val, &arr_type[0], true) {
+                                    Ok(true_prim_value) => true_arr.push(true_prim_value.unwrap()),
+                                    Err(err) => return Err(Error::new(InvalidData, format!("Wrong type of array attribute {attr:?}, {err}", attr=key.named(naming).0)))
+                                };
+                            }
+                            record.insert(key, TrueType::Array(Some(true_arr)));
+                        },
+                        None => {
+                            if !is_required && value.is_null() {
+                                record.insert(key, NULL);
+                            } else {
+                                return Err(Error::new(InvalidData, format!("Wrong type of attribute {attr:?}, expected: Array", attr=key.named(naming).0)));
+                            }
+                        }
+                    };
+                },
+                AttrType::Object(obj_def) => {
+                    if !is_required && value.is_null() {
+                        record.insert(key, TrueType::Object(None));
+                        continue;
+                    }
+                    match from_value::<HashMap<AttrName, Value>>(value) {
+                        Ok(nested_values) => {
+                            match parse_attributes(nested_values, &obj_def.object.attributes, &obj_def.object.required, naming) {
+                                Ok(nested_record) => record.insert(key, TrueType::Object(Some(nested_record))),
+                                Err(err) => return Err(Error::new(InvalidData, format!("Invalid object attribute {attr:?}, {err}", attr=key.named(naming).0)))
+                            };
+                        },
+                        Err(_) => return Err(Error::new(InvalidData, format!("Wrong type of attribute {attr:?}, expected: Object", attr=key.named(naming).0)))
+                    };
+                }
+            }
+        } else {
+            return Err(Error::new(InvalidData, format!("Unknown attribute: {attr:?}", attr=key.0)));
+        }
+    }
+
+    Ok(record)
+}
+
+/*
+    add_default_values: 
+        Adds the default values of the model definition to a given JSON-String,
+        for every attribute with a default which is missing in it.
+        Literals get added as they are, generators generate a new value.
+        Attributes explicitly set to null keep their null value.
+
+    returns:
+        the JSON-String with added default values or an Error
+        if the given String is not valid JSON
+*/
+fn add_default_values(json: &str, model: &ModelDefinition) -> Result<String> {
+    let defaults: &HashMap<AttrName, DefaultValue> = match &model.defaults {
+        Some(defaults) => defaults,
+        None => return Ok(json.to_string())
+    };
+    let mut values: HashMap<AttrName, Value> = match parse::<HashMap<AttrName, Value>>(json) {
+        Ok(values) => values,
+        Err(_) => return Err(Error::new(InvalidData, "Given JSON-String is not valid JSON"))
+    };
+    for (attr_name, default) in defaults {
+        if values.contains_key(attr_name) {
+            continue;
+        }
+        let value: Value = match default {
+            DefaultValue::Literal(value) => value.clone(),
+            DefaultValue::Generated { generator } => generator.generate(model.attributes.get(attr_name).unwrap())  // existence is validated with the model definition
+        };
+        values.insert(attr_name.clone(), value);
+    }
+    Ok(serde_json::to_string(&values).unwrap())
+}
+
+/*
+    add_generated_key: 
+        Adds a new primary key generated by the server to a given JSON-String.
+        Integer keys get the next value of the model's sequence from the storage handler,
+        String keys get a new UUID or ULID.
+
+    returns:
+        the JSON-String with added primary key or an Error
+        if the given String is not valid JSON, already contains a key
+        or the storage handler is unable to allocate the next key
+*/
+fn add_generated_key(json: &str, model: &ModelDefinition, generator: &KeyGenerator, storage_handler: &impl StorageHandler) -> Result<String> {
+    let mut values: HashMap<AttrName, Value> = match parse::<HashMap<AttrName, Value>>(json) {
+        Ok(values) => values,
+        Err(_) => return Err(Error::new(InvalidData, "Given JSON-String is not valid JSON"))
+    };
+    // generators are only allowed for single keys
+    let key_attr: AttrName = model.primary_key.attrs().remove(0);
+    if values.contains_key(&key_attr) {
+        return Err(Error::new(InvalidData, format!("The primary key {key:?} is generated by the server and must not be given", key=key_attr.0)));
+    }
+    let key: Value = match generator {
+        KeyGenerator::increment => Value::from(storage_handler.next_sequence()?),
+        KeyGenerator::uuid => Value::String(Uuid::new_v4().to_string()),
+        KeyGenerator::ulid => Value::String(Ulid::new().to_string())
+    };
+    values.insert(key_attr, key);
+    Ok(serde_json::to_string(&values).unwrap())
+}
+
+/*
+    hash_secrets: 
+        Replaces the values of hashed attributes by salted Argon2 hashes in PHC format,
+        so their plaintext never gets stored.
+
+    returns:
+        the record with hashed values or an Error if hashing fails
+*/
+fn hash_secrets(mut record: Record, model: &ModelDefinition) -> Result<Record> {
+    for attr in model.hashed.iter().flatten() {
+        let hash: String = match record.get(attr) {
+            Some(TrueType::Primitive(Some(TruePrimitiveType::String(plaintext)))) => Argon2::default()
+                .hash_password(plaintext.as_bytes(), &SaltString::generate(&mut OsRng))
+                .map_err(|err| Error::other(format!("Unable to hash attribute {attr:?}, {err}", attr=attr.0)))?
+                .to_string(),
+            _ => continue
+        };
+        record.insert(attr.clone(), TrueType::Primitive(Some(TruePrimitiveType::String(hash))));
+    }
+    Ok(record)
+}
+
+/*
+    expose: 
+        Prepares a record to be returned via REST or GraphQL,
+        therefore its secrets are hidden and its attributes are named by the model's naming policy.
+
+    returns:
+        the record as it is returned
+*/
+fn expose(record: Record, model: &ModelDefinition) -> Record {
+    expose_names(hide_secrets(record, model), model.naming.as_ref())
+}
+
+// the attributes of nested objects are named by the naming policy too
+fn expose_names(record: Record, naming: Option<&Naming>) -> Record {
+    let Some(naming) = naming else {
+        return record;
+    };
+    record.into_iter()
+        .map(|(attr, value)| match value {
+            TrueType::Object(Some(nested)) => (naming.apply(&attr), TrueType::Object(Some(expose_names(nested, Some(naming))))),
+            value => (naming.apply(&attr), value)
+        })
+        .collect()
+}
+
+/*
+    internal_names: 
+        Renames the attributes of a JSON object given via REST or GraphQL to the names they are stored with,
+        which differ from the given ones if the model has a naming policy.
+
+    returns:
+        the JSON object with the stored names, it's returned as given if it isn't valid JSON, so parsing it reports the Error,
+        or an Error if an attribute is given by its stored instead of its exposed name
+*/
+fn internal_names(json: &str, model: &ModelDefinition) -> Result<String> {
+    let Some(naming) = &model.naming else {
+        return Ok(json.to_string());
+    };
+    match parse::<Value>(json) {
+        Ok(value) => Ok(internal_value_names(value, &model.attributes, &model.managed_attrs(), naming)?.to_string()),
+        Err(_) => Ok(json.to_string())
+    }
+}
+
+fn internal_value_names(value: Value, attributes: &Attributes, managed: &[AttrName], naming: &Naming) -> Result<Value> {
+    let Value::Object(object) = value else {
+        return Ok(value);
+    };
+    let mut renamed: Map<String, Value> = Map::new();
+    for (name, value) in object {
+        match attributes.keys().chain(managed).find(|attr| naming.apply(attr).0 == name) {
+            Some(attr) => {
+                let value: Value = match attributes.get(attr) {
+                    Some(AttrType::Object(obj_def)) => internal_value_names(value, &obj_def.object.attributes, &[], naming)?,
+                    _ => value
+                };
+                renamed.insert(attr.0.clone(), value);
+            },
+            None if attributes.contains_key(&AttrName(name.clone())) || managed.contains(&AttrName(name.clone())) => {
+                return Err(Error::new(InvalidData, format!("Attribute {name:?} has to be named {exposed:?}", exposed=naming.apply(&AttrName(name.clone())).0)));
+            },
+            // unknown attributes are reported by parsing the record
+            None => { renamed.insert(name, value); }
+        }
+    }
+    Ok(Value::Object(renamed))
+}
+
+// write-only and hashed attributes are never returned
+fn hide_secrets(mut record: Record, model: &ModelDefinition) -> Record {
+    for attr in model.secret_attrs() {
+        record.remove(&attr);
+    }
+    record
+}
+
+/*
+    add_timestamps: 
+        Sets the timestamps of a record if they are enabled for its model,
+        updatedAt always gets the current time, createdAt only if no creation time is given.
+
+    returns:
+        the record with timestamps
+*/
+fn add_timestamps(mut record: Record, model: &ModelDefinition, created_at: Option<TrueType>) -> Record {
+    if !model.timestamps {
+        return record;
+    }
+    let now: TrueType = now();
+    record.insert(AttrName(CREATED_AT.to_string()), created_at.unwrap_or(now.clone()));
+    record.insert(AttrName(UPDATED_AT.to_string()), now);
+    record
+}
+
+/*
+    add_expiry: 
+        Adds the expiry time of records with a fixed time to live, counted from their creation.
+
+    returns:
+        the record with its expiry time, or without one if it would exceed the supported dates
+*/
+fn add_expiry(mut record: Record, model: &ModelDefinition) -> Record {
+    if let Some(Ttl::Seconds(seconds)) = model.ttl {
+        let expiry: Option<DateTime<Utc>> = Duration::from_std(std::time::Duration::from_secs(seconds)).ok()
+            .and_then(|ttl| Utc::now().checked_add_signed(ttl));
+        record.insert(
+            AttrName(EXPIRES_AT.to_string()),
+            TrueType::Primitive(expiry.map(|expiry| TruePrimitiveType::String(expiry.to_rfc3339_opts(SecondsFormat::AutoSi, true))))
+        );
+    }
+    record
+}
+
+fn now() -> TrueType {
+    let now: Value = Generator::now.generate(&AttrType::Primitive(PrimitiveType::DateTime));
+    TrueType::Primitive(to_true_prim_type(&now, &PrimitiveType::DateTime, true).unwrap())
+}
+
+fn add_null_values(record: Record, model: &ModelDefinition) -> Record {
+    let mut record: Record = add_nested_null_values(record, &model.attributes);
+    for attr in model.managed_attrs() {
+        record.entry(attr).or_insert(NULL);
+    }
+    record
+}
+
+fn add_nested_null_values(mut record: Record, attributes: &Attributes) -> Record {
+    for (attr_name, attr_type) in attributes {
+        match (record.remove(attr_name), attr_type) {
+            (Some(TrueType::Object(Some(nested))), AttrType::Object(obj_def)) => record.insert(attr_name.clone(), TrueType::Object(Some(add_nested_null_values(nested, &obj_def.object.attributes)))),
+            (Some(value), _) => record.insert(attr_name.clone(), value),
+            (None, _) => record.insert(attr_name.clone(), NULL)
+        };
+    }
+    record
+}
+
+fn check_references(record: &Record, model: &ModelDefinition) -> Result<()> {
+    for (name, association) in model.associations.iter().flatten() {
+        if association.kind != AssociationKind::BelongsTo {
+            continue;
+        }
+        if let Some(value) = record.get(&association.foreign_key).filter(|value| value != &&NULL) {
+            let referenced: ModelDefinition = parse_model(&association.model)?;
+            check_foreign_key(name, association, model, &referenced)?;
+            if let Err(err) = read_visible(&get_handler(&referenced)?, from_ref(value), &referenced) {
+                return match err.kind() {
+                    NotFound => Err(Error::new(InvalidData, format!("Referenced record of association {name:?} doesn't exist", name=name.0))),
+                    _ => Err(err)
+                };
+            }
+        }
+    }
+    Ok(())
+}
+
+/*
+    check_foreign_key: 
+        Checks if the foreign key of a belongsTo association fits the primary key of the referenced model,
+        the referenced model has to be identified by a single key attribute of the foreign key's type.
+
+    returns:
+        Empty tuple if the foreign key can reference records of the model, else Error
+*/
+fn check_foreign_key(name: &AttrName, association: &Association, model: &ModelDefinition, referenced: &ModelDefinition) -> Result<()> {
+    let key_attrs: Vec<AttrName> = referenced.primary_key.attrs();
+    if key_attrs.len() != 1 {
+        return Err(Error::new(InvalidData, format!("Association {name:?} can't reference the composite primary key of model {referenced_name:?}", name=name.0, referenced_name=referenced.model_name.0.0)));
+    }
+    if model.attributes.get(&association.foreign_key) != referenced.attributes.get(&key_attrs[0]) {
+        return Err(Error::new(InvalidData, format!(
+            "The foreign key {foreign_key:?} of association {name:?} doesn't have the type of the primary key {key:?} of model {referenced_name:?}",
+            foreign_key=association.foreign_key.0,
+            name=name.0,
+            key=key_attrs[0].0,
+            referenced_name=referenced.model_name.0.0
+        )));
+    }
+    Ok(())
+}
+
+fn check_constraints(record: &Record, model: &ModelDefinition) -> Result<()> {
+    if let Some(constraints) = &model.constraints {
+        for (attr_name, constraint) in constraints {
+            if let Some(value) = record.get(attr_name) {
+                if let Err(err) = constraint.check(value) {
+                    return Err(Error::new(InvalidData, format!("Constraint violation of attribute {attr:?}, {err}", attr=model.exposed_name(attr_name).0)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// rules are checked on every write, so every expression is parsed once and reused
+fn parsed_expression(expression: &str) -> Result<Arc<Expression>> {
+    static EXPRESSIONS: OnceLock<Mutex<HashMap<String, Arc<Expression>>>> = OnceLock::new();
+    let mut expressions = EXPRESSIONS.get_or_init(Default::default).lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(parsed) = expressions.get(expression) {
+        return Ok(parsed.clone());
+    }
+    let parsed: Arc<Expression> = Arc::new(Expression::parse(expression)?);
+    expressions.insert(expression.to_string(), parsed.clone());
+    Ok(parsed)
+}
+
+// the rules of the model which don't use any of the given attributes
+fn rules_without(model: &ModelDefinition, attrs: &[AttrName]) -> Result<Option<HashMap<String, String>>> {
+    let mut rules: HashMap<String, String> = HashMap::new();
+    for (name, expression) in model.rules.iter().flatten() {
+        if !parsed_expression(expression)?.attributes().iter().any(|path| attrs.contains(&path[0])) {
+            rules.insert(name.clone(), expression.clone());
+        }
+    }
+    Ok(model.rules.as_ref().map(|_| rules))
+}
+
+/*
+    check_rules: 
+        Evaluates the rules of the model for a complete record, on updates the record merged with the stored one.
+        A rule is violated if its expression results in false, null means the rule doesn't apply.
+
+    returns:
+        Empty tuple if no rule is violated, else Error naming the first violated rule
+*/
+fn check_rules(record: &Record, model: &ModelDefinition) -> Result<()> {
+    let mut rules: Vec<(&String, &String)> = model.rules.iter().flatten().collect();
+    rules.sort();
+    for (name, expression) in rules {
+        // the expression is validated with the model definition
+        match parsed_expression(expression)?.evaluate(record) {
+            Ok(Value::Bool(true) | Value::Null) => (),
+            Ok(Value::Bool(false)) => return Err(Error::new(InvalidData, format!("Rule {name:?} violated: {expression}"))),
+            Ok(other) => return Err(Error::new(InvalidData, format!("Rule {name:?} results in {other} instead of a Boolean"))),
+            Err(err) => return Err(Error::new(InvalidData, format!("Rule {name:?} can't be evaluated, {err}")))
+        }
+    }
+    Ok(())
+}
+
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{
+        remove_file,
+        write
+    };
+
+    #[test]
+    fn test_parse_record() {
+        let valid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        let expected_record = Record::from([
+            (AttrName("id".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("name".to_string()),TrueType::Primitive(Some(TruePrimitiveType::String("Natural Born Killers".to_string())))),
+            (AttrName("year".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Integer(1994)))),
+            (AttrName("actors".to_string()),TrueType::Array(Some(vec!(TruePrimitiveType::String("Woody Harrelson".to_string()), TruePrimitiveType::String("Juliette Lewis".to_string()))))),
+            (AttrName("recommended".to_string()),TrueType::Primitive(Some(TruePrimitiveType::Boolean(true))))
+        ]);
+
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
+                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("name".to_string()),
+                AttrName("recommended".to_string())
+            ),
+            ..base_model()
+        };
+        let parsed_record: Record = parse_record(valid_input, &movie_model).unwrap();
+        
+        assert_eq!(&parsed_record, &expected_record);
+        
+        // test null values
+        let valid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "year": null,
+                "actors": null,
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(valid_input, &movie_model).is_ok(),
+            "Unexpected Error for parsing null-values for non-required attributes"
+        );
+
+        // test errors
+        // test String instead of Integer
+        let invalid_input = r#"
+            {
+                "id": "1",
+                "name": "Natural Born Killers",
+                "year": "1994",
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for parsing String-Value to Integer"
+        );
+
+        // test String instead of Boolean 
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": "true"
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for parsing String-Value to Boolean"
+        );
+
+        // test Integer instead of String 
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "name": 1994,
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for parsing Integer-Value to String"
+        );
+
+        // test Array(Integer) instead of Array(String)
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "year": 1994,
+                "actors": [1, 2],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for parsing Array(Integer)-Value to Array(String)"
+        );
+
+        // test missing attribute
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for missing required attributes"
+        );
+
+        // test null value for required attribute
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "name": null,
+                "year": 1994,
+                "actors": ["Woody Harrelson", "Juliette Lewis"],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for null-valued required attributes"
+        );
+
+        // test null values as array entries
+        let invalid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "year": 1994,
+                "actors": ["Woody Harrelson", null],
+                "recommended": true
+            }
+        "#;
+        assert!(
+            parse_record(invalid_input, &movie_model).is_err(),
+            "Expected Error for array storing null values"
+        );
+
+        // test invalid json
+        assert!(
+            parse_record("invalid json", &movie_model).is_err(),
+            "Expected Error for parsing invalid JSON input"
+        );
+        // test nested object attributes
+        let mut movie_model = movie_model;
+        movie_model.attributes.insert(AttrName("studio".to_string()), AttrType::Object(ObjectDefinition {
+            object: ObjectAttributes {
+                attributes: Attributes::from([
+                    (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("founded".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+                ]),
+                required: vec!(AttrName("name".to_string())),
+                descriptions: None
+            }
+        }));
+        let valid_input = r#"
+            {
+                "id": 1,
+                "name": "Natural Born Killers",
+                "recommended": true,
+                "studio": {"name": "Warner Bros."}
+            }
+        "#;
+        let parsed_record: Record = parse_record(valid_input, &movie_model).unwrap();
+        assert_eq!(
+            parsed_record.get(&AttrName("studio".to_string())),
+            Some(&TrueType::Object(Some(Record::from([(AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Warner Bros.".to_string()))))]))))
+        );
+        assert_eq!(
+            add_null_values(parsed_record, &movie_model).get(&AttrName("studio".to_string())),
+            Some(&TrueType::Object(Some(Record::from([
+                (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Warner Bros.".to_string())))),
+                (AttrName("founded".to_string()), NULL)
+            ])))),
+            "Expected null values for missing nested attributes"
+        );
+        for studio in [r#"{"founded": 1923}"#, r#"{"name": "Warner Bros.", "founded": "1923"}"#, r#"{"name": "Warner Bros.", "unknown": 1}"#, r#""Warner Bros.""#] {
+            let invalid_input = format!(r#"{{"id": 1, "name": "Natural Born Killers", "recommended": true, "studio": {studio}}}"#);
+            assert!(
+                parse_record(&invalid_input, &movie_model).is_err(),
+                "Expected Error for parsing invalid nested object {studio}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_default_values() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean)),
+                (AttrName("added".to_string()), AttrType::Primitive(PrimitiveType::DateTime)),
+                (AttrName("code".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            defaults: Some(HashMap::from([
+                (AttrName("recommended".to_string()), DefaultValue::Literal(Value::Bool(false))),
+                (AttrName("added".to_string()), DefaultValue::Generated { generator: Generator::now }),
+                (AttrName("code".to_string()), DefaultValue::Generated { generator: Generator::uuid })
+            ])),
+            ..base_model()
+        };
+
+        let record: Record = parse_record(&add_default_values(r#"{"id": "1", "recommended": null}"#, &movie_model).unwrap(), &movie_model).unwrap();
+        assert_eq!(record.get(&AttrName("recommended".to_string())), Some(&NULL), "Expected explicit null value to be kept");
+        assert_eq!(record.get(&AttrName("year".to_string())), None, "Expected no value for attribute without default");
+        assert!(
+            matches!(record.get(&AttrName("added".to_string())), Some(TrueType::Primitive(Some(TruePrimitiveType::String(_))))),
+            "Expected generated DateTime for attribute with now generator"
+        );
+        match record.get(&AttrName("code".to_string())) {
+            Some(TrueType::Primitive(Some(TruePrimitiveType::String(code)))) => assert!(Uuid::parse_str(code).is_ok(), "Expected generated UUID, got {code}"),
+            other => panic!("Expected generated UUID, got {other:?}")
+        }
+
+        let record: Record = parse_record(&add_default_values(r#"{"id": "1"}"#, &movie_model).unwrap(), &movie_model).unwrap();
+        assert_eq!(
+            record.get(&AttrName("recommended".to_string())),
+            Some(&TrueType::Primitive(Some(TruePrimitiveType::Boolean(false)))),
+            "Expected literal default value for missing attribute"
+        );
+
+        assert!(add_default_values("invalid json", &movie_model).is_err(), "Expected Error for invalid JSON input");
+    }
+
+    #[test]
+    fn test_add_timestamps() {
+        let mut movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            timestamps: true,
+            ..base_model()
+        };
+        let created_at = AttrName(CREATED_AT.to_string());
+        let updated_at = AttrName(UPDATED_AT.to_string());
+
+        let record: Record = add_timestamps(parse_record(r#"{"id": "1"}"#, &movie_model).unwrap(), &movie_model, None);
+        assert!(
+            matches!(record.get(&created_at), Some(TrueType::Primitive(Some(TruePrimitiveType::String(_))))),
+            "Expected creation time for new record"
+        );
+        assert_eq!(record.get(&created_at), record.get(&updated_at), "Expected same creation and update time for new record");
+
+        let creation: TrueType = TrueType::Primitive(Some(TruePrimitiveType::String("2000-01-01T00:00:00Z".to_string())));
+        let record: Record = add_timestamps(parse_record(r#"{"id": "1"}"#, &movie_model).unwrap(), &movie_model, Some(creation.clone()));
+        assert_eq!(record.get(&created_at), Some(&creation), "Expected creation time to be kept");
+        assert_ne!(record.get(&updated_at), Some(&creation), "Expected new update time");
+
+        for input in [r#"{"id": "1", "createdAt": "2000-01-01T00:00:00Z"}"#, r#"{"id": "1", "updatedAt": null}"#] {
+            assert!(parse_record(input, &movie_model).is_err(), "Expected Error for read-only timestamp in {input}");
+        }
+
+        movie_model.timestamps = false;
+        let record: Record = add_timestamps(parse_record(r#"{"id": "1"}"#, &movie_model).unwrap(), &movie_model, None);
+        assert!(!record.contains_key(&created_at) && !record.contains_key(&updated_at), "Expected no timestamps if they are disabled");
+    }
+
+    #[test]
+    fn test_add_expiry() {
+        let mut session_model = ModelDefinition {
+            model_name: ModelName(AttrName("session".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("validUntil".to_string()), AttrType::Primitive(PrimitiveType::DateTime))
+            ]),
+            ttl: Some(Ttl::Seconds(3600)),
+            ..base_model()
+        };
+        let expires_at = AttrName(EXPIRES_AT.to_string());
+
+        let record: Record = add_expiry(parse_record(r#"{"id": "1"}"#, &session_model).unwrap(), &session_model);
+        assert!(
+            matches!(record.get(&expires_at), Some(TrueType::Primitive(Some(TruePrimitiveType::String(_))))),
+            "Expected expiry time for new record"
+        );
+        assert!(!is_expired(&record, &session_model), "Expected new record not to be expired");
+        assert!(parse_record(r#"{"id": "1", "expiresAt": "2000-01-01T00:00:00Z"}"#, &session_model).is_err(), "Expected Error for read-only expiry time");
+
+        let mut record: Record = parse_record(r#"{"id": "1"}"#, &session_model).unwrap();
+        record.insert(expires_at.clone(), TrueType::Primitive(Some(TruePrimitiveType::String("2000-01-01T00:00:00Z".to_string()))));
+        assert!(is_expired(&record, &session_model), "Expected record with past expiry time to be expired");
+
+        session_model.ttl = Some(Ttl::Seconds(u64::MAX));
+        let record: Record = add_expiry(parse_record(r#"{"id": "1"}"#, &session_model).unwrap(), &session_model);
+        assert_eq!(record.get(&expires_at), Some(&NULL), "Expected no expiry time beyond the supported dates");
+        assert!(!is_expired(&record, &session_model), "Expected record without expiry time not to be expired");
+
+        session_model.ttl = Some(Ttl::Attribute(AttrName("validUntil".to_string())));
+        for (input, expired) in [
+            (r#"{"id": "1", "validUntil": "2000-01-01T00:00:00Z"}"#, true),
+            (r#"{"id": "1", "validUntil": "2999-01-01T00:00:00Z"}"#, false),
+            (r#"{"id": "1", "validUntil": null}"#, false)
+        ] {
+            let record: Record = add_expiry(parse_record(input, &session_model).unwrap(), &session_model);
+            assert!(!record.contains_key(&expires_at), "Expected no managed expiry time if it comes from an attribute");
+            assert_eq!(is_expired(&record, &session_model), expired, "Unexpected expiry of {input}");
+        }
+    }
+
+    #[test]
+    fn test_naming() {
+        let mut movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("release_year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("studio".to_string()), AttrType::Object(ObjectDefinition {
+                    object: ObjectAttributes {
+                        attributes: Attributes::from([(AttrName("zip_code".to_string()), AttrType::Primitive(PrimitiveType::String))]),
+                        required: vec!(),
+                        descriptions: None
+                    }
+                }))
+            ]),
+            timestamps: true,
+            naming: Some(Naming::CamelCase),
+            ..base_model()
+        };
+
+        let json: String = internal_names(r#"{"id": "1", "releaseYear": 1994, "studio": {"zipCode": "90210"}}"#, &movie_model).unwrap();
+        let record: Record = add_timestamps(parse_record(&json, &movie_model).unwrap(), &movie_model, None);
+        assert_eq!(
+            record.get(&AttrName("release_year".to_string())),
+            Some(&TrueType::Primitive(Some(TruePrimitiveType::Integer(1994)))),
+            "Expected the exposed name to be stored with the defined name"
+        );
+
+        let exposed: Record = expose(record, &movie_model);
+        let mut names: Vec<&str> = exposed.keys().map(|attr| attr.0.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!("createdAt", "id", "releaseYear", "studio", "updatedAt"), "Unexpected exposed names");
+        assert!(
+            matches!(exposed.get(&AttrName("studio".to_string())), Some(TrueType::Object(Some(studio))) if studio.contains_key(&AttrName("zipCode".to_string()))),
+            "Expected nested attributes to be exposed by the naming policy"
+        );
+
+        for input in [r#"{"id": "1", "release_year": 1994}"#, r#"{"id": "1", "studio": {"zip_code": "90210"}}"#] {
+            assert!(internal_names(input, &movie_model).is_err(), "Expected Error for attribute given by its stored name in {input}");
+        }
+        assert_eq!(internal_names("no json", &movie_model).ok().as_deref(), Some("no json"), "Expected invalid JSON to be kept for parsing the record");
+
+        movie_model.naming = None;
+        let input: &str = r#"{"id": "1", "release_year": 1994}"#;
+        assert_eq!(internal_names(input, &movie_model).ok().as_deref(), Some(input), "Expected names to be kept without naming policy");
+    }
+
+    #[test]
+    fn test_check_foreign_key() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("studio".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            required: vec!(AttrName("id".to_string()), AttrName("studio".to_string())),
+            ..base_model()
+        };
+        let association = Association {
+            kind: AssociationKind::BelongsTo,
+            model: ModelName(AttrName("movie".to_string())),
+            foreign_key: AttrName("movieId".to_string()),
+            other_key: None,
+            through: None,
+            on_delete: None
+        };
+        let name = AttrName("movie".to_string());
+        let mut award_model = ModelDefinition {
+            model_name: ModelName(AttrName("award".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("movieId".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+            ]),
+            required: vec!(AttrName("id".to_string())),
+            associations: Some(HashMap::from([(name.clone(), association.clone())])),
+            ..movie_model.clone()
+        };
+
+        assert!(check_foreign_key(&name, &association, &award_model, &movie_model).is_ok(), "Expected a foreign key of the primary key's type to be valid");
+
+        award_model.attributes.insert(AttrName("movieId".to_string()), AttrType::Primitive(PrimitiveType::String));
+        assert!(check_foreign_key(&name, &association, &award_model, &movie_model).is_err(), "Expected Error for a foreign key of another type than the primary key");
+
+        let composite_movie_model = ModelDefinition {
+            primary_key: KeyAttrs::Composite(vec!(AttrName("id".to_string()), AttrName("studio".to_string()))),
+            ..movie_model.clone()
+        };
+        award_model.attributes.insert(AttrName("movieId".to_string()), AttrType::Primitive(PrimitiveType::Integer));
+        assert!(check_foreign_key(&name, &association, &award_model, &composite_movie_model).is_err(), "Expected Error for a foreign key referencing a composite primary key");
+    }
+
+    #[test]
+    fn test_add_generated_key() {
+        const TEST_STORAGE_DEFINITIONS: &str = "test_add_generated_key.storages.json";
+        const TEST_STORAGE_FILE: &str = "test_add_generated_key.json";
+        const TEST_META_FILE: &str = "test_add_generated_key.json.meta";
+
+        for file in [TEST_STORAGE_DEFINITIONS, TEST_STORAGE_FILE, TEST_META_FILE] {
+            if PathBuf::from(file).is_file() {
+                assert!(remove_file(file).is_ok(), "Storage file {file} already existing, unable to remove");
+            }
+        }
+        assert!(
+            write(TEST_STORAGE_DEFINITIONS, format!(r#"{{"json": {{"storage_file": "{TEST_STORAGE_FILE}"}}}}"#)).is_ok(),
+            "Unable to write storage definition file for tests"
+        );
+
+        let mut movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            primary_key_generator: Some(KeyGenerator::increment),
+            required: vec!(AttrName("id".to_string()), AttrName("name".to_string())),
+            ..base_model()
+        };
+        let input = r#"{"name": "Natural Born Killers"}"#;
+        let handler = get_handler_for(&movie_model, Some(PathBuf::from(TEST_STORAGE_DEFINITIONS))).unwrap();
+
+        let record: Record = parse_record(&add_generated_key(input, &movie_model, &KeyGenerator::increment, &handler).unwrap(), &movie_model).unwrap();
+        assert_eq!(
+            record.get(&AttrName("id".to_string())),
+            Some(&TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            "Expected the next sequence value as generated key"
+        );
+        assert!(
+            add_generated_key(r#"{"id": 1, "name": "Natural Born Killers"}"#, &movie_model, &KeyGenerator::increment, &handler).is_err(),
+            "Expected Error for given key when it is generated by the server"
+        );
+
+        movie_model.attributes.insert(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String));
+        for generator in [KeyGenerator::uuid, KeyGenerator::ulid] {
+            let record: Record = parse_record(&add_generated_key(input, &movie_model, &generator, &handler).unwrap(), &movie_model).unwrap();
+            match record.get(&AttrName("id".to_string())) {
+                Some(TrueType::Primitive(Some(TruePrimitiveType::String(key)))) => assert!(
+                    if generator == KeyGenerator::uuid { Uuid::parse_str(key).is_ok() } else { Ulid::from_string(key).is_ok() },
+                    "Expected generated {generator:?} key, got {key}"
+                ),
+                other => panic!("Expected generated {generator:?} key, got {other:?}")
+            }
+        }
+
+        for file in [TEST_STORAGE_DEFINITIONS, TEST_STORAGE_FILE, TEST_META_FILE] {
+            if PathBuf::from(file).is_file() {
+                assert!(remove_file(file).is_ok(), "Unable to remove storage file {file} after test");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_model() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
+                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("name".to_string()),
+                AttrName("recommended".to_string())
+            ),
+            rules: Some(HashMap::from([("recommendedNeedsYear".to_string(), "!recommended || year != null".to_string())])),
+            associations: Some(HashMap::from([(AttrName("cast".to_string()), Association {
+                kind: AssociationKind::ManyToMany,
+                model: ModelName(AttrName("actor".to_string())),
+                foreign_key: AttrName("movieId".to_string()),
+                other_key: Some(AttrName("actorName".to_string())),
+                through: None,
+                on_delete: None
+            })])),
+            ..base_model()
+        };
+
+        let expected_result: ModelDefinition = movie_model;
+        assert_eq!(&parse_model(&ModelName(AttrName("movie".to_string()))).unwrap(), &expected_result);
+
+        // test errors
+        assert!(
+            parse_model(&ModelName(AttrName("not_existing_model".to_string()))).is_err(),
+            "Expected error for no matching model definitions"
+        );
+    }
+
+    #[test]
+    fn test_parse_models() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
+                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("name".to_string()),
+                AttrName("recommended".to_string())
+            ),
+            ..base_model()
+        };
+
+        let expected_result: Vec<ModelDefinition> = vec![movie_model];
+        assert_eq!(&parse_models(Path::new("./testing/model")).unwrap(), &expected_result);
+
+        // test errors
+        assert!(
+            parse_models(Path::new("./testing/model/not_existing_dir")).is_err(),
+            // test a not existing directory
+            "Expected error for not existing models' path"
+        );
+        assert!(
+            parse_models(Path::new("./testing/model/dummy_dir")).is_err(),
+            // test a directory without any valid model definitions
+            "Expected error for no existing valid model definitions"
+        );
+    }
+
+    #[test]
+    fn test_parse_resolved_models() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String])),
+                (AttrName("recommended".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("name".to_string()),
+                AttrName("recommended".to_string())
+            ),
+            ..base_model()
+        };
+
+        let director_model = ModelDefinition {
+            model_name: ModelName(AttrName("director".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("born".to_string()), AttrType::Primitive(PrimitiveType::Date))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("name".to_string())
+            ),
+            ..base_model()
+        };
+
+        // the base model and the attribute set get resolved
+        let mut documentary_model: ModelDefinition = movie_model.clone();
+        documentary_model.model_name = ModelName(AttrName("documentary".to_string()));
+        documentary_model.attributes.extend([
+            (AttrName("createdBy".to_string()), AttrType::Primitive(PrimitiveType::String)),
+            (AttrName("reviewed".to_string()), AttrType::Primitive(PrimitiveType::Boolean)),
+            (AttrName("topic".to_string()), AttrType::Primitive(PrimitiveType::String))
+        ]);
+        documentary_model.required.extend([AttrName("createdBy".to_string()), AttrName("topic".to_string())]);
+        documentary_model.descriptions = Some(HashMap::from([(AttrName("createdBy".to_string()), "The name of the author".to_string())]));
+
+        // the models are sorted by their paths
+        let expected_result: Vec<ModelDefinition> = vec![director_model, documentary_model, movie_model];
+        assert_eq!(&parse_models(Path::new("./testing/model_inheritance")).unwrap(), &expected_result, "Expected the base model and the attribute set to be resolved");
+    }
+
+    #[test]
+    fn test_diagnose_models() {
+        let (models, issues) = diagnose_models(Path::new("./testing/model")).unwrap();
+        assert_eq!(models.len(), 1, "Expected only the valid and unique models");
+
+        let rejected: Vec<String> = issues.iter().filter_map(|issue| match issue {
+            ModelIssue::Rejected(path, _) => Some(path.file_name().unwrap().to_string_lossy().to_string()),
+            ModelIssue::Duplicate(_, _) => None
+        }).collect();
+        assert_eq!(
+            rejected,
+            vec!(
+                "array_pk.json",
+                "inflection_no_plural_variant.json",
+                "invalid_pk.json",
+                "invalid_storage.json",
+                "multivalued_array.json",
+                "no json.json",
+                "pk_not_required.json",
+                "required_attr_doesnt_exist.json"
+            ),
+            "Expected an issue for every invalid model file"
+        );
+
+        // serde's position is part of the reason
+        let reason: Option<&String> = issues.iter().find_map(|issue| match issue {
+            ModelIssue::Rejected(path, reason) if path.ends_with("no json.json") => Some(reason),
+            _ => None
+        });
+        assert!(reason.is_some_and(|reason| reason.starts_with("no valid JSON") && reason.contains("line 1 column")), "Expected the position of the JSON error, got {reason:?}");
+
+        assert!(
+            issues.contains(&ModelIssue::Duplicate(
+                ModelName(AttrName("movieClones".to_string())),
+                vec!(PathBuf::from("./testing/model/dup1.json"), PathBuf::from("./testing/model/dup2.json"))
+            )),
+            "Expected an issue for models with the same name"
+        );
+        assert!(
+            issues.contains(&ModelIssue::Duplicate(
+                ModelName(AttrName("clonedMovies".to_string())),
+                vec!(PathBuf::from("./testing/model/inflection_dup1.json"), PathBuf::from("./testing/model/inflection_dup2.json"))
+            )),
+            "Expected an issue for models with the same plural name"
+        );
+        assert_eq!(issues.len(), rejected.len() + 2, "Unexpected issues");
+
+        assert!(diagnose_models(Path::new("./testing/model/not_existing_dir")).is_err(), "Expected error for not existing models' path");
+    }
+
+    #[test]
+    fn test_diagnose_history_models() {
+        let (models, issues) = diagnose_models(Path::new("./testing/model_history")).unwrap();
+        assert_eq!(
+            models.iter().map(|model| model.model_name.0.0.as_str()).collect::<Vec<&str>>(),
+            vec!("movieHistory"),
+            "Expected only the model without colliding history model"
+        );
+        assert!(
+            matches!(issues.as_slice(), [ModelIssue::Rejected(path, reason)] if path.ends_with("movie.json") && reason.contains("movieHistory")),
+            "Expected an issue for the model whose history model collides with another model, got {issues:?}"
+        );
+    }
+
+    #[test]
+    fn test_check_rules() {
+        let movie_model = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("endYear".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("title".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            rules: Some(HashMap::from([
+                ("endAfterStart".to_string(), "endYear >= year".to_string()),
+                ("endNeedsYear".to_string(), "endYear == null || year != null".to_string()),
+                ("titleIsNoNumber".to_string(), "title + 1".to_string())
+            ])),
+            ..base_model()
+        };
+
+        for (input, violated) in [
+            (r#"{"id": 1, "year": 1999, "endYear": 2003}"#, None),
+            (r#"{"id": 1}"#, None),
+            (r#"{"id": 1, "year": 2003, "endYear": 1999}"#, Some("endAfterStart")),
+            (r#"{"id": 1, "endYear": 1999}"#, Some("endNeedsYear")),
+            (r#"{"id": 1, "title": "Matrix"}"#, Some("titleIsNoNumber"))
+        ] {
+            let record: Record = add_null_values(parse_record(input, &movie_model).unwrap(), &movie_model);
+            match (check_rules(&record, &movie_model), violated) {
+                (Ok(()), None) => (),
+                (Err(err), Some(rule)) => assert!(err.to_string().contains(&format!("{rule:?}")), "Expected the error to name rule {rule:?}, got {err}"),
+                (result, _) => panic!("Unexpected result {result:?} of the rules for {input}")
+            }
+        }
+
+        // updates don't see the stored hashes of hashed attributes, only the given values
+        let account_model = ModelDefinition {
+            model_name: ModelName(AttrName("account".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("password".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("locked".to_string()), AttrType::Primitive(PrimitiveType::Boolean))
+            ]),
+            hashed: Some(vec!(AttrName("password".to_string()))),
+            rules: Some(HashMap::from([
+                ("lockedNeedsDefaultPassword".to_string(), "!locked || password == 'secret'".to_string())
+            ])),
+            ..base_model()
+        };
+        let stored: Record = Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("password".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo".to_string())))),
+            (AttrName("locked".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Boolean(false))))
+        ]);
+        assert!(prepare_update(&account_model, Some(&stored), vec!(TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))), r#"{"locked": true}"#, None).is_ok(), "Expected rules not to see the stored hash");
+        assert!(prepare_update(&account_model, Some(&stored), vec!(TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))), r#"{"locked": true, "password": "secret"}"#, None).is_ok(), "Unexpected violation for the given password");
+        assert!(prepare_update(&account_model, Some(&stored), vec!(TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))), r#"{"locked": true, "password": "other"}"#, None).is_err(), "Expected a violation for the given password");
+    }
+}
@@ -1,562 +1,1197 @@
-// use apollo_compiler::hir;
-use serde::ser;
-
-// used types
-use apollo_compiler::execution::GraphQLError;
-use apollo_compiler::validation::Valid;
-use std::collections::HashMap;
-use super::{
-    TruePrimitiveType,
-    ModelDefinition,
-    PrimitiveType,
-    AttrName,
-    AttrType,
-    TrueType,
-    Record,
-    NULL
-};
-use serde_derive::{
-    Deserialize,
-    Serialize
-};
-use apollo_compiler::{
-    ExecutableDocument,
-    Parser,
-    Schema,
-    Node
-};
-use apollo_compiler::executable::{
-    OperationType,
-    SelectionSet,
-    Operation,
-    Selection,
-    Field,
-    Type
-};
-use apollo_compiler::schema::{
-    FieldDefinition,
-    ExtendedType,
-    NamedType
-};
-
-// used macros
-use apollo_compiler::name as named_type;
-
-// used functions
-use serde_json::from_str;
-use super::{
-    parse_models,
-    create_one,
-    read_one,
-    update_one,
-    delete_one
-};
-
-type Errors = Vec<GraphQLError>;
-type FieldName = String;
-
-#[derive(Clone)]
-enum FieldValue {
-    Scalar(TrueType),
-    Objects(Vec<Data>),
-    Object(Data)
-}
-
-#[derive(Clone)]
-pub struct Data {
-    map: Vec<(FieldName, FieldValue)>
-}
-
-impl Data {
-    fn new() -> Self {
-        Self {
-            map: vec!()
-        }
-    }
-    fn insert(&mut self, key: FieldName, value: FieldValue) {
-        self.map.push((key, value));
-    }
-    fn get(&self, key: &FieldName) -> Option<FieldValue> {
-        let index: usize = self.map.iter().position(|entry| &entry.0 == key)?;
-        Some(self.map[index].1.clone())
-    }
-    fn is_empty(&self) -> bool {
-        self.map.is_empty()
-    }
-    fn append(&mut self, mut other: Data) {
-        self.map.append(&mut other.map);
-    }
-}
-
-impl From<Vec<(FieldName, FieldValue)>> for Data {
-    fn from(vec: Vec<(FieldName, FieldValue)>) -> Self {
-        Self {
-            map: vec
-        }
-    }
-}
-
-impl ser::Serialize for Data {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: ser::Serializer,
-    {
-        use ser::SerializeMap;
-        let mut map = serializer.serialize_map(Some(self.map.len()))?;
-        for (k, v) in &self.map {
-            match v {
-                FieldValue::Scalar(field) => map.serialize_entry(k, field)?,
-                FieldValue::Object(data) => map.serialize_entry(k, data)?,
-                FieldValue::Objects(data) => map.serialize_entry(k, data)?
-            }
-        }
-        map.end()
-    }
-}
-
-
-#[derive(Serialize)]
-pub struct GraphQLReturn {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub errors: Option<Errors>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<Data>
-}
-
-impl From<&str> for GraphQLReturn {
-    fn from(error_message: &str) -> Self {
-        Self {
-            errors: Some(Errors::from([
-                GraphQLError {
-                    message: error_message.to_string(),
-                    locations: vec!()
-                }
-            ])),
-            data: None
-        }
-    }
-}
-
-impl From<Errors> for GraphQLReturn {
-    fn from(errors: Errors) -> Self {
-        Self {
-            errors: Some(errors),
-            data: None
-        }
-    }
-}
-
-impl From<Data> for GraphQLReturn {
-    fn from(data: Data) -> Self {
-        Self {
-            data: Some(data),
-            errors: None
-        }
-    }
-}
-
-#[derive(Deserialize)]
-#[allow(non_snake_case)]
-pub struct GraphQLPost {
-    query: String,
-    operationName: Option<String>,
-    variables: Option<HashMap<String, String>>,
-}
-
-impl From<String> for GraphQLPost {
-    fn from(value: String) -> Self {
-        Self {
-            query: value,
-            operationName: None,
-            variables: None
-        }
-    }
-}
-
-impl TryFrom<&str> for GraphQLPost {
-    type Error = GraphQLReturn;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match from_str::<Self>(value) {
-            Ok(post) => Ok(post),
-            Err(_) => Err(Self::Error::from("invalid body"))
-        }
-    }
-}
-
-fn create_schema() -> String {
-    if let Some(args) = crate::cli::get_valid_start_args() {
-        let mut type_definitions: String = String::new();
-        let mut query_resolvers: Vec<String> = vec!();
-        let mut mutation_resolvers: Vec<String> = vec!();
-        let mut subscription_resolvers: Vec<String> = vec!();
-
-        let models: Vec<ModelDefinition> = match parse_models(args.modelspath.as_path()) {
-            Ok(models) => models,
-            Err(_) => return String::new()
-        };
-
-        for model in models {
-            let pasc_sing_model_name: &str = &model.model_name.pascal().singular().0.0;
-
-            let mut type_def: String = format!("type {pasc_sing_model_name} {{");
-            let mut update_one: String = format!(" updateOne{pasc_sing_model_name}(");
-            let mut create_one: String = format!(" addOne{pasc_sing_model_name}(");
-
-            let mut attributes: Vec<(&AttrName, &AttrType)> = model.attributes.iter().collect();
-            attributes.sort_by(|(a, _), (b, _)| {
-                if a == &&model.primary_key {
-                    std::cmp::Ordering::Less
-                } else if b == &&model.primary_key {
-                    std::cmp::Ordering::Greater
-                } else {
-                    a.0.cmp(&b.0)
-                }
-            });
-            for (attr_name, attr_type) in attributes {
-                let gql_type = match attr_type {
-                    AttrType::Primitive(prim) => to_gql_type(prim),
-                    AttrType::Array(arr) => format!("[{ty}!]", ty=to_gql_type(&arr[0])),
-                };
-                let attr: &str = attr_name.0.as_str();
-                let attr_ty: &str = gql_type.as_str();
-                
-                update_one.push_str(format!(" {attr}:{attr_ty}").as_str());
-                create_one.push_str(format!(" {attr}:{attr_ty}").as_str());
-
-                if &model.primary_key == attr_name {
-                    query_resolvers.push(format!(" readOne{pasc_sing_model_name}({attr}:{attr_ty}!):{pasc_sing_model_name}!"));
-                    mutation_resolvers.push(format!(" deleteOne{pasc_sing_model_name}({attr}:{attr_ty}!):{pasc_sing_model_name}!"));
-                    update_one.push('!');
-                }
-                type_def.push_str(format!(" {attr}:{attr_ty}").as_str());
-                if model.required.contains(attr_name) {
-                    create_one.push('!');
-                    type_def.push('!');
-                }
-            }
-            mutation_resolvers.push(format!("{update_args}):{pasc_sing_model_name}!", update_args=update_one.as_str()));
-            mutation_resolvers.push(format!("{create_args}):{pasc_sing_model_name}!", create_args=create_one.as_str()));
-            type_def.push('}');
-            type_definitions.push_str(type_def.as_str());
-        }
-
-        if !query_resolvers.is_empty() {
-            type_definitions.push_str(format!("type Query{{{resolvers}}}", resolvers=query_resolvers.join(" ").as_str()).as_str());
-        }
-        if !mutation_resolvers.is_empty() {
-            type_definitions.push_str(format!("type Mutation{{{resolvers}}}", resolvers=mutation_resolvers.join(" ").as_str()).as_str());
-        }
-        if !subscription_resolvers.is_empty() {
-            type_definitions.push_str(format!("type Subscription{{{resolvers}}}", resolvers=subscription_resolvers.join(" ").as_str()).as_str());
-        }
-
-        return type_definitions;
-    }
-    unreachable!("creating GraphQL schemas is only used for handling HTTP requests, so when the server runs")
-}
-
-fn to_gql_type(prim_type: &PrimitiveType) -> String {
-    match prim_type {
-        PrimitiveType::Integer => "Int".to_string(),
-        PrimitiveType::String => "String".to_string(),
-        PrimitiveType::Boolean => "Boolean".to_string()
-    }
-}
-
-pub fn handle_gql_post(body: GraphQLPost) -> GraphQLReturn {
-    let mut parser = Parser::new(); //.token_limit(...).recursion_limit(...) TODO!
-
-    #[cfg(test)]
-    parser.parse_schema(create_schema(), "schema").unwrap().validate().unwrap();
-
-    let schema: &Valid<Schema> = &Valid::assume_valid(parser.parse_schema(create_schema(), "schema").unwrap());
-    let document: ExecutableDocument = match parser.parse_executable(schema, &body.query, "query") {
-        Ok(doc) => doc,
-        Err(err) => return GraphQLReturn::from(err.errors.iter().map(|d| d.to_json()).collect::<Errors>()),
-    };
-
-    let valid_document: &Valid<ExecutableDocument> = &match document.validate(schema) {
-        Ok(valid_doc) => valid_doc,
-        Err(err) => return GraphQLReturn::from(err.errors.iter().map(|d| d.to_json()).collect::<Errors>()),
-    };
-
-    match get_executing_operation(valid_document, body.operationName) {
-        Ok(op) => execute_operation(op, schema, valid_document),
-        Err(ret) => ret
-    }
-}
-
-fn get_executing_operation(document: &Valid<ExecutableDocument>, operation_name: Option<String>) -> Result<&Node<Operation>, GraphQLReturn> {
-    let mut operations /* impl Iterator<Item = &'_ Node<Operation>> */ = document.all_operations();
-
-    if operations.next().is_none() {
-        return Err(GraphQLReturn::from("document does not contain any executable operations"));
-    }
-    if operation_name.is_none() && operations.next().is_some() {
-        return Err(GraphQLReturn::from("document contains more than one operation, missing operation name"));
-    }
-
-    match document.get_operation(operation_name.as_deref()) {
-        Ok(o) => Ok(o),
-        Err(_) => Err(GraphQLReturn::from(format!("operation with name {name:?} does not exist", name=operation_name.unwrap().as_str()).as_str()))
-    }
-}
-
-fn execute_operation(operation: &Node<Operation>, schema: &Valid<Schema>, document: &Valid<ExecutableDocument>) -> GraphQLReturn {
-    let mut data = Data::new();
-    let mut errors = Errors::new();
-    for root_resolver in &operation.selection_set.selections {
-        let field: &Node<Field> = match root_resolver {
-            Selection::Field(field) => field,
-            Selection::FragmentSpread(_) => todo!(),
-            Selection::InlineFragment(_) => todo!()
-        };
-        
-        match field.name.as_str() {
-            "__schema" => {
-                let record = &Data::from(vec![
-                    (FieldName::from("types"), resolve_type_system(schema)),
-                    (FieldName::from("queryType"), FieldValue::Object(resolve_type_definition(&named_type!("Query"), schema).unwrap())),
-                    (FieldName::from("mutationType"), FieldValue::Object(resolve_type_definition(&named_type!("Mutation"), schema).unwrap())),
-                    // (FieldName::from("subscriptionType"), FieldValue::Object(resolve_type_definition(named_type!("Subscription"), schema).unwrap())),
-                    (FieldName::from("subscriptionType"), FieldValue::Scalar(NULL)),
-                    (FieldName::from("directives"), FieldValue::Scalar(TrueType::Array(Some(vec!())))) // directives currently not supported, so ther are none
-                ]);
-                data.insert(FieldName::from(field.response_key().as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), record, document)));
-            },
-            "__type" => match resolve_type_definition(&NamedType::new_unchecked(field.arguments[0].value.as_str().unwrap().into()), schema) {
-                Some(res) => data.insert(FieldName::from(field.name.as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), &res, document))),
-                None => data.insert(FieldName::from(field.name.as_str()), FieldValue::Scalar(NULL))
-            },
-            "__typename" => data.insert(FieldName::from(field.name.as_str()), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(operation.operation_type.default_type_name().to_string()))))),
-
-            resolver_name => {
-                let prefix: &str = match operation.operation_type {
-                    OperationType::Query => {
-                        if resolver_name.starts_with("readOne") {
-                            "readOne"
-                        } else {
-                            ""  // readMany has no prefix because it's the plural variant of the model name
-                        }
-                    },
-                    OperationType::Mutation => {
-                        if resolver_name.starts_with("addOne") {
-                            "addOne"
-                        } else if resolver_name.starts_with("updateOne") {
-                            "updateOne"
-                        } else {
-                            "deleteOne" // operation is expected to be validated by handle_gql_post
-                        }
-                    },
-                    OperationType::Subscription => todo!(),
-                };
-
-                let args: HashMap<&str, TrueType> = HashMap::from_iter(
-                    field.arguments.iter().map(|arg| {
-                        if let Some(arr) = arg.value.as_list() {
-                            (arg.name.as_str(), TrueType::Array(Some(arr.iter().map(|a| from_str::<TruePrimitiveType>(&a.to_string()).unwrap()).collect::<Vec<TruePrimitiveType>>())))
-                        } else {
-                            (arg.name.as_str(), from_str::<TrueType>(&arg.value.to_string()).unwrap())
-                        }
-                    })
-                );
-
-                let record: Result<Record, std::io::Error> = match prefix {
-                    "addOne" => create_one(resolver_name.strip_prefix(prefix).unwrap(), serde_json::to_string(&args).unwrap().as_str()),
-                    "readOne" => {
-                        let model_name: &str = resolver_name.strip_prefix(prefix).unwrap();
-                        let id: &str = &args.values().next().unwrap().to_string();
-                        read_one(model_name, id)
-                    },
-                    "updateOne" => {
-                        let id_attr_name: &str = field.arguments[0].name.as_str();
-                        update_one(resolver_name.strip_prefix(prefix).unwrap(), &args.get(id_attr_name).unwrap().to_string(), serde_json::to_string(&args).unwrap().as_str())
-                    },
-                    "deleteOne" => {
-                        let model_name: &str = resolver_name.strip_prefix(prefix).unwrap();
-                        let id: &str = &args.values().next().unwrap().to_string();
-                        delete_one(model_name, id)
-                    },
-                    "" => todo!(),
-                    _ => unreachable!("there are currently only five root resolver types")
-                };
-
-                match record {
-                    Ok(record) => {
-                        let mut fields = Data::new();
-                        for (attr_name, value) in record {
-                            fields.insert(attr_name.0, FieldValue::Scalar(value));
-                        }
-                        data.insert(FieldName::from(field.response_key().as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), &fields, document)));
-                    },
-                    Err(err) => errors.append(&mut vec!(GraphQLError {
-                        message: err.to_string(),
-                        locations: vec!()
-                    }))
-                }
-            }
-        }
-    }
-
-    if errors.is_empty() {
-        return GraphQLReturn::from(data);
-    } else if data.is_empty() {
-        return GraphQLReturn::from(errors);
-    }
-
-    GraphQLReturn {
-        errors: Some(errors),
-        data: Some(data)
-    }
-}
-
-fn resolve_selection_set_order(selection_set: &SelectionSet, resolver_ty: &Type,  field_data: &Data, document: &Valid<ExecutableDocument>) -> Data {
-    let mut data = Data::new();
-    for sel in &selection_set.selections {
-        match sel {
-            Selection::Field(sel_field) => {
-                match field_data.get(&FieldName::from(sel_field.name.as_str())) {
-                    Some(FieldValue::Objects(sub_data)) => {
-                        let resolved: Vec<Data> = sub_data.iter().map(|d| resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), d, document)).collect();
-                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Objects(resolved));
-                    },
-                    Some(FieldValue::Object(sub_data)) => {
-                        let resolved: Data = resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), &sub_data, document);
-                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Object(resolved));
-                    },
-                    Some(scalar) => data.insert(FieldName::from(sel_field.response_key().as_str()), scalar),
-                    None => {
-                        assert_eq!(sel_field.name.as_str(), "__typename", "Unhandled field \"{field}\" in graphql request", field=sel_field.name.as_str());
-                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(resolver_ty.inner_named_type().to_string())))));
-                    }
-                }
-            },
-            Selection::FragmentSpread(frag) => data.append(resolve_selection_set_order(&document.fragments.get(&frag.fragment_name).unwrap().selection_set, resolver_ty, field_data, document)),
-            Selection::InlineFragment(frag) => data.append(resolve_selection_set_order(&frag.selection_set, resolver_ty, field_data, document))
-        }
-    }
-
-    data
-}
-
-fn resolve_type_system(schema: &Valid<Schema>) -> FieldValue {
-    let mut types: Vec<Data> = vec!();
-    for ty_def in schema.types.keys() {
-        if let Some(res) = resolve_type_definition(ty_def, schema) {
-            types.push(res);
-        }
-    }
-
-    FieldValue::Objects(types)
-}
-
-fn resolve_type_definition(ty_name: &NamedType, schema: &Valid<Schema>) -> Option<Data> {
-    if ty_name.as_str().starts_with("__") {
-        return None; // don't resolve unnecessarily introspection types
-    }
-    let mut data = Data::new();
-
-    let ty_def: &ExtendedType = schema.types.get(ty_name)?;
-    
-    data.insert(FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(ty_name.to_string())))));
-
-    match ty_def {
-        ExtendedType::Object(def) => {
-            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("OBJECT".to_string())))));
-            match &def.description {
-                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
-                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
-            }
-            let fields: Vec<Data> = def.fields.values().map(|f| resolve_field_definition(&f.node, schema)).collect();
-            data.insert(FieldName::from("fields"), FieldValue::Objects(fields));
-        },
-        ExtendedType::Scalar(def) => {
-            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("SCALAR".to_string())))));
-            match &def.description {
-                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
-                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
-            }
-            data.insert(FieldName::from("fields"), FieldValue::Scalar(NULL));
-        },
-        _ => return None
-    }
-
-    data.insert(FieldName::from("ofType"), FieldValue::Scalar(NULL)); // a type has no ofType if it has a TypeDefinition
-
-    // the following fields get default values because they are currently not used
-    data.insert(FieldName::from("interfaces"), FieldValue::Scalar(TrueType::Array(Some(vec!()))));
-    data.insert(FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects enums, not used
-    data.insert(FieldName::from("possibleTypes"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects interfaces
-    data.insert(FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects input types, not used
-
-    Some(data)
-}
-
-fn resolve_type(ty: &Type, schema: &Valid<Schema>) -> FieldValue {
-    let mut resolved = Data::from(vec![
-        (FieldName::from("name"), FieldValue::Scalar(NULL)),
-        (FieldName::from("description"), FieldValue::Scalar(NULL)),
-        (FieldName::from("fields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
-        (FieldName::from("interfaces"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
-        (FieldName::from("possibleTypes"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
-        (FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
-        (FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!()))))
-    ]);
-    match ty {
-        Type::List(ty) => {
-            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("LIST".to_string())))));
-            resolved.insert(FieldName::from("ofType"), resolve_type(ty, schema));
-        },
-        Type::NonNullList(ty) => {
-            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("NON_NULL".to_string())))));
-            resolved.insert(FieldName::from("ofType"), resolve_type(&Type::List(ty.clone()), schema));
-        },
-        Type::NonNullNamed(name) => {
-            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("NON_NULL".to_string())))));
-            resolved.insert(FieldName::from("ofType"), resolve_type(&Type::Named(name.clone()), schema));
-        },
-        Type::Named(name) =>{
-            return match resolve_type_definition(name, schema) {
-                Some(res) => FieldValue::Object(res),
-                None => FieldValue::Scalar(NULL)
-            }
-        }
-    };
-
-    FieldValue::Object(resolved)
-}
-
-fn resolve_field_definition(field: &Node<FieldDefinition>, schema: &Valid<Schema>) -> Data {  // __Field
-    let mut data = Data::new();
-    data.insert(FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(field.name.to_string())))));
-    match &field.description {
-        Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
-        None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
-    }
-
-    let args: Vec<Data> = field.arguments.iter().map(|a| {
-        let mut data = Data::from(vec![
-            (FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(a.name.to_string()))))),
-            (FieldName::from("type"), resolve_type(&a.ty, schema))
-        ]);
-        match &a.description {
-            Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
-            None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
-        }
-        match &a.default_value {
-            Some(val) => data.insert(FieldName::from("defaultValue"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(val.to_string()))))),
-            None => data.insert(FieldName::from("defaultValue"), FieldValue::Scalar(NULL))
-        }
-        data
-    }).collect();
-
-    data.insert(FieldName::from("args"), FieldValue::Objects(args));
-
-    data.insert(FieldName::from("type"), resolve_type(&field.ty, schema));
-    data.insert(FieldName::from("isDeprecated"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::Boolean(false)))));
-    data.insert(FieldName::from("deprecationReason"), FieldValue::Scalar(NULL));
-
-    data
-}
\ No newline at end of file
+// use apollo_compiler::hir;
+use serde::ser;
+
+// used types
+use apollo_compiler::execution::GraphQLError;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ast::Value as GraphQLValue;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use super::{
+    TruePrimitiveType,
+    AssociationKind,
+    ModelDefinition,
+    Association,
+    DefaultValue,
+    ModelName,
+    PrimitiveType,
+    AttrName,
+    AttrType,
+    TrueType,
+    Record,
+    NULL,
+    DELETED_AT,
+    EXPIRES_AT,
+    HISTORY,
+    VERSION,
+    Naming,
+    Ttl
+};
+use serde_derive::{
+    Deserialize,
+    Serialize
+};
+use apollo_compiler::{
+    ExecutableDocument,
+    Parser,
+    Schema,
+    Node
+};
+use apollo_compiler::executable::{
+    OperationType,
+    SelectionSet,
+    Operation,
+    Selection,
+    Field,
+    Type
+};
+use apollo_compiler::schema::{
+    InputValueDefinition,
+    FieldDefinition,
+    ExtendedType,
+    NamedType
+};
+
+// used macros
+use apollo_compiler::name as named_type;
+
+// used functions
+use serde_json::{
+    from_value,
+    from_str
+};
+use super::{
+    parse_models,
+    parse_all_models,
+    parse_model,
+    find_model,
+    create_one,
+    read_association,
+    read_one,
+    read_one_with_deleted,
+    read_one_as_of,
+    read_history_of,
+    update_one,
+    delete_one,
+    link_one,
+    unlink_one,
+    restore_one,
+    purge_one,
+    verify_one
+};
+
+type Errors = Vec<GraphQLError>;
+type FieldName = String;
+
+#[derive(Clone)]
+enum FieldValue {
+    Scalar(TrueType),
+    Objects(Vec<Data>),
+    Object(Data),
+    Association(ModelName, AttrName, TrueType),  // gets resolved only if selected, holds the model, the association's name and the value of the referencing key
+    History(ModelName, Vec<TrueType>)  // gets resolved only if selected, holds the model and the key of the record
+}
+
+#[derive(Clone)]
+pub struct Data {
+    map: Vec<(FieldName, FieldValue)>
+}
+
+impl Data {
+    fn new() -> Self {
+        Self {
+            map: vec!()
+        }
+    }
+    fn insert(&mut self, key: FieldName, value: FieldValue) {
+        self.map.push((key, value));
+    }
+    fn get(&self, key: &FieldName) -> Option<FieldValue> {
+        let index: usize = self.map.iter().position(|entry| &entry.0 == key)?;
+        Some(self.map[index].1.clone())
+    }
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+    fn append(&mut self, mut other: Data) {
+        self.map.append(&mut other.map);
+    }
+}
+
+impl From<Vec<(FieldName, FieldValue)>> for Data {
+    fn from(vec: Vec<(FieldName, FieldValue)>) -> Self {
+        Self {
+            map: vec
+        }
+    }
+}
+
+impl ser::Serialize for Data {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.map.len()))?;
+        for (k, v) in &self.map {
+            match v {
+                FieldValue::Scalar(field) => map.serialize_entry(k, field)?,
+                FieldValue::Object(data) => map.serialize_entry(k, data)?,
+                FieldValue::Objects(data) => map.serialize_entry(k, data)?,
+                FieldValue::Association(..) | FieldValue::History(..) => map.serialize_entry(k, &NULL)?  // unresolved associations and histories aren't selected
+            }
+        }
+        map.end()
+    }
+}
+
+
+#[derive(Serialize)]
+pub struct GraphQLReturn {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Errors>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Data>
+}
+
+impl From<&str> for GraphQLReturn {
+    fn from(error_message: &str) -> Self {
+        Self {
+            errors: Some(Errors::from([
+                GraphQLError {
+                    message: error_message.to_string(),
+                    locations: vec!()
+                }
+            ])),
+            data: None
+        }
+    }
+}
+
+impl From<Errors> for GraphQLReturn {
+    fn from(errors: Errors) -> Self {
+        Self {
+            errors: Some(errors),
+            data: None
+        }
+    }
+}
+
+impl From<Data> for GraphQLReturn {
+    fn from(data: Data) -> Self {
+        Self {
+            data: Some(data),
+            errors: None
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[allow(non_snake_case)]
+pub struct GraphQLPost {
+    query: String,
+    operationName: Option<String>,
+    variables: Option<HashMap<String, String>>,
+}
+
+impl From<String> for GraphQLPost {
+    fn from(value: String) -> Self {
+        Self {
+            query: value,
+            operationName: None,
+            variables: None
+        }
+    }
+}
+
+impl TryFrom<&str> for GraphQLPost {
+    type Error = GraphQLReturn;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match from_str::<Self>(value) {
+            Ok(post) => Ok(post),
+            Err(_) => Err(Self::Error::from("invalid body"))
+        }
+    }
+}
+
+fn create_schema() -> String {
+    if let Some(args) = crate::cli::get_valid_start_args() {
+        return match parse_models(args.modelspath.as_path()) {
+            Ok(models) => schema_of(&models),
+            Err(_) => String::new()
+        };
+    }
+    unreachable!("creating GraphQL schemas is only used for handling HTTP requests, so when the server runs")
+}
+
+/*
+    schema_of: 
+        Builds the GraphQL schema of the given models,
+        every model gets its type, query and mutation resolvers, described by the descriptions of the model definitions.
+
+    returns:
+        the type definitions of the schema in SDL
+*/
+fn schema_of(models: &[ModelDefinition]) -> String {
+    // custom scalars for the temporal primitive types
+    let mut type_definitions: String = String::from(concat!(
+        "\"A calendar date, formatted as YYYY-MM-DD\" scalar Date ",
+        "\"A date with time and timezone, formatted as RFC 3339\" scalar DateTime ",
+        "\"A time of day, formatted as hh:mm:ss\" scalar Time "
+    ));
+    let mut query_resolvers: Vec<String> = vec!();
+    let mut mutation_resolvers: Vec<String> = vec!();
+    let mut subscription_resolvers: Vec<String> = vec!();
+
+    let model_names: Vec<ModelName> = models.iter().map(|model| model.model_name.plural().camel()).collect();
+
+    for model in models {
+        let pasc_sing_model_name: &str = &model.model_name.pascal().singular().0.0;
+
+        let mut type_def: String = format!("{description}type {pasc_sing_model_name} {{", description=to_gql_description(model.description.as_deref()));
+        let mut update_one: String = format!(" updateOne{pasc_sing_model_name}(");
+        let mut create_args: Vec<String> = vec!();
+        let mut key_args: Vec<String> = vec!();
+
+        // primary key attributes first in order of the key, the others alphabetically
+        let key_attrs: Vec<AttrName> = model.primary_key.attrs();
+        let secret_attrs: Vec<AttrName> = model.secret_attrs();
+        let mut attributes: Vec<(&AttrName, &AttrType)> = model.attributes.iter().collect();
+        attributes.sort_by_key(|(attr_name, _)| (key_attrs.iter().position(|key_attr| &key_attr == attr_name).unwrap_or(key_attrs.len()), attr_name.0.clone()));
+        for (attr_name, attr_type) in attributes {
+            let (gql_type, gql_input_type) = to_gql_attr_types(pasc_sing_model_name, attr_name, attr_type, model.naming.as_ref(), &mut type_definitions);
+            let exposed_name: AttrName = model.exposed_name(attr_name);
+            let attr: &str = exposed_name.0.as_str();
+            let attr_ty: &str = gql_type.as_str();
+            let attr_input_ty: &str = gql_input_type.as_str();
+            let description: String = to_gql_description(model.descriptions.as_ref().and_then(|descriptions| descriptions.get(attr_name)).map(String::as_str));
+            
+            update_one.push_str(format!(" {description}{attr}:{attr_input_ty}").as_str());
+
+            if model.primary_key.contains(attr_name) {
+                key_args.push(format!("{description}{attr}:{attr_ty}!"));
+                update_one.push('!');
+            }
+            // write-only and hashed attributes are input arguments only
+            if !secret_attrs.contains(attr_name) {
+                type_def.push_str(format!(" {description}{attr}:{attr_ty}").as_str());
+                if model.required.contains(attr_name) {
+                    type_def.push('!');
+                }
+            }
+
+            // generated keys must not be given when creating records
+            if model.primary_key.contains(attr_name) && model.primary_key_generator.is_some() {
+                continue;
+            }
+            let mut create_arg: String = format!("{description}{attr}:{attr_input_ty}");
+            if model.required.contains(attr_name) {
+                create_arg.push('!');
+            }
+            // generated defaults can't be expressed in the schema, they get applied by create_one,
+            // so do the defaults of secrets, which must not be visible via introspection
+            if let (Some(DefaultValue::Literal(value)), false) = (model.defaults.as_ref().and_then(|defaults| defaults.get(attr_name)), secret_attrs.contains(attr_name)) {
+                create_arg.push_str(format!(" = {literal}", literal=to_gql_literal(value, attr_type, model.naming.as_ref())).as_str());
+            }
+            create_args.push(create_arg);
+        }
+
+        // timestamps are output fields only, they are managed by the server,
+        // records stored before enabling them don't have them
+        for attr in model.timestamp_attrs() {
+            type_def.push_str(format!(" {attr}:DateTime", attr=model.exposed_name(&attr).0).as_str());
+        }
+        if model.soft_delete {
+            type_def.push_str(format!(" {attr}:DateTime", attr=model.exposed_name(&AttrName(DELETED_AT.to_string())).0).as_str());
+        }
+        if let Some(Ttl::Seconds(_)) = model.ttl {
+            type_def.push_str(format!(" {attr}:DateTime", attr=model.exposed_name(&AttrName(EXPIRES_AT.to_string())).0).as_str());
+        }
+        // the history lists the changes of a record, each holding the record before and after the change
+        if model.history {
+            type_def.push_str(format!(" {HISTORY}:[{pasc_sing_model_name}History!]!").as_str());
+            type_definitions.push_str(format!("type {pasc_sing_model_name}History {{operation:String! at:DateTime! previous:{pasc_sing_model_name} record:{pasc_sing_model_name}}}").as_str());
+        }
+        // versions are output fields, the expected version is given as argument when changing a record,
+        // records stored before enabling versions don't have one
+        let mut version_arg: &str = "";
+        if model.versioned {
+            type_def.push_str(format!(" {attr}:Int", attr=model.exposed_name(&AttrName(VERSION.to_string())).0).as_str());
+            update_one.push_str(" expectedVersion:Int");
+            version_arg = " expectedVersion:Int";
+        }
+
+        // associations are output fields only, they are set via their foreign keys
+        let mut associations: Vec<(&AttrName, &Association)> = model.associations.iter().flatten().collect();
+        associations.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+        for (name, association) in associations {
+            // associations to not existing models can't be resolved
+            if !model_names.contains(&association.model.plural().camel()) {
+                continue;
+            }
+            let assoc_type: &str = &association.model.pascal().singular().0.0;
+            let field: AttrName = model.exposed_name(name);
+            let description: String = to_gql_description(model.descriptions.as_ref().and_then(|descriptions| descriptions.get(name)).map(String::as_str));
+            match association.kind {
+                AssociationKind::BelongsTo => type_def.push_str(format!(" {description}{field}:{assoc_type}", field=field.0).as_str()),
+                AssociationKind::HasMany => type_def.push_str(format!(" {description}{field}:[{assoc_type}!]!", field=field.0).as_str()),
+                AssociationKind::ManyToMany => {
+                    type_def.push_str(format!(" {description}{field}:[{assoc_type}!]!", field=field.0).as_str());
+
+                    // linking needs the single primary key of the other model
+                    let other: &ModelDefinition = models.iter().find(|other| other.model_name.plural().camel() == association.model.plural().camel()).unwrap();
+                    if let [other_key_attr] = other.primary_key.attrs().as_slice() {
+                        if let Some(AttrType::Primitive(other_key_type)) = other.attributes.get(other_key_attr) {
+                            let link_name: String = link_resolver_name(model, name);
+                            let link_args: String = format!("{key_args} {other_key}:{ty}!", key_args=key_args.join(" "), other_key=model.exposed_name(association.other_key.as_ref().unwrap()).0, ty=to_gql_type(other_key_type));
+                            mutation_resolvers.push(format!(" link{link_name}({link_args}):{assoc_type}!"));
+                            mutation_resolvers.push(format!(" unlink{link_name}({link_args}):{assoc_type}!"));
+                        }
+                    }
+                }
+            }
+        }
+        let mut read_args: Vec<String> = key_args.clone();
+        if model.soft_delete {
+            read_args.push("withDeleted:Boolean = false".to_string());
+            mutation_resolvers.push(format!(" restoreOne{pasc_sing_model_name}({key_args}):{pasc_sing_model_name}!", key_args=key_args.join(" ")));
+            mutation_resolvers.push(format!(" purgeOne{pasc_sing_model_name}({key_args}):{pasc_sing_model_name}!", key_args=key_args.join(" ")));
+        }
+        if model.history {
+            read_args.push("asOf:DateTime".to_string());
+        }
+        if let Some(hashed) = model.hashed.as_ref().filter(|hashed| !hashed.is_empty()) {
+            let hashed_args: Vec<String> = hashed.iter().map(|attr| format!("{attr}:String", attr=model.exposed_name(attr).0)).collect();
+            query_resolvers.push(format!(" verify{pasc_sing_model_name}({key_args} {hashed_args}):Boolean!", key_args=key_args.join(" "), hashed_args=hashed_args.join(" ")));
+        }
+        query_resolvers.push(format!(" readOne{pasc_sing_model_name}({read_args}):{pasc_sing_model_name}!", read_args=read_args.join(" ")));
+        mutation_resolvers.push(format!(" deleteOne{pasc_sing_model_name}({key_args}{version_arg}):{pasc_sing_model_name}!", key_args=key_args.join(" ")));
+        mutation_resolvers.push(format!("{update_args}):{pasc_sing_model_name}!", update_args=update_one.as_str()));
+        // a list of arguments mustn't be empty, which happens if the model only consists of a generated key
+        if create_args.is_empty() {
+            mutation_resolvers.push(format!(" addOne{pasc_sing_model_name}:{pasc_sing_model_name}!"));
+        } else {
+            mutation_resolvers.push(format!(" addOne{pasc_sing_model_name}({create_args}):{pasc_sing_model_name}!", create_args=create_args.join(" ")));
+        }
+        type_def.push('}');
+        type_definitions.push_str(type_def.as_str());
+    }
+
+    if !query_resolvers.is_empty() {
+        type_definitions.push_str(format!("type Query{{{resolvers}}}", resolvers=query_resolvers.join(" ").as_str()).as_str());
+    }
+    if !mutation_resolvers.is_empty() {
+        type_definitions.push_str(format!("type Mutation{{{resolvers}}}", resolvers=mutation_resolvers.join(" ").as_str()).as_str());
+    }
+    if !subscription_resolvers.is_empty() {
+        type_definitions.push_str(format!("type Subscription{{{resolvers}}}", resolvers=subscription_resolvers.join(" ").as_str()).as_str());
+    }
+
+    type_definitions
+}
+
+/*
+    to_gql_attr_types: 
+        Translates an attribute's type to its GraphQL types,
+        enums and nested objects get their own type definitions named after their parent type and the attribute,
+        which are appended to the given type definitions, the nested attributes are named by the naming policy,
+        the values of enums and the nested attributes are preceded by their descriptions.
+
+    returns:
+        A tuple of the GraphQL output type and input type
+*/
+fn to_gql_attr_types(parent_type_name: &str, attr_name: &AttrName, attr_type: &AttrType, naming: Option<&Naming>, type_definitions: &mut String) -> (String, String) {
+    let type_name: String = format!("{parent_type_name}{pasc_attr_name}", pasc_attr_name=attr_name.pascal().0);
+    match attr_type {
+        AttrType::Primitive(prim) => (to_gql_type(prim), to_gql_type(prim)),
+        AttrType::Array(arr) => (format!("[{ty}!]", ty=to_gql_type(&arr[0])), format!("[{ty}!]", ty=to_gql_type(&arr[0]))),
+        AttrType::Enum(enum_def) => {
+            let values: Vec<String> = enum_def.values.iter().map(|value| format!("{description}{value}", description=to_gql_description(enum_def.descriptions.as_ref().and_then(|descriptions| descriptions.get(value)).map(String::as_str)))).collect();
+            type_definitions.push_str(format!("enum {type_name} {{{values}}}", values=values.join(" ")).as_str());
+            (type_name.clone(), type_name)
+        },
+        AttrType::Object(obj_def) => {
+            let input_type_name: String = format!("{type_name}Input");
+            let mut type_def: String = format!("type {type_name} {{");
+            let mut input_def: String = format!("input {input_type_name} {{");
+
+            let mut attributes: Vec<(&AttrName, &AttrType)> = obj_def.object.attributes.iter().collect();
+            attributes.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+            for (nested_name, nested_type) in attributes {
+                let (gql_type, gql_input_type) = to_gql_attr_types(&type_name, nested_name, nested_type, naming, type_definitions);
+                let non_null: &str = if obj_def.object.required.contains(nested_name) { "!" } else { "" };
+                let description: String = to_gql_description(obj_def.object.descriptions.as_ref().and_then(|descriptions| descriptions.get(nested_name)).map(String::as_str));
+                type_def.push_str(format!(" {description}{attr}:{gql_type}{non_null}", attr=nested_name.named(naming).0).as_str());
+                input_def.push_str(format!(" {description}{attr}:{gql_input_type}{non_null}", attr=nested_name.named(naming).0).as_str());
+            }
+            type_def.push('}');
+            input_def.push('}');
+            type_definitions.push_str(type_def.as_str());
+            type_definitions.push_str(input_def.as_str());
+
+            (type_name, input_type_name)
+        }
+    }
+}
+
+/*
+    to_gql_description: 
+        Translates an optional description to a GraphQL string preceding the described definition,
+        JSON escapes are valid in GraphQL strings too.
+
+    returns:
+        the quoted description followed by a space, or an empty String without description
+*/
+fn to_gql_description(description: Option<&str>) -> String {
+    match description {
+        Some(description) => format!("{quoted} ", quoted=serde_json::to_string(description).unwrap()),
+        None => String::new()
+    }
+}
+
+/*
+    to_gql_literal: 
+        Translates a JSON value to its GraphQL literal representation,
+        used to show default values of arguments in the schema, the fields of objects are named by the naming policy.
+
+    returns:
+        the GraphQL literal, the value is expected to fit to the given attribute type
+*/
+fn to_gql_literal(value: &JsonValue, attr_type: &AttrType, naming: Option<&Naming>) -> String {
+    match (value, attr_type) {
+        (JsonValue::String(enum_value), AttrType::Enum(_)) => enum_value.clone(),
+        (JsonValue::Array(values), _) => format!("[{values}]", values=values.iter().map(|val| to_gql_literal(val, attr_type, naming)).collect::<Vec<String>>().join(", ")),
+        (JsonValue::Object(values), AttrType::Object(obj_def)) => {
+            let mut fields: Vec<String> = values.iter()
+                .filter_map(|(key, val)| {
+                    let attr: AttrName = AttrName(key.clone());
+                    obj_def.object.attributes.get(&attr).map(|ty| format!("{key}: {literal}", key=attr.named(naming).0, literal=to_gql_literal(val, ty, naming)))
+                })
+                .collect();
+            fields.sort();
+            format!("{{{fields}}}", fields=fields.join(", "))
+        },
+        (val, _) => val.to_string()
+    }
+}
+
+/*
+    expected_version_arg:
+        Reads the optional expectedVersion argument of a mutation on a versioned model.
+
+    returns:
+        the expected version, if given
+*/
+fn expected_version_arg(args: &HashMap<&str, TrueType>) -> Option<i64> {
+    match args.get("expectedVersion") {
+        Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(version)))) => Some(*version),
+        _ => None
+    }
+}
+
+/*
+    key_arg_values: 
+        Collects the values of the primary key arguments in the order of the model's primary key,
+        since the arguments of a query may be given in any order.
+
+    returns:
+        the key values as they would occur in the URI
+*/
+fn key_arg_values(model: &ModelDefinition, args: &HashMap<&str, TrueType>) -> Vec<String> {
+    model.primary_key.attrs().iter().map(|key_attr| args.get(model.exposed_name(key_attr).0.as_str()).map(TrueType::to_string).unwrap_or_default()).collect()
+}
+
+/*
+    link_resolver_name: 
+        Builds the name of the link and unlink resolvers of a manyToMany association without their prefix.
+
+    returns:
+        the model's and the association's name in PascalCase
+*/
+fn link_resolver_name(model: &ModelDefinition, association_name: &AttrName) -> String {
+    format!("{model}{association}", model=model.model_name.pascal().singular().0.0, association=ModelName(association_name.clone()).pascal().0.0)
+}
+
+/*
+    find_link: 
+        Finds the manyToMany association belonging to a link or unlink resolver.
+
+    returns:
+        the model and the name of the association or an Error if there isn't such association
+*/
+fn find_link(link_name: &str) -> Result<(ModelDefinition, AttrName), std::io::Error> {
+    if let Some(args) = crate::cli::get_valid_start_args() {
+        for model in parse_models(args.modelspath.as_path())? {
+            let association_name: Option<AttrName> = model.associations.iter().flatten()
+                .find(|(name, association)| association.kind == AssociationKind::ManyToMany && link_resolver_name(&model, name) == link_name)
+                .map(|(name, _)| name.clone());
+            if let Some(name) = association_name {
+                return Ok((model, name));
+            }
+        }
+    }
+    Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("No association found for {link_name:?}")))
+}
+
+/*
+    resolve_link: 
+        Links or unlinks the records given by the arguments of a link or unlink resolver.
+
+    returns:
+        the linked or unlinked record and its model or an Error if it wasn't possible
+*/
+fn resolve_link(prefix: &str, link_name: &str, args: &HashMap<&str, TrueType>) -> Result<(Record, ModelDefinition), std::io::Error> {
+    let (model, association_name) = find_link(link_name)?;
+    let association: &Association = model.associations.as_ref().and_then(|associations| associations.get(&association_name)).unwrap();
+    let other: ModelDefinition = parse_model(&association.model)?;
+
+    let model_name: &str = &model.model_name.singular().0.0;
+    let key: Vec<String> = key_arg_values(&model, args);
+    let ids: &[&str] = &key.iter().map(String::as_str).collect::<Vec<&str>>();
+    let other_id: String = args.get(model.exposed_name(association.other_key.as_ref().unwrap()).0.as_str()).map(TrueType::to_string).unwrap_or_default();
+
+    let record: Record = match prefix {
+        "link" => link_one(model_name, ids, &association_name.0, &other_id)?,
+        _ => unlink_one(model_name, ids, &association_name.0, &other_id)?
+    };
+    Ok((record, other))
+}
+
+/*
+    resolve_verify: 
+        Verifies the values of hashed attributes given by the arguments of a verify resolver,
+        the other arguments are the primary key of the record.
+
+    returns:
+        true if all given values match the stored hashes, else false, or an Error if the record doesn't exist
+*/
+fn resolve_verify(model_name: &str, args: &HashMap<&str, TrueType>) -> Result<bool, std::io::Error> {
+    let model: ModelDefinition = parse_model(&ModelName(AttrName::try_from(model_name)?))?;
+    let key: Vec<String> = key_arg_values(&model, args);
+    let ids: &[&str] = &key.iter().map(String::as_str).collect::<Vec<&str>>();
+    let candidates: HashMap<&&str, &TrueType> = args.iter()
+        .filter(|(name, value)| model.hashed.iter().flatten().any(|attr| model.exposed_name(attr).0 == **name) && **value != NULL)
+        .collect();
+    verify_one(model_name, ids, serde_json::to_string(&candidates).unwrap().as_str())
+}
+
+fn to_gql_type(prim_type: &PrimitiveType) -> String {
+    match prim_type {
+        PrimitiveType::Integer => "Int".to_string(),
+        PrimitiveType::String => "String".to_string(),
+        PrimitiveType::Boolean => "Boolean".to_string(),
+        PrimitiveType::Float => "Float".to_string(),
+        PrimitiveType::Date => "Date".to_string(),
+        PrimitiveType::DateTime => "DateTime".to_string(),
+        PrimitiveType::Time => "Time".to_string()
+    }
+}
+
+pub fn handle_gql_post(body: GraphQLPost) -> GraphQLReturn {
+    let mut parser = Parser::new(); //.token_limit(...).recursion_limit(...) TODO!
+
+    #[cfg(test)]
+    parser.parse_schema(create_schema(), "schema").unwrap().validate().unwrap();
+
+    let schema: &Valid<Schema> = &Valid::assume_valid(parser.parse_schema(create_schema(), "schema").unwrap());
+    let document: ExecutableDocument = match parser.parse_executable(schema, &body.query, "query") {
+        Ok(doc) => doc,
+        Err(err) => return GraphQLReturn::from(err.errors.iter().map(|d| d.to_json()).collect::<Errors>()),
+    };
+
+    let valid_document: &Valid<ExecutableDocument> = &match document.validate(schema) {
+        Ok(valid_doc) => valid_doc,
+        Err(err) => return GraphQLReturn::from(err.errors.iter().map(|d| d.to_json()).collect::<Errors>()),
+    };
+
+    match get_executing_operation(valid_document, body.operationName) {
+        Ok(op) => execute_operation(op, schema, valid_document),
+        Err(ret) => ret
+    }
+}
+
+fn get_executing_operation(document: &Valid<ExecutableDocument>, operation_name: Option<String>) -> Result<&Node<Operation>, GraphQLReturn> {
+    let mut operations /* impl Iterator<Item = &'_ Node<Operation>> */ = document.all_operations();
+
+    if operations.next().is_none() {
+        return Err(GraphQLReturn::from("document does not contain any executable operations"));
+    }
+    if operation_name.is_none() && operations.next().is_some() {
+        return Err(GraphQLReturn::from("document contains more than one operation, missing operation name"));
+    }
+
+    match document.get_operation(operation_name.as_deref()) {
+        Ok(o) => Ok(o),
+        Err(_) => Err(GraphQLReturn::from(format!("operation with name {name:?} does not exist", name=operation_name.unwrap().as_str()).as_str()))
+    }
+}
+
+fn execute_operation(operation: &Node<Operation>, schema: &Valid<Schema>, document: &Valid<ExecutableDocument>) -> GraphQLReturn {
+    let mut data = Data::new();
+    let mut errors = Errors::new();
+    // parsed once, so the associations of all resolved records are read without parsing the models again
+    let models: &[ModelDefinition] = &parse_all_models().unwrap_or_default();
+    for root_resolver in &operation.selection_set.selections {
+        let field: &Node<Field> = match root_resolver {
+            Selection::Field(field) => field,
+            Selection::FragmentSpread(_) => todo!(),
+            Selection::InlineFragment(_) => todo!()
+        };
+        
+        match field.name.as_str() {
+            "__schema" => {
+                let record = &Data::from(vec![
+                    (FieldName::from("types"), resolve_type_system(schema)),
+                    (FieldName::from("queryType"), FieldValue::Object(resolve_type_definition(&named_type!("Query"), schema).unwrap())),
+                    (FieldName::from("mutationType"), FieldValue::Object(resolve_type_definition(&named_type!("Mutation"), schema).unwrap())),
+                    // (FieldName::from("subscriptionType"), FieldValue::Object(resolve_type_definition(named_type!("Subscription"), schema).unwrap())),
+                    (FieldName::from("subscriptionType"), FieldValue::Scalar(NULL)),
+                    (FieldName::from("directives"), FieldValue::Scalar(TrueType::Array(Some(vec!())))) // directives currently not supported, so ther are none
+                ]);
+                data.insert(FieldName::from(field.response_key().as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), record, document, models)));
+            },
+            "__type" => match resolve_type_definition(&NamedType::new_unchecked(field.arguments[0].value.as_str().unwrap().into()), schema) {
+                Some(res) => data.insert(FieldName::from(field.name.as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), &res, document, models))),
+                None => data.insert(FieldName::from(field.name.as_str()), FieldValue::Scalar(NULL))
+            },
+            "__typename" => data.insert(FieldName::from(field.name.as_str()), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(operation.operation_type.default_type_name().to_string()))))),
+
+            resolver_name => {
+                let prefix: &str = match operation.operation_type {
+                    OperationType::Query => {
+                        if resolver_name.starts_with("readOne") {
+                            "readOne"
+                        } else if resolver_name.starts_with("verify") {
+                            "verify"
+                        } else {
+                            ""  // readMany has no prefix because it's the plural variant of the model name
+                        }
+                    },
+                    OperationType::Mutation => {
+                        if resolver_name.starts_with("addOne") {
+                            "addOne"
+                        } else if resolver_name.starts_with("updateOne") {
+                            "updateOne"
+                        } else if resolver_name.starts_with("restoreOne") {
+                            "restoreOne"
+                        } else if resolver_name.starts_with("purgeOne") {
+                            "purgeOne"
+                        } else if resolver_name.starts_with("link") {
+                            "link"
+                        } else if resolver_name.starts_with("unlink") {
+                            "unlink"
+                        } else {
+                            "deleteOne" // operation is expected to be validated by handle_gql_post
+                        }
+                    },
+                    OperationType::Subscription => todo!(),
+                };
+
+                let args: HashMap<&str, TrueType> = HashMap::from_iter(
+                    field.arguments.iter().map(|arg| (arg.name.as_str(), from_value::<TrueType>(to_json_value(&arg.value)).unwrap()))
+                );
+
+                let model_name: &str = resolver_name.strip_prefix(prefix).unwrap();
+                if prefix == "verify" {
+                    match resolve_verify(model_name, &args) {
+                        Ok(verified) => data.insert(FieldName::from(field.response_key().as_str()), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::Boolean(verified))))),
+                        Err(err) => errors.append(&mut vec!(GraphQLError {
+                            message: err.to_string(),
+                            locations: vec!()
+                        }))
+                    }
+                    continue;
+                }
+                let record: Result<(Record, ModelDefinition), std::io::Error> = if let "link" | "unlink" = prefix {
+                    resolve_link(prefix, model_name, &args)
+                } else {
+                    AttrName::try_from(model_name)
+                        .and_then(|name| find_model(models, &ModelName(name)).cloned())
+                        .and_then(|model| {
+                            let key: Vec<String> = key_arg_values(&model, &args);
+                            let ids: &[&str] = &key.iter().map(String::as_str).collect::<Vec<&str>>();
+                            let record: Record = match prefix {
+                                "addOne" => create_one(model_name, serde_json::to_string(&args).unwrap().as_str())?,
+                                "readOne" => {
+                                    // withDeleted is only given for models with soft delete, asOf only for models with history
+                                    if let Some(TrueType::Primitive(Some(TruePrimitiveType::String(as_of)))) = args.get("asOf") {
+                                        read_one_as_of(model_name, ids, as_of)?
+                                    } else if args.get("withDeleted") == Some(&TrueType::Primitive(Some(TruePrimitiveType::Boolean(true)))) {
+                                        read_one_with_deleted(model_name, ids)?
+                                    } else {
+                                        read_one(model_name, ids)?
+                                    }
+                                },
+                                "updateOne" => {
+                                    let attr_args: HashMap<&&str, &TrueType> = args.iter().filter(|(name, _)| **name != "expectedVersion").collect();
+                                    update_one(model_name, ids, serde_json::to_string(&attr_args).unwrap().as_str(), expected_version_arg(&args))?
+                                },
+                                "deleteOne" => delete_one(model_name, ids, expected_version_arg(&args))?,
+                                "restoreOne" => restore_one(model_name, ids)?,
+                                "purgeOne" => purge_one(model_name, ids)?,
+                                "" => todo!(),
+                                _ => unreachable!("there are currently only seven root resolver types besides link and unlink")
+                            };
+                            Ok((record, model))
+                        })
+                };
+
+                match record {
+                    Ok((record, model)) => {
+                        let fields: Data = model_record_to_data(record, &model);
+                        data.insert(FieldName::from(field.response_key().as_str()), FieldValue::Object(resolve_selection_set_order(&field.selection_set, field.ty(), &fields, document, models)));
+                    },
+                    Err(err) => errors.append(&mut vec!(GraphQLError {
+                        message: err.to_string(),
+                        locations: vec!()
+                    }))
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        return GraphQLReturn::from(data);
+    } else if data.is_empty() {
+        return GraphQLReturn::from(errors);
+    }
+
+    GraphQLReturn {
+        errors: Some(errors),
+        data: Some(data)
+    }
+}
+
+fn record_to_data(record: Record) -> Data {
+    let mut data = Data::new();
+    for (attr_name, value) in record {
+        match value {
+            TrueType::Object(Some(nested)) => data.insert(attr_name.0, FieldValue::Object(record_to_data(nested))),
+            scalar => data.insert(attr_name.0, FieldValue::Scalar(scalar))
+        }
+    }
+    data
+}
+
+/*
+    model_record_to_data: 
+        Converts a record of the given model to Data,
+        its associations are added unresolved, so they only get fetched if they are selected.
+
+    returns:
+        the record's Data including its associations
+*/
+fn model_record_to_data(record: Record, model: &ModelDefinition) -> Data {
+    let mut data = Data::new();
+    for (name, association) in model.associations.iter().flatten() {
+        let key_attr: AttrName = match association.kind {
+            AssociationKind::BelongsTo => association.foreign_key.clone(),
+            AssociationKind::HasMany | AssociationKind::ManyToMany => model.primary_key.attrs().remove(0)  // associated models need single primary keys
+        };
+        data.insert(model.exposed_name(name).0, FieldValue::Association(model.model_name.clone(), name.clone(), record.get(&model.exposed_name(&key_attr)).cloned().unwrap_or(NULL)));
+    }
+    if model.history {
+        let key: Vec<TrueType> = model.primary_key.attrs().iter().map(|key_attr| record.get(&model.exposed_name(key_attr)).cloned().unwrap_or(NULL)).collect();
+        data.insert(HISTORY.to_string(), FieldValue::History(model.model_name.clone(), key));
+    }
+    data.append(record_to_data(record));
+    data
+}
+
+/*
+    resolve_association: 
+        Fetches the records referenced by the association with the given name with the given key value.
+
+    returns:
+        the referenced record as Object for belongsTo associations, or null if it doesn't exist,
+        the referencing records as Objects for hasMany associations,
+        the linked records as Objects for manyToMany associations
+*/
+fn resolve_association(models: &[ModelDefinition], owner_name: &ModelName, association_name: &AttrName, value: &TrueType) -> FieldValue {
+    let owner: &ModelDefinition = match find_model(models, owner_name) {
+        Ok(owner) => owner,
+        Err(_) => return FieldValue::Scalar(NULL)
+    };
+    let association: &Association = match owner.associations.as_ref().and_then(|associations| associations.get(association_name)) {
+        Some(association) => association,
+        None => return FieldValue::Scalar(NULL)
+    };
+    let model: &ModelDefinition = match find_model(models, &association.model) {
+        Ok(model) => model,
+        Err(_) => return FieldValue::Scalar(NULL)
+    };
+    let records: Vec<Record> = read_association(models, owner, association_name, value).unwrap_or_default();
+    match association.kind {
+        AssociationKind::BelongsTo => match records.into_iter().next() {
+            Some(record) => FieldValue::Object(model_record_to_data(record, model)),
+            None => FieldValue::Scalar(NULL)
+        },
+        AssociationKind::HasMany | AssociationKind::ManyToMany => FieldValue::Objects(records.into_iter().map(|record| model_record_to_data(record, model)).collect())
+    }
+}
+
+/*
+    resolve_history: 
+        Fetches the history of the record with the given key,
+        the records before and after each change are resolved like records of the model.
+
+    returns:
+        the history entries as Objects, which are empty if the record has no history
+*/
+fn resolve_history(models: &[ModelDefinition], model_name: &ModelName, key: &[TrueType]) -> FieldValue {
+    let model: &ModelDefinition = match find_model(models, model_name) {
+        Ok(model) => model,
+        Err(_) => return FieldValue::Objects(vec!())
+    };
+    let entries: Vec<Record> = read_history_of(model, key).unwrap_or_default();
+    FieldValue::Objects(entries.into_iter().map(|mut entry| {
+        let mut data = Data::new();
+        for field in ["operation", "at"] {
+            data.insert(field.to_string(), FieldValue::Scalar(entry.remove(&AttrName(field.to_string())).unwrap_or(NULL)));
+        }
+        for field in ["previous", "record"] {
+            match entry.remove(&AttrName(field.to_string())) {
+                Some(TrueType::Object(Some(record))) => data.insert(field.to_string(), FieldValue::Object(model_record_to_data(record, model))),
+                _ => data.insert(field.to_string(), FieldValue::Scalar(NULL))
+            }
+        }
+        data
+    }).collect())
+}
+
+fn to_json_value(value: &GraphQLValue) -> JsonValue {
+    match value {
+        GraphQLValue::Enum(name) => JsonValue::String(name.to_string()),
+        GraphQLValue::String(string) => JsonValue::String(string.as_str().to_string()),
+        GraphQLValue::List(list) => JsonValue::Array(list.iter().map(|val| to_json_value(val)).collect()),
+        GraphQLValue::Object(fields) => JsonValue::Object(fields.iter().map(|(name, val)| (name.to_string(), to_json_value(val))).collect()),
+        GraphQLValue::Variable(_) | GraphQLValue::Null => JsonValue::Null,  // variables are currently not supported
+        other => from_str::<JsonValue>(&other.to_string()).unwrap()
+    }
+}
+
+fn resolve_selection_set_order(selection_set: &SelectionSet, resolver_ty: &Type,  field_data: &Data, document: &Valid<ExecutableDocument>, models: &[ModelDefinition]) -> Data {
+    let mut data = Data::new();
+    for sel in &selection_set.selections {
+        match sel {
+            Selection::Field(sel_field) => {
+                match field_data.get(&FieldName::from(sel_field.name.as_str())) {
+                    Some(FieldValue::Objects(sub_data)) => {
+                        let resolved: Vec<Data> = sub_data.iter().map(|d| resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), d, document, models)).collect();
+                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Objects(resolved));
+                    },
+                    Some(FieldValue::Object(sub_data)) => {
+                        let resolved: Data = resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), &sub_data, document, models);
+                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Object(resolved));
+                    },
+                    Some(FieldValue::Association(owner_name, association_name, value)) => {
+                        let resolved: FieldValue = match resolve_association(models, &owner_name, &association_name, &value) {
+                            FieldValue::Object(sub_data) => FieldValue::Object(resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), &sub_data, document, models)),
+                            FieldValue::Objects(sub_data) => FieldValue::Objects(sub_data.iter().map(|d| resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), d, document, models)).collect()),
+                            other => other
+                        };
+                        data.insert(FieldName::from(sel_field.response_key().as_str()), resolved);
+                    },
+                    Some(FieldValue::History(model_name, key)) => {
+                        let resolved: FieldValue = match resolve_history(models, &model_name, &key) {
+                            FieldValue::Objects(sub_data) => FieldValue::Objects(sub_data.iter().map(|d| resolve_selection_set_order(&sel_field.selection_set, sel_field.ty(), d, document, models)).collect()),
+                            other => other
+                        };
+                        data.insert(FieldName::from(sel_field.response_key().as_str()), resolved);
+                    },
+                    Some(scalar) => data.insert(FieldName::from(sel_field.response_key().as_str()), scalar),
+                    None => {
+                        assert_eq!(sel_field.name.as_str(), "__typename", "Unhandled field \"{field}\" in graphql request", field=sel_field.name.as_str());
+                        data.insert(FieldName::from(sel_field.name.as_str()), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(resolver_ty.inner_named_type().to_string())))));
+                    }
+                }
+            },
+            Selection::FragmentSpread(frag) => data.append(resolve_selection_set_order(&document.fragments.get(&frag.fragment_name).unwrap().selection_set, resolver_ty, field_data, document, models)),
+            Selection::InlineFragment(frag) => data.append(resolve_selection_set_order(&frag.selection_set, resolver_ty, field_data, document, models))
+        }
+    }
+
+    data
+}
+
+fn resolve_type_system(schema: &Valid<Schema>) -> FieldValue {
+    let mut types: Vec<Data> = vec!();
+    for ty_def in schema.types.keys() {
+        if let Some(res) = resolve_type_definition(ty_def, schema) {
+            types.push(res);
+        }
+    }
+
+    FieldValue::Objects(types)
+}
+
+fn resolve_type_definition(ty_name: &NamedType, schema: &Valid<Schema>) -> Option<Data> {
+    if ty_name.as_str().starts_with("__") {
+        return None; // don't resolve unnecessarily introspection types
+    }
+    let mut data = Data::new();
+
+    let ty_def: &ExtendedType = schema.types.get(ty_name)?;
+    
+    data.insert(FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(ty_name.to_string())))));
+
+    match ty_def {
+        ExtendedType::Object(def) => {
+            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("OBJECT".to_string())))));
+            match &def.description {
+                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+            }
+            let fields: Vec<Data> = def.fields.values().map(|f| resolve_field_definition(&f.node, schema)).collect();
+            data.insert(FieldName::from("fields"), FieldValue::Objects(fields));
+            data.insert(FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects enums
+            data.insert(FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects input types
+        },
+        ExtendedType::Scalar(def) => {
+            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("SCALAR".to_string())))));
+            match &def.description {
+                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+            }
+            data.insert(FieldName::from("fields"), FieldValue::Scalar(NULL));
+            data.insert(FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects enums
+            data.insert(FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects input types
+        },
+        ExtendedType::Enum(def) => {
+            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("ENUM".to_string())))));
+            match &def.description {
+                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+            }
+            data.insert(FieldName::from("fields"), FieldValue::Scalar(NULL));
+            let values: Vec<Data> = def.values.values().map(|v| {
+                let mut value = Data::from(vec![
+                    (FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(v.value.to_string()))))),
+                    (FieldName::from("isDeprecated"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::Boolean(false))))),
+                    (FieldName::from("deprecationReason"), FieldValue::Scalar(NULL))
+                ]);
+                match &v.description {
+                    Some(desc) => value.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+                    None => value.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+                }
+                value
+            }).collect();
+            data.insert(FieldName::from("enumValues"), FieldValue::Objects(values));
+            data.insert(FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects input types
+        },
+        ExtendedType::InputObject(def) => {
+            data.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("INPUT_OBJECT".to_string())))));
+            match &def.description {
+                Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+                None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+            }
+            data.insert(FieldName::from("fields"), FieldValue::Scalar(NULL));
+            data.insert(FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects enums
+            let input_fields: Vec<Data> = def.fields.values().map(|f| resolve_input_value_definition(&f.node, schema)).collect();
+            data.insert(FieldName::from("inputFields"), FieldValue::Objects(input_fields));
+        },
+        _ => return None
+    }
+
+    data.insert(FieldName::from("ofType"), FieldValue::Scalar(NULL)); // a type has no ofType if it has a TypeDefinition
+
+    // the following fields get default values because they are currently not used
+    data.insert(FieldName::from("interfaces"), FieldValue::Scalar(TrueType::Array(Some(vec!()))));
+    data.insert(FieldName::from("possibleTypes"), FieldValue::Scalar(TrueType::Array(Some(vec!())))); // because it affects interfaces
+
+    Some(data)
+}
+
+fn resolve_type(ty: &Type, schema: &Valid<Schema>) -> FieldValue {
+    let mut resolved = Data::from(vec![
+        (FieldName::from("name"), FieldValue::Scalar(NULL)),
+        (FieldName::from("description"), FieldValue::Scalar(NULL)),
+        (FieldName::from("fields"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
+        (FieldName::from("interfaces"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
+        (FieldName::from("possibleTypes"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
+        (FieldName::from("enumValues"), FieldValue::Scalar(TrueType::Array(Some(vec!())))),
+        (FieldName::from("inputFields"), FieldValue::Scalar(TrueType::Array(Some(vec!()))))
+    ]);
+    match ty {
+        Type::List(ty) => {
+            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("LIST".to_string())))));
+            resolved.insert(FieldName::from("ofType"), resolve_type(ty, schema));
+        },
+        Type::NonNullList(ty) => {
+            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("NON_NULL".to_string())))));
+            resolved.insert(FieldName::from("ofType"), resolve_type(&Type::List(ty.clone()), schema));
+        },
+        Type::NonNullNamed(name) => {
+            resolved.insert(FieldName::from("kind"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String("NON_NULL".to_string())))));
+            resolved.insert(FieldName::from("ofType"), resolve_type(&Type::Named(name.clone()), schema));
+        },
+        Type::Named(name) =>{
+            return match resolve_type_definition(name, schema) {
+                Some(res) => FieldValue::Object(res),
+                None => FieldValue::Scalar(NULL)
+            }
+        }
+    };
+
+    FieldValue::Object(resolved)
+}
+
+fn resolve_field_definition(field: &Node<FieldDefinition>, schema: &Valid<Schema>) -> Data {  // __Field
+    let mut data = Data::new();
+    data.insert(FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(field.name.to_string())))));
+    match &field.description {
+        Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+        None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+    }
+
+    let args: Vec<Data> = field.arguments.iter().map(|a| resolve_input_value_definition(a, schema)).collect();
+
+    data.insert(FieldName::from("args"), FieldValue::Objects(args));
+
+    data.insert(FieldName::from("type"), resolve_type(&field.ty, schema));
+    data.insert(FieldName::from("isDeprecated"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::Boolean(false)))));
+    data.insert(FieldName::from("deprecationReason"), FieldValue::Scalar(NULL));
+
+    data
+}
+
+fn resolve_input_value_definition(input_value: &InputValueDefinition, schema: &Valid<Schema>) -> Data {  // __InputValue
+    let mut data = Data::from(vec![
+        (FieldName::from("name"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(input_value.name.to_string()))))),
+        (FieldName::from("type"), resolve_type(&input_value.ty, schema))
+    ]);
+    match &input_value.description {
+        Some(desc) => data.insert(FieldName::from("description"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(desc.to_string()))))),
+        None => data.insert(FieldName::from("description"), FieldValue::Scalar(NULL))
+    }
+    match &input_value.default_value {
+        Some(val) => data.insert(FieldName::from("defaultValue"), FieldValue::Scalar(TrueType::Primitive(Some(TruePrimitiveType::String(val.to_string()))))),
+        None => data.insert(FieldName::from("defaultValue"), FieldValue::Scalar(NULL))
+    }
+    data
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+    use serde_json::to_value;
+    use super::super::{
+        Attributes,
+        validate_model_definition,
+        base_model
+    };
+
+    #[test]
+    fn test_schema_descriptions() {
+        let models: Vec<ModelDefinition> = parse_models(Path::new("./testing/model_descriptions")).unwrap();
+        let schema: String = schema_of(&models);
+        for description in [
+            r#""A movie which can be recommended" type Movie {"#,
+            r#""The year of the release" year:Int"#,
+            r#""The director of the movie" director:Director"#,
+            r#""The movie isn't released yet" PLANNED"#,
+            r#""The city of the headquarters" city:String"#
+        ] {
+            assert!(schema.contains(description), "Expected the schema to contain the description {description:?}");
+        }
+        assert!(!schema.contains(r#""The year of the release" year:Int!"#), "Descriptions must not change the types of the described fields");
+    }
+
+    #[test]
+    fn test_schema_secret_defaults() {
+        let account = ModelDefinition {
+            model_name: ModelName(AttrName("account".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("role".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("password".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("apiToken".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            write_only: Some(vec!(AttrName("apiToken".to_string()))),
+            hashed: Some(vec!(AttrName("password".to_string()))),
+            defaults: Some(HashMap::from([
+                (AttrName("role".to_string()), DefaultValue::Literal(JsonValue::from("user"))),
+                (AttrName("password".to_string()), DefaultValue::Literal(JsonValue::from("changeMe"))),
+                (AttrName("apiToken".to_string()), DefaultValue::Literal(JsonValue::from("token")))
+            ])),
+            ..base_model()
+        };
+        assert!(validate_model_definition(&account).is_ok(), "The account model should be valid");
+        let schema: String = schema_of(&[account]);
+        assert!(schema.contains(r#"role:String = "user""#), "Expected the default of a regular attribute in the schema");
+        for secret in ["changeMe", "token"] {
+            assert!(!schema.contains(secret), "Expected the default {secret:?} of a secret not to be in the schema");
+        }
+        assert!(schema.contains("password:String") && schema.contains("apiToken:String"), "Expected the secrets to stay input arguments");
+    }
+
+    #[test]
+    fn test_schema_naming() {
+        let movie = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            ..base_model()
+        };
+        let festival = ModelDefinition {
+            model_name: ModelName(AttrName("festival".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("openingMovie".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            associations: Some(HashMap::from([
+                (AttrName("openingFilm".to_string()), Association {
+                    kind: AssociationKind::BelongsTo,
+                    model: ModelName(AttrName("movie".to_string())),
+                    foreign_key: AttrName("openingMovie".to_string()),
+                    other_key: None,
+                    through: None,
+                    on_delete: None
+                }),
+                (AttrName("programMovies".to_string()), Association {
+                    kind: AssociationKind::ManyToMany,
+                    model: ModelName(AttrName("movie".to_string())),
+                    foreign_key: AttrName("festivalId".to_string()),
+                    other_key: Some(AttrName("movieId".to_string())),
+                    through: None,
+                    on_delete: None
+                })
+            ])),
+            naming: Some(Naming::SnakeCase),
+            ..base_model()
+        };
+        assert!(validate_model_definition(&festival).is_ok(), "The festival model should be valid");
+        let schema: String = schema_of(&[movie, festival]);
+        for field in ["opening_movie:String", "opening_film:Movie", "program_movies:[Movie!]!", "linkFestivalProgramMovies(id:Int! movie_id:String!)"] {
+            assert!(schema.contains(field), "Expected the schema to contain {field:?} named by the naming policy");
+        }
+        for field in ["openingFilm", "programMovies", "movieId"] {
+            assert!(!schema.contains(&format!("{field}:")), "Expected the schema not to contain the stored name {field:?}");
+        }
+    }
+
+    // introspection resolves the descriptions of the parsed schema
+    #[test]
+    fn test_introspection_descriptions() {
+        let models: Vec<ModelDefinition> = parse_models(Path::new("./testing/model_descriptions")).unwrap();
+        let schema: Valid<Schema> = Parser::new().parse_schema(schema_of(&models), "schema").unwrap().validate().unwrap();
+
+        let movie: JsonValue = to_value(resolve_type_definition(&named_type!("Movie"), &schema).unwrap()).unwrap();
+        assert_eq!(movie["description"], "A movie which can be recommended", "Unexpected description of the model's type");
+        for (field, description) in [("year", JsonValue::from("The year of the release")), ("director", JsonValue::from("The director of the movie")), ("id", JsonValue::Null)] {
+            let field_def: &JsonValue = movie["fields"].as_array().unwrap().iter().find(|field_def| field_def["name"] == field).unwrap();
+            assert_eq!(field_def["description"], description, "Unexpected description of the field {field:?}");
+        }
+
+        let status: JsonValue = to_value(resolve_type_definition(&named_type!("MovieStatus"), &schema).unwrap()).unwrap();
+        let planned: &JsonValue = status["enumValues"].as_array().unwrap().iter().find(|value| value["name"] == "PLANNED").unwrap();
+        assert_eq!(planned["description"], "The movie isn't released yet", "Unexpected description of the enum value");
+
+        let studio: JsonValue = to_value(resolve_type_definition(&named_type!("MovieStudio"), &schema).unwrap()).unwrap();
+        let city: &JsonValue = studio["fields"].as_array().unwrap().iter().find(|field_def| field_def["name"] == "city").unwrap();
+        assert_eq!(city["description"], "The city of the headquarters", "Unexpected description of the nested attribute");
+    }
+}
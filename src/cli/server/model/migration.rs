@@ -0,0 +1,702 @@
+// used types
+use crate::cli::Migrate;
+use std::collections::HashMap;
+use std::path::{
+    PathBuf,
+    Path
+};
+use std::fmt::{
+    Formatter,
+    Display
+};
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+use serde_json::Value;
+use std::fs::File;
+use argon2::password_hash::PasswordHash;
+use super::{
+    TruePrimitiveType,
+    ModelDefinition,
+    PrimitiveType,
+    DefaultValue,
+    ModelName,
+    AttrType,
+    AttrName,
+    TrueType,
+    Record,
+    NULL,
+    DELETED_AT,
+    EXPIRES_AT,
+    VERSION
+};
+use super::storage_handler::StorageHandler;
+
+// used functions
+use super::storage_handler::get_handler_for;
+use super::{
+    parse_models,
+    parse_attributes,
+    add_null_values,
+    add_expiry,
+    now,
+    check_constraints,
+    check_rules,
+    rules_without,
+    hash_secrets,
+    add_history_entry_to
+};
+use serde_json::{
+    to_string,
+    to_value
+};
+use std::process::exit;
+use std::slice::from_ref;
+
+/*
+    MigrationStep:
+        A change of the stored attributes between the applied and the current model definition.
+        Renames are never guessed, they have to be given explicitly.
+*/
+#[derive(Debug, PartialEq)]
+pub enum MigrationStep {
+    Rename(AttrName, AttrName),
+    Add(AttrName, Option<DefaultValue>),
+    Convert(AttrName, Box<AttrType>, Box<AttrType>),
+    Drop(AttrName),
+    Backfill(AttrName),
+    Hash(AttrName)
+}
+
+impl Display for MigrationStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationStep::Rename(from, to) => write!(f, "rename attribute {from:?} to {to:?}", from=from.0, to=to.0),
+            MigrationStep::Add(attr, Some(DefaultValue::Literal(value))) => write!(f, "add attribute {attr:?}, backfilled with {value}", attr=attr.0),
+            MigrationStep::Add(attr, Some(DefaultValue::Generated { generator })) => write!(f, "add attribute {attr:?}, backfilled with generated {generator:?}", attr=attr.0),
+            MigrationStep::Add(attr, None) => write!(f, "add attribute {attr:?}, backfilled with null", attr=attr.0),
+            MigrationStep::Convert(attr, from, to) => write!(
+                f,
+                "convert attribute {attr:?} from {from} to {to}",
+                attr=attr.0,
+                from=to_string(from).unwrap(),
+                to=to_string(to).unwrap()
+            ),
+            MigrationStep::Drop(attr) => write!(f, "drop attribute {attr:?}", attr=attr.0),
+            MigrationStep::Backfill(attr) => write!(f, "backfill managed attribute {attr:?}", attr=attr.0),
+            MigrationStep::Hash(attr) => write!(f, "hash the stored values of attribute {attr:?}", attr=attr.0)
+        }
+    }
+}
+
+/*
+    Migration:
+        The planned migration of a model's stored records.
+        Lost values are allowed if the migration is forced, invalid records never.
+*/
+#[derive(Debug)]
+pub struct Migration {
+    pub steps: Vec<MigrationStep>,
+    pub lost: Vec<String>,
+    pub invalid: Vec<String>,
+    pub records: Vec<Record>
+}
+
+/*
+    plan_migration:
+        Plans the migration of records stored with the applied definition to the current definition.
+
+        What happens exactly:
+            1. check the renames, the old attribute must be removed and the new one added
+            2. collect the steps, every attribute of the current definition
+               is either renamed, converted, added or unchanged,
+               attributes which aren't stored anymore are dropped,
+               newly managed attributes are backfilled like for new records, except deletedAt which stays null,
+               kept attributes which are newly hashed get their stored values hashed
+            3. migrate every record, memorize dropped values and values which can't be converted as lost
+            4. add null values and check required attributes, constraints and rules of the migrated records,
+               hashed attributes and the rules using them are skipped, only their hashes are stored
+            5. hash the values of hashed attributes which aren't hashes yet, e.g. added defaults
+
+    returns:
+        the planned migration or an Error if a rename doesn't fit to the definitions
+*/
+pub fn plan_migration(applied: &ModelDefinition, current: &ModelDefinition, records: Vec<Record>, renames: &[(AttrName, AttrName)]) -> Result<Migration> {
+    let mut steps: Vec<MigrationStep> = vec!();
+    for (from, to) in renames {
+        if !applied.attributes.contains_key(from) || current.attributes.contains_key(from) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("invalid rename, attribute {from:?} isn't removed from the model", from=from.0)));
+        }
+        if applied.attributes.contains_key(to) || !current.attributes.contains_key(to) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("invalid rename, attribute {to:?} isn't added to the model", to=to.0)));
+        }
+        steps.push(MigrationStep::Rename(from.clone(), to.clone()));
+    }
+
+    // sorted, so the steps are always shown the same way
+    let mut attrs: Vec<&AttrName> = current.attributes.keys().collect();
+    attrs.sort_by(|a, b| a.0.cmp(&b.0));
+    for attr in attrs {
+        let current_type: &AttrType = current.attributes.get(attr).unwrap();
+        let renamed_from: Option<&AttrName> = renames.iter().find(|(_, to)| to == attr).map(|(from, _)| from);
+        match applied.attributes.get(renamed_from.unwrap_or(attr)) {
+            Some(applied_type) if applied_type != current_type => steps.push(MigrationStep::Convert(attr.clone(), Box::new(applied_type.clone()), Box::new(current_type.clone()))),
+            Some(_) => (),
+            None => steps.push(MigrationStep::Add(attr.clone(), current.defaults.as_ref().and_then(|defaults| defaults.get(attr)).cloned()))
+        }
+    }
+    let stored: Vec<AttrName> = current.attributes.keys().cloned().chain(current.managed_attrs()).collect();
+    let mut dropped: Vec<AttrName> = applied.attributes.keys().cloned().chain(applied.managed_attrs())
+        .filter(|attr| !stored.contains(attr) && !renames.iter().any(|(from, _)| from == attr))
+        .collect();
+    dropped.sort_by(|a, b| a.0.cmp(&b.0));
+    steps.extend(dropped.into_iter().map(MigrationStep::Drop));
+    let applied_managed: Vec<AttrName> = applied.managed_attrs();
+    steps.extend(current.managed_attrs().into_iter()
+        .filter(|attr| !applied_managed.contains(attr) && attr.0 != DELETED_AT)
+        .map(MigrationStep::Backfill));
+    let mut newly_hashed: Vec<AttrName> = current.hashed.iter().flatten()
+        .filter(|attr| !applied.hashed.iter().flatten().any(|hashed| &hashed == attr))
+        .filter(|attr| applied.attributes.contains_key(renames.iter().find(|(_, to)| &to == attr).map(|(from, _)| from).unwrap_or(attr)))
+        .cloned()
+        .collect();
+    newly_hashed.sort_by(|a, b| a.0.cmp(&b.0));
+    steps.extend(newly_hashed.into_iter().map(MigrationStep::Hash));
+
+    let mut lost: Vec<String> = vec!();
+    let mut invalid: Vec<String> = vec!();
+    let mut migrated: Vec<Record> = vec!();
+    for mut record in records {
+        let key: String = record_key(&record, applied);
+        for step in &steps {
+            match step {
+                MigrationStep::Rename(from, to) => {
+                    let value: TrueType = record.remove(from).unwrap_or(NULL);
+                    record.insert(to.clone(), value);
+                },
+                MigrationStep::Add(attr, default) => {
+                    let value: TrueType = match default {
+                        Some(DefaultValue::Literal(value)) => convert_value(attr, value.clone(), current).unwrap_or(NULL),
+                        Some(DefaultValue::Generated { generator }) => convert_value(attr, generator.generate(&current.attributes[attr]), current).unwrap_or(NULL),
+                        None => NULL
+                    };
+                    record.insert(attr.clone(), value);
+                },
+                MigrationStep::Convert(attr, _, to) => {
+                    let value: TrueType = record.remove(attr).unwrap_or(NULL);
+                    match convert_value(attr, to_value(&value).unwrap(), current) {
+                        Some(converted) => record.insert(attr.clone(), converted),
+                        None => {
+                            lost.push(format!("record {key}: value {value} of attribute {attr:?} can't be converted to {to}", attr=attr.0, to=to_string(to).unwrap()));
+                            record.insert(attr.clone(), NULL)
+                        }
+                    };
+                },
+                MigrationStep::Drop(attr) => {
+                    if let Some(value) = record.remove(attr).filter(|value| value != &NULL) {
+                        lost.push(format!("record {key}: value {value} of attribute {attr:?} is dropped", attr=attr.0));
+                    }
+                },
+                MigrationStep::Backfill(attr) => {
+                    let value: TrueType = match attr.0.as_str() {
+                        VERSION => TrueType::Primitive(Some(TruePrimitiveType::Integer(1))),
+                        EXPIRES_AT => add_expiry(Record::new(), current).remove(attr).unwrap_or(NULL),
+                        _ => now()
+                    };
+                    record.insert(attr.clone(), value);
+                },
+                // the values are hashed after checking the records, together with added defaults
+                MigrationStep::Hash(_) => ()
+            }
+        }
+        migrated.push(add_null_values(record, current));
+    }
+    invalid.extend(invalid_records(&migrated, current)?);
+    let migrated: Vec<Record> = migrated.into_iter().map(|record| hash_plaintext(record, current)).collect::<Result<Vec<Record>>>()?;
+
+    Ok(Migration {
+        steps,
+        lost,
+        invalid,
+        records: migrated
+    })
+}
+
+/*
+    invalid_records:
+        Checks required attributes, constraints and rules of records stored with the given definition,
+        hashed attributes and the rules using them are skipped, only their hashes are stored.
+
+    returns:
+        a description of every violation or an Error if the rules can't be parsed
+*/
+fn invalid_records(records: &[Record], model: &ModelDefinition) -> Result<Vec<String>> {
+    let hashed: Vec<AttrName> = model.hashed.clone().unwrap_or_default();
+    let mut checked_model: ModelDefinition = model.clone();
+    checked_model.rules = rules_without(model, &hashed)?;
+
+    let mut invalid: Vec<String> = vec!();
+    for record in records {
+        let key: String = record_key(record, model);
+        for attr in &model.required {
+            if record.get(attr).is_none_or(|value| value == &NULL) {
+                invalid.push(format!("record {key}: required attribute {attr:?} is null", attr=attr.0));
+            }
+        }
+        let checked: Record = add_null_values(record.clone(), model).into_iter()
+            .filter(|(attr, _)| !hashed.contains(attr))
+            .collect();
+        if let Err(err) = check_constraints(&checked, model).and_then(|_| check_rules(&checked, &checked_model)) {
+            invalid.push(format!("record {key}: {err}"));
+        }
+    }
+    Ok(invalid)
+}
+
+// hashes the values of hashed attributes unless they are argon2 hashes already
+fn hash_plaintext(record: Record, model: &ModelDefinition) -> Result<Record> {
+    let mut plaintext_model: ModelDefinition = model.clone();
+    plaintext_model.hashed = Some(model.hashed.iter().flatten()
+        .filter(|attr| matches!(record.get(attr), Some(TrueType::Primitive(Some(TruePrimitiveType::String(value)))) if PasswordHash::new(value).is_err()))
+        .cloned()
+        .collect());
+    hash_secrets(record, &plaintext_model)
+}
+
+/*
+    convert_value:
+        Converts a stored JSON value to the current type of the attribute.
+        Numbers, Booleans and Strings are converted into each other if no information gets lost,
+        single values are wrapped into arrays and arrays with a single value are unwrapped.
+        Other values are kept as they are, then they must fit to the current type already.
+
+    returns:
+        the converted value or None if the value doesn't fit to the current type
+*/
+fn convert_value(attr: &AttrName, value: Value, model: &ModelDefinition) -> Option<TrueType> {
+    let converted: Value = match (&model.attributes[attr], value) {
+        (AttrType::Array([prim_type]), Value::Array(values)) => Value::Array(values.into_iter().map(|value| convert_primitive(value, prim_type)).collect::<Option<Vec<Value>>>()?),
+        (AttrType::Array(_), Value::Null) => Value::Null,
+        (AttrType::Array([prim_type]), value) => Value::Array(vec!(convert_primitive(value, prim_type)?)),
+        (AttrType::Primitive(prim_type), Value::Array(mut values)) if values.len() == 1 => convert_primitive(values.remove(0), prim_type)?,
+        (AttrType::Primitive(prim_type), value) => convert_primitive(value, prim_type)?,
+        (_, value) => value
+    };
+    // the same translation as for values sent by clients
+    parse_attributes(HashMap::from([(attr.clone(), converted)]), &model.attributes, &[], None).ok()?.remove(attr)
+}
+
+fn convert_primitive(value: Value, prim_type: &PrimitiveType) -> Option<Value> {
+    match (prim_type, value) {
+        (_, Value::Null) => Some(Value::Null),
+        (PrimitiveType::String, Value::Number(number)) => Some(Value::from(number.to_string())),
+        (PrimitiveType::String, Value::Bool(boolean)) => Some(Value::from(boolean.to_string())),
+        // floats out of the range of i64 would saturate, so they can't be converted
+        (PrimitiveType::Integer, Value::Number(number)) => number.as_i64().or(number.as_f64()
+            .filter(|float| float.fract() == 0.0 && *float >= i64::MIN as f64 && *float < i64::MAX as f64)
+            .map(|float| float as i64)).map(Value::from),
+        (PrimitiveType::Integer, Value::String(string)) => string.trim().parse::<i64>().ok().map(Value::from),
+        (PrimitiveType::Float, Value::Number(number)) => number.as_f64().map(Value::from),
+        (PrimitiveType::Float, Value::String(string)) => string.trim().parse::<f64>().ok().map(Value::from),
+        (PrimitiveType::Boolean, Value::String(string)) => string.trim().parse::<bool>().ok().map(Value::from),
+        (_, value) => Some(value)
+    }
+}
+
+// the key of a record as shown to users
+fn record_key(record: &Record, model: &ModelDefinition) -> String {
+    model.primary_key.attrs().iter().map(|attr| record.get(attr).unwrap_or(&NULL).to_string()).collect::<Vec<String>>().join(",")
+}
+
+/*
+    migrate:
+        Shows the migrations of all models in the models' path and applies them if it isn't a dry run.
+        Models without applied definition get their current definition memorized without changes, if their stored records fit to it.
+        Migrations with lost values are only applied if forced, migrations with invalid records never.
+        Exits with a failure code if any migration isn't applied.
+*/
+pub fn migrate(args: Migrate) {
+    let models: Vec<ModelDefinition> = match parse_models(args.modelspath.as_path()) {
+        Ok(models) => models,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    };
+    let mut failed: bool = false;
+    for model in models {
+        let renames: Vec<(AttrName, AttrName)> = args.rename.iter()
+            .filter(|rename| ModelName(AttrName(rename.model.clone())).plural().camel() == model.model_name.plural().camel())
+            .map(|rename| (AttrName(rename.from.clone()), AttrName(rename.to.clone())))
+            .collect();
+        if let Err(err) = migrate_model(&model, args.storage_definitions.clone(), &renames, args.dry_run, args.force) {
+            eprintln!("{model}: {err}", model=model.model_name.0.0);
+            failed = true;
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn migrate_model(model: &ModelDefinition, storage_definitions: Option<PathBuf>, renames: &[(AttrName, AttrName)], dry_run: bool, force: bool) -> Result<()> {
+    let name: &str = &model.model_name.0.0;
+    let storage_handler = get_handler_for(model, storage_definitions.clone())?;
+    // the records must not change between reading and replacing them, so running servers prevent migrations
+    let _process_lock: Option<File> = match dry_run {
+        true => None,
+        false => Some(storage_handler.lock_process(true).map_err(|err| Error::new(err.kind(), format!("{err}, stop the server before migrating")))?)
+    };
+    let applied: ModelDefinition = match storage_handler.read_definition()? {
+        Some(applied) => applied,
+        None => {
+            // the stored records may stem from an older definition, which isn't known, so they must fit to the current one
+            let invalid: Vec<String> = invalid_records(&storage_handler.read_many(&Record::new())?, model)?;
+            if !invalid.is_empty() {
+                println!("{name}: no applied definition, the stored records don't fit to the current one");
+                for invalid in &invalid {
+                    println!("    invalid: {invalid}");
+                }
+                return Err(Error::new(ErrorKind::InvalidData, "not memorized, fix the invalid records first"));
+            }
+            println!("{name}: no applied definition, the current one is memorized");
+            return if dry_run { Ok(()) } else { storage_handler.migrate(model, None) };
+        }
+    };
+    if &applied == model && renames.is_empty() {
+        println!("{name}: up to date");
+        return Ok(());
+    }
+
+    let stored: Vec<Record> = storage_handler.read_many(&Record::new())?;
+    let migration: Migration = plan_migration(&applied, model, stored.clone(), renames)?;
+    println!("{name}: {count} change(s) of {records} record(s)", count=migration.steps.len(), records=migration.records.len());
+    for step in &migration.steps {
+        println!("    {step}");
+    }
+    for lost in &migration.lost {
+        println!("    lost: {lost}");
+    }
+    for invalid in &migration.invalid {
+        println!("    invalid: {invalid}");
+    }
+
+    if !migration.invalid.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidData, "not migrated, the migrated records would be invalid"));
+    }
+    if !migration.lost.is_empty() && !force {
+        return Err(Error::new(ErrorKind::InvalidData, "not migrated, values would get lost, use --force to migrate anyway"));
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    // the changed records are added to the history first, so the entries can be removed if the migration fails
+    let history_handler = get_handler_for(&model.history_model(), storage_definitions)?;
+    let mut entries: Vec<Record> = vec!();
+    if model.history {
+        for (previous, migrated) in stored.iter().zip(&migration.records).filter(|(previous, migrated)| previous != migrated) {
+            match add_history_entry_to(&history_handler, model, "update", Some(previous), Some(migrated)) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => return Err(remove_history_entries(&history_handler, &entries, err))
+            }
+        }
+    }
+    if let Err(err) = storage_handler.migrate(model, Some(&migration.records)) {
+        return Err(remove_history_entries(&history_handler, &entries, err));
+    }
+    println!("{name}: migrated");
+    Ok(())
+}
+
+// removes the history entries of a failed migration, returning its Error
+fn remove_history_entries(history_handler: &impl StorageHandler, entries: &[Record], err: Error) -> Error {
+    for entry in entries {
+        if let Err(remove_err) = history_handler.delete_one(from_ref(entry.get(&AttrName("sequence".to_string())).unwrap_or(&NULL)), None) {
+            return Error::other(format!("{err}, removing the history entries failed: {remove_err}"));
+        }
+    }
+    err
+}
+
+// prints a warning for every model changed since its last migration or without applied definition, nothing gets written
+pub fn warn_pending_migrations(model_path: &Path, storage_definitions: Option<PathBuf>) {
+    for model in parse_models(model_path).unwrap_or_default() {
+        let storage_handler = match get_handler_for(&model, storage_definitions.clone()) {
+            Ok(storage_handler) => storage_handler,
+            Err(_) => continue
+        };
+        match storage_handler.read_definition() {
+            Ok(Some(applied)) if applied != model => eprintln!("warning: model {name:?} changed since its last migration, run 'gus migrate'", name=model.model_name.0.0),
+            Ok(None) => eprintln!("warning: model {name:?} has no applied definition, run 'gus migrate' to memorize it", name=model.model_name.0.0),
+            _ => ()
+        }
+    }
+}
+
+/*
+    lock_storages:
+        Locks the storages of all models in the models' path shared,
+        so they can't be migrated while the server runs.
+
+    returns:
+        the locks, which are released when they are dropped, or an Error if a storage is being migrated
+*/
+pub fn lock_storages(model_path: &Path, storage_definitions: Option<PathBuf>) -> Result<Vec<File>> {
+    let mut locks: Vec<File> = vec!();
+    for model in parse_models(model_path).unwrap_or_default() {
+        let storage_handler = get_handler_for(&model, storage_definitions.clone())?;
+        locks.push(storage_handler.lock_process(false).map_err(|err| Error::new(err.kind(), format!("{err}, wait for the migration to finish")))?);
+    }
+    Ok(locks)
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{
+        StrConstraint,
+        Constraints,
+        Attributes,
+        Generator,
+        KeyAttrs,
+        Ttl,
+        CREATED_AT,
+        UPDATED_AT,
+        base_model
+    };
+    use argon2::Argon2;
+    use argon2::password_hash::PasswordVerifier;
+    use std::fs::{
+        remove_file,
+        write
+    };
+
+    #[test]
+    fn test_plan_migration() {
+        let applied = &ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("title".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("rating".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+            ]),
+            ..base_model()
+        };
+        let current = &ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("genre".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("added".to_string()), AttrType::Primitive(PrimitiveType::DateTime))
+            ]),
+            required: vec!(AttrName("id".to_string()), AttrName("name".to_string())),
+            defaults: Some(HashMap::from([
+                (AttrName("genre".to_string()), DefaultValue::Literal(Value::from("drama"))),
+                (AttrName("added".to_string()), DefaultValue::Generated { generator: Generator::now })
+            ])),
+            ..base_model()
+        };
+        let renames = [(AttrName("title".to_string()), AttrName("name".to_string()))];
+
+        let migration: Migration = plan_migration(applied, current, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("title".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string())))),
+            (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("1979".to_string())))),
+            (AttrName("rating".to_string()), NULL)
+        ])), &renames).unwrap();
+        assert_eq!(
+            migration.steps,
+            vec!(
+                MigrationStep::Rename(AttrName("title".to_string()), AttrName("name".to_string())),
+                MigrationStep::Add(AttrName("added".to_string()), Some(DefaultValue::Generated { generator: Generator::now })),
+                MigrationStep::Add(AttrName("genre".to_string()), Some(DefaultValue::Literal(Value::from("drama")))),
+                MigrationStep::Convert(AttrName("year".to_string()), Box::new(AttrType::Primitive(PrimitiveType::String)), Box::new(AttrType::Primitive(PrimitiveType::Integer))),
+                MigrationStep::Drop(AttrName("rating".to_string()))
+            ),
+            "Unexpected migration steps"
+        );
+        assert!(migration.lost.is_empty() && migration.invalid.is_empty(), "Expected a lossless migration, got {migration:?}");
+        let migrated: &Record = &migration.records[0];
+        assert_eq!(migrated.get(&AttrName("name".to_string())), Some(&TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string())))), "Expected the renamed value");
+        assert_eq!(migrated.get(&AttrName("year".to_string())), Some(&TrueType::Primitive(Some(TruePrimitiveType::Integer(1979)))), "Expected the converted value");
+        assert_eq!(migrated.get(&AttrName("genre".to_string())), Some(&TrueType::Primitive(Some(TruePrimitiveType::String("drama".to_string())))), "Expected the default value");
+        assert!(migrated.get(&AttrName("added".to_string())).is_some_and(|added| added != &NULL), "Expected the generated value");
+        assert!(!migrated.contains_key(&AttrName("rating".to_string())), "Expected the dropped attribute to be removed");
+
+        // lost values
+        let migration: Migration = plan_migration(applied, current, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("title".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string())))),
+            (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("seventies".to_string())))),
+            (AttrName("rating".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(5))))
+        ])), &renames).unwrap();
+        assert_eq!(migration.lost.len(), 2, "Expected the unconvertable and the dropped value to be lost, got {lost:?}", lost=migration.lost);
+        assert_eq!(migration.records[0].get(&AttrName("year".to_string())), Some(&NULL), "Expected unconvertable values to become null");
+        assert!(migration.invalid.is_empty(), "Expected no invalid records for lost optional values");
+
+        // invalid records
+        let migration: Migration = plan_migration(applied, current, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("title".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string())))),
+            (AttrName("year".to_string()), NULL),
+            (AttrName("rating".to_string()), NULL)
+        ])), &[]).unwrap();
+        assert_eq!(migration.invalid.len(), 1, "Expected the missing required attribute to make the record invalid, got {invalid:?}", invalid=migration.invalid);
+
+        // renames must fit to the definitions
+        let wrong_renames = [(AttrName("year".to_string()), AttrName("name".to_string()))];
+        assert!(plan_migration(applied, current, vec!(), &wrong_renames).is_err(), "Expected Error for renaming a kept attribute");
+
+        // newly managed attributes get backfilled, hashed attributes aren't checked
+        let mut managed: ModelDefinition = applied.clone();
+        managed.attributes.insert(AttrName("password".to_string()), AttrType::Primitive(PrimitiveType::String));
+        managed.constraints = Some(HashMap::from([(AttrName("password".to_string()), Constraints::String(StrConstraint {
+            max_length: Some(8),
+            ..Default::default()
+        }))]));
+        let mut hashed: ModelDefinition = managed.clone();
+        hashed.hashed = Some(vec!(AttrName("password".to_string())));
+        let mut backfilled: ModelDefinition = hashed.clone();
+        backfilled.timestamps = true;
+        backfilled.soft_delete = true;
+        backfilled.versioned = true;
+        backfilled.ttl = Some(Ttl::Seconds(60));
+        let migration: Migration = plan_migration(&hashed, &backfilled, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("password".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo".to_string()))))
+        ])), &[]).unwrap();
+        assert_eq!(
+            migration.steps,
+            vec!(
+                MigrationStep::Backfill(AttrName(CREATED_AT.to_string())),
+                MigrationStep::Backfill(AttrName(UPDATED_AT.to_string())),
+                MigrationStep::Backfill(AttrName(VERSION.to_string())),
+                MigrationStep::Backfill(AttrName(EXPIRES_AT.to_string()))
+            ),
+            "Unexpected migration steps for newly managed attributes"
+        );
+        assert!(migration.invalid.is_empty(), "Expected hashed attributes to be skipped by constraints, got {invalid:?}", invalid=migration.invalid);
+        let migrated: &Record = &migration.records[0];
+        for attr in [CREATED_AT, UPDATED_AT, EXPIRES_AT] {
+            assert!(migrated.get(&AttrName(attr.to_string())).is_some_and(|value| value != &NULL), "Expected the managed attribute {attr:?} to be backfilled");
+        }
+        assert_eq!(migrated.get(&AttrName(VERSION.to_string())), Some(&TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))), "Expected the first version");
+        assert_eq!(migrated.get(&AttrName(DELETED_AT.to_string())), Some(&NULL), "Expected backfilled records not to be deleted");
+        assert_eq!(
+            migrated.get(&AttrName("password".to_string())),
+            Some(&TrueType::Primitive(Some(TruePrimitiveType::String("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo".to_string())))),
+            "Expected stored hashes to be kept"
+        );
+
+        // plaintext values of hashed attributes are hashed, whether they are stored already or added
+        let is_hash_of = |record: &Record, plaintext: &str| match record.get(&AttrName("password".to_string())) {
+            Some(TrueType::Primitive(Some(TruePrimitiveType::String(hash)))) => PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(plaintext.as_bytes(), &hash).is_ok()),
+            _ => false
+        };
+        let migration: Migration = plan_migration(&managed, &hashed, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("password".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("n4tur4l b0rn".to_string()))))
+        ])), &[]).unwrap();
+        assert_eq!(migration.steps, vec!(MigrationStep::Hash(AttrName("password".to_string()))), "Expected newly hashed attributes to be hashed");
+        assert!(migration.invalid.is_empty(), "Expected the plaintext to be checked like before, got {invalid:?}", invalid=migration.invalid);
+        assert!(is_hash_of(&migration.records[0], "n4tur4l b0rn"), "Expected the stored plaintext to be replaced by its hash, got {record:?}", record=migration.records[0]);
+        let mut defaulted: ModelDefinition = hashed.clone();
+        defaulted.defaults = Some(HashMap::from([(AttrName("password".to_string()), DefaultValue::Literal(Value::from("k1ll3rs")))]));
+        let migration: Migration = plan_migration(applied, &defaulted, vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1))))
+        ])), &[]).unwrap();
+        assert!(migration.steps.contains(&MigrationStep::Add(AttrName("password".to_string()), Some(DefaultValue::Literal(Value::from("k1ll3rs"))))), "Expected the hashed attribute to be added");
+        assert!(!migration.steps.contains(&MigrationStep::Hash(AttrName("password".to_string()))), "Expected added attributes not to be hashed as a separate step");
+        assert!(is_hash_of(&migration.records[0], "k1ll3rs"), "Expected the added default to be hashed, got {record:?}", record=migration.records[0]);
+
+        // the same value is checked if the attribute isn't hashed
+        let migration: Migration = plan_migration(&managed, &managed.clone(), vec!(Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("password".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo".to_string()))))
+        ])), &[]).unwrap();
+        assert_eq!(migration.invalid.len(), 1, "Expected the constraint of a not hashed attribute to be checked, got {invalid:?}", invalid=migration.invalid);
+    }
+
+    #[test]
+    fn test_migrate_model() {
+        const TEST_STORAGE_FILE: &str = "test_migrate_model.json";
+        const TEST_META_FILE: &str = "test_migrate_model.json.meta";
+        const TEST_LOCK_FILE: &str = "test_migrate_model.json.lock";
+        const TEST_STORAGES_FILE: &str = "test_migrate_model.storages.json";
+
+        for file in [TEST_STORAGE_FILE, TEST_META_FILE] {
+            let _ = remove_file(file);
+        }
+        assert!(write(TEST_STORAGES_FILE, format!("{{\"json\": {{\"storage_file\": \"{TEST_STORAGE_FILE}\"}}}}")).is_ok(), "Unable to write storage definitions for tests");
+        let model = &ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            required: vec!(AttrName("id".to_string()), AttrName("name".to_string())),
+            ..base_model()
+        };
+        let storages: Option<PathBuf> = Some(PathBuf::from(TEST_STORAGES_FILE));
+
+        // records stored before the first migration must fit to the current definition
+        assert!(write(TEST_STORAGE_FILE, "{\"movie\": {\"1\": {\"id\": 1}}}").is_ok(), "Unable to write storage file for tests");
+        assert!(migrate_model(model, storages.clone(), &[], false, false).is_err(), "Expected Error for stored records violating the current definition");
+        let handler = get_handler_for(model, storages.clone()).unwrap();
+        assert_eq!(handler.read_definition().unwrap(), None, "Expected the definition not to be memorized for invalid records");
+
+        assert!(write(TEST_STORAGE_FILE, "{\"movie\": {\"1\": {\"id\": 1, \"name\": \"Alien\"}}}").is_ok(), "Unable to write storage file for tests");
+        assert!(migrate_model(model, storages.clone(), &[], false, false).is_ok(), "Unexpected error when memorizing the definition of valid records");
+        assert_eq!(handler.read_definition().unwrap().as_ref(), Some(model), "Expected the definition to be memorized");
+
+        for file in [TEST_STORAGE_FILE, TEST_META_FILE, TEST_LOCK_FILE, TEST_STORAGES_FILE] {
+            assert!(remove_file(file).is_ok(), "Unable to remove {file} after test");
+        }
+    }
+
+    #[test]
+    fn test_convert_value() {
+        let model = &ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("integer".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("float".to_string()), AttrType::Primitive(PrimitiveType::Float)),
+                (AttrName("string".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("boolean".to_string()), AttrType::Primitive(PrimitiveType::Boolean)),
+                (AttrName("date".to_string()), AttrType::Primitive(PrimitiveType::Date)),
+                (AttrName("strings".to_string()), AttrType::Array([PrimitiveType::String]))
+            ]),
+            primary_key: KeyAttrs::Single(AttrName("integer".to_string())),
+            required: vec!(),
+            ..base_model()
+        };
+
+        for (attr, value, expected) in [
+            ("integer", Value::from(2.0), Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(2))))),
+            ("integer", Value::from(2.5), None),
+            ("integer", Value::from(1e19), None),
+            ("integer", Value::from(-1e19), None),
+            ("integer", Value::from(" 42 "), Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(42))))),
+            ("integer", Value::from("42a"), None),
+            ("integer", Value::from(vec!(7)), Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(7))))),
+            ("integer", Value::from(vec!(7, 8)), None),
+            ("float", Value::from(3), Some(TrueType::Primitive(Some(TruePrimitiveType::Float(3.0))))),
+            ("float", Value::from("1.5"), Some(TrueType::Primitive(Some(TruePrimitiveType::Float(1.5))))),
+            ("string", Value::from(3), Some(TrueType::Primitive(Some(TruePrimitiveType::String("3".to_string()))))),
+            ("string", Value::from(false), Some(TrueType::Primitive(Some(TruePrimitiveType::String("false".to_string()))))),
+            ("boolean", Value::from("true"), Some(TrueType::Primitive(Some(TruePrimitiveType::Boolean(true))))),
+            ("date", Value::from("2000-01-01"), Some(TrueType::Primitive(Some(TruePrimitiveType::String("2000-01-01".to_string()))))),
+            ("date", Value::from("yesterday"), None),
+            ("strings", Value::from(1), Some(TrueType::Array(Some(vec!(TruePrimitiveType::String("1".to_string())))))),
+            ("strings", Value::from(vec!(1, 2)), Some(TrueType::Array(Some(vec!(TruePrimitiveType::String("1".to_string()), TruePrimitiveType::String("2".to_string())))))),
+            ("strings", Value::Null, Some(NULL))
+        ] {
+            assert_eq!(convert_value(&AttrName(attr.to_string()), value.clone(), model), expected, "Unexpected conversion of {value} to attribute {attr:?}");
+        }
+    }
+}
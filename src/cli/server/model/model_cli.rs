@@ -1,227 +1,647 @@
-// used types
-use dialoguer::console::Style;
-use crate::cli::CreateModel;
-use super::{
-    ModelDefinition,
-    StorageType,
-    Attributes,
-    ModelName,
-    AttrType,
-    AttrName
-};
-use dialoguer::{
-    theme::ColorfulTheme,
-    MultiSelect,
-    Validator,
-    Confirm,
-    Select,
-    Input
-};
-use std::path::{
-    PathBuf,
-    Path
-};
-
-// used functions
-use std::fs::write;
-use cruet::string::{
-    singularize::to_singular as singularize,
-    pluralize::to_plural as pluralize
-};
-use serde_json::{
-    to_string_pretty,
-    from_str
-};
-
-pub fn create_model(args: CreateModel) {
-    let mut attributes = Attributes::new();
-    let mut primary_key_opts: Vec<String> = vec!();
-    let mut required_opts: Vec<String> = vec!();
-    let mut required: Vec<AttrName> = vec!();
-
-    // get model name
-    let model_name: String = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Model Name:")
-        .validate_with(ModelNameValidator)
-        .interact_text()
-        .unwrap();
-
-    // get storage type
-    let storage_types: Vec<&str> = vec!(
-        "json"
-    );
-    let storage_type_selection: usize = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Storage Type:")
-        .default(0)
-        .items(&storage_types)
-        .interact()
-        .unwrap();
-    let storage_type: StorageType = from_str(format!("\"{ty}\"", ty=storage_types[storage_type_selection]).as_str()).unwrap();
-
-    // define attributes
-    loop {
-        // get attribute name
-        let attr_name: String = Input::with_theme(&ColorfulTheme::default())
-            .with_prompt("Attribute Name:")
-            .validate_with(AttrNameValidator)
-            .interact_text()
-            .unwrap();
-
-        // get attribute type
-        let primitives = vec!(
-            "String",
-            "Integer",
-            // "Float",
-            "Boolean"
-        );
-        let mut types: Vec<&str> = primitives.clone();
-        types.extend(vec!("Array"));
-        let type_selection: usize = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Data Type:")
-            .default(0)
-            .items(&types)
-            .interact()
-            .unwrap();
-
-        // get array type it is such and don't add it to the primary key options, since it is not allowed as key
-        if types[type_selection] == "Array" {
-            let arr_type_selection: usize = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("Array Type:")
-                .default(0)
-                .items(&primitives)
-                .interact()
-                .unwrap();
-            let selected_type: String = format!("{ty:?}", ty=primitives[arr_type_selection]);
-            let selected_arr_type: AttrType = AttrType::Array([from_str(&selected_type).unwrap()]);
-            attributes.insert(AttrName::try_from(attr_name.as_str()).unwrap(), selected_arr_type);
-        } else {
-            let selected_type: String = format!("{ty:?}", ty=types[type_selection]);
-            let selected_attr_type: AttrType = from_str(&selected_type).unwrap();
-            attributes.insert(AttrName::try_from(attr_name.as_str()).unwrap(), selected_attr_type);
-
-            // don't add attribute names multiple times if they are defined multiple times
-            if !primary_key_opts.contains(&attr_name) {
-                primary_key_opts.push(attr_name.clone());
-            }
-        }
-
-        /*
-            define constraints here
-        */
-
-        // don't add attribute names multiple times if they are defined multiple times
-        if !required_opts.contains(&attr_name) {
-            required_opts.push(attr_name.clone());
-        }
-
-        // don't break up with defining attributes until there are some that are key candidates (so if not Array)
-        if !primary_key_opts.is_empty() {
-            println!();
-            if !Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Do you want to add another attribute?")
-                .interact()
-                .unwrap()
-            {
-                break;
-            }
-        }
-        println!();
-    }
-
-    // get primary key
-    let id_selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Primary Key:")
-        .default(0)
-        .items(&primary_key_opts)
-        .interact()
-        .unwrap();
-    let primary_key = primary_key_opts[id_selection].to_string();
-    
-    // automatically set primary key as required
-    required.push(AttrName::try_from(primary_key.as_str()).unwrap());
-
-    // don't allow the user to unselect the key as not required
-    required_opts.retain(|s| s != &primary_key);
-
-    println!();
-
-    // set up the theme for the multi select because it may happen that the used terminal emulator
-    // doesn't show the icon for selected items with the default setting -> ASCII is safe
-    let multi_select_theme = ColorfulTheme{
-        checked_item_prefix: Style::new().green().apply_to(" [X]".to_string()),
-        unchecked_item_prefix: Style::new().red().apply_to(" [ ]".to_string()),
-        ..Default::default()
-    };
-
-    // get required attributes
-    if !required_opts.is_empty() {
-        let required_selection = MultiSelect::with_theme(&multi_select_theme)
-            .with_prompt("Set required attributes:")
-            .items(&required_opts)
-            .interact()
-            .unwrap();
-
-        for attr_index in required_selection {
-            required.push(AttrName::try_from(required_opts[attr_index].as_str()).unwrap());
-        }
-    }
-
-    // create model definition
-    let created_model = ModelDefinition {
-        model_name: ModelName(AttrName::try_from(model_name.as_str()).unwrap()),
-        storage_type,
-        attributes: attributes.clone(),
-        primary_key: AttrName::try_from(primary_key.as_str()).unwrap(),
-        required,
-        constraints: None
-    };
-
-    #[cfg(debug_assertions)]
-    {
-        // this should never fail because the user is not allowed to cause an invalid model
-        // so if it really fails, it is a mistake in this dialogue implementation
-        assert!(super::validate_model_definition(&created_model).is_ok(), "Invalid model definition");
-    }
-
-    // build file path
-    let mut modelspath = PathBuf::new();
-    modelspath.push(args.modelspath);
-    modelspath.push(model_name);
-    modelspath.set_extension("json");
-
-    let model_file_path: &Path = modelspath.as_path();
-
-    // try to write the definition to a file, else write it to stdout
-    if write(model_file_path, to_string_pretty(&created_model).unwrap()).is_err() {
-        println!("{model}", model=to_string_pretty(&created_model).unwrap());
-        eprintln!("unable to write file");
-    }
-}
-
-struct AttrNameValidator;
-
-impl Validator<String> for AttrNameValidator {
-    type Err = String;
-
-    fn validate(&mut self, input: &String) -> Result<(), Self::Err> {
-        match AttrName::try_from(input.as_str()) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(err.to_string())
-        }
-    }
-}
-
-struct ModelNameValidator;
-
-impl Validator<String> for ModelNameValidator {
-    type Err = String;
-
-    fn validate(&mut self, input: &String) -> Result<(), Self::Err> {
-        let mut attr_validator = AttrNameValidator;
-        attr_validator.validate(input)?;
-        if pluralize(input) == singularize(input) {
-            return Err("Name has no plural variant".to_string());
-        }
-        Ok(())
-    }
+// used types
+use dialoguer::console::Style;
+use crate::cli::{
+    CheckModels,
+    CreateModel
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use super::{
+    validate_enum_values,
+    FloatConstraint,
+    ArrayConstraint,
+    EnumDefinition,
+    ModelDefinition,
+    ModelFormat,
+    IntConstraint,
+    StrConstraint,
+    Pattern,
+    KeyGenerator,
+    KeyAttrs,
+    PrimitiveType,
+    Constraints,
+    StorageType,
+    Attributes,
+    ModelName,
+    AttrType,
+    AttrName,
+    Naming,
+    Ttl,
+    CREATED_AT,
+    UPDATED_AT,
+    DELETED_AT,
+    EXPIRES_AT,
+    HISTORY,
+    VERSION
+};
+use dialoguer::{
+    theme::ColorfulTheme,
+    MultiSelect,
+    Validator,
+    Confirm,
+    Select,
+    Input
+};
+use std::path::{
+    PathBuf,
+    Path
+};
+
+// used functions
+use super::diagnose_models;
+use std::fs::write;
+use std::process::exit;
+use cruet::string::{
+    singularize::to_singular as singularize,
+    pluralize::to_plural as pluralize
+};
+use serde_json::from_str;
+
+pub fn create_model(args: CreateModel) {
+    let mut attributes = Attributes::new();
+    let mut constraints: HashMap<AttrName, Constraints> = HashMap::new();
+    let mut descriptions: HashMap<AttrName, String> = HashMap::new();
+    let mut primary_key_opts: Vec<String> = vec!();
+    let mut required_opts: Vec<String> = vec!();
+    let mut required: Vec<AttrName> = vec!();
+
+    // get model name
+    let model_name: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Model Name:")
+        .validate_with(ModelNameValidator)
+        .interact_text()
+        .unwrap();
+
+    // get model description, empty if there is none
+    let model_description: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Model Description (optional):")
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+
+    // get storage type
+    let storage_types: Vec<&str> = vec!(
+        "json"
+    );
+    let storage_type_selection: usize = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Storage Type:")
+        .default(0)
+        .items(&storage_types)
+        .interact()
+        .unwrap();
+    let storage_type: StorageType = from_str(format!("\"{ty}\"", ty=storage_types[storage_type_selection]).as_str()).unwrap();
+
+    // define attributes
+    loop {
+        // get attribute name
+        let attr_name: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Attribute Name:")
+            .validate_with(AttrNameValidator)
+            .interact_text()
+            .unwrap();
+
+        // get attribute type
+        let primitives = vec!(
+            "String",
+            "Integer",
+            "Float",
+            "Boolean",
+            "Date",
+            "DateTime",
+            "Time"
+        );
+        let mut types: Vec<&str> = primitives.clone();
+        types.extend(vec!("Array", "Enum"));
+        let type_selection: usize = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Data Type:")
+            .default(0)
+            .items(&types)
+            .interact()
+            .unwrap();
+
+        // get array type it is such and don't add it to the primary key options, since it is not allowed as key
+        if types[type_selection] == "Array" {
+            let arr_type_selection: usize = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Array Type:")
+                .default(0)
+                .items(&primitives)
+                .interact()
+                .unwrap();
+            let selected_type: String = format!("{ty:?}", ty=primitives[arr_type_selection]);
+            let selected_arr_type: AttrType = AttrType::Array([from_str(&selected_type).unwrap()]);
+            attributes.insert(AttrName::try_from(attr_name.as_str()).unwrap(), selected_arr_type);
+        } else if types[type_selection] == "Enum" {
+            // enums aren't allowed as key either
+            let enum_values: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Enum Values (comma-separated):")
+                .validate_with(EnumValuesValidator)
+                .interact_text()
+                .unwrap();
+            let selected_enum_type = AttrType::Enum(EnumDefinition {
+                values: split_enum_values(&enum_values),
+                descriptions: None
+            });
+            attributes.insert(AttrName::try_from(attr_name.as_str()).unwrap(), selected_enum_type);
+        } else {
+            let selected_type: String = format!("{ty:?}", ty=types[type_selection]);
+            let selected_attr_type: AttrType = from_str(&selected_type).unwrap();
+            attributes.insert(AttrName::try_from(attr_name.as_str()).unwrap(), selected_attr_type);
+
+            // floats aren't allowed as key, don't add attribute names multiple times if they are defined multiple times
+            if types[type_selection] == "Float" {
+                primary_key_opts.retain(|opt| opt != &attr_name);
+            } else if !primary_key_opts.contains(&attr_name) {
+                primary_key_opts.push(attr_name.clone());
+            }
+        }
+
+        // get attribute description, replace an existing one since the attribute may be redefined
+        let attr_description: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Attribute Description (optional):")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap();
+        descriptions.remove(&AttrName::try_from(attr_name.as_str()).unwrap());
+        if !attr_description.trim().is_empty() {
+            descriptions.insert(AttrName::try_from(attr_name.as_str()).unwrap(), attr_description.trim().to_string());
+        }
+
+        // define constraints, replace existing ones since the attribute may be redefined with another type
+        let attr: AttrName = AttrName::try_from(attr_name.as_str()).unwrap();
+        constraints.remove(&attr);
+        if let Some(constraint) = define_constraint(attributes.get(&attr).unwrap()) {
+            constraints.insert(attr, constraint);
+        }
+
+        // don't add attribute names multiple times if they are defined multiple times
+        if !required_opts.contains(&attr_name) {
+            required_opts.push(attr_name.clone());
+        }
+
+        // don't break up with defining attributes until there are some that are key candidates (so if not Array)
+        if !primary_key_opts.is_empty() {
+            println!();
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want to add another attribute?")
+                .interact()
+                .unwrap()
+            {
+                break;
+            }
+        }
+        println!();
+    }
+
+    // get primary key
+    let id_selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Primary Key:")
+        .default(0)
+        .items(&primary_key_opts)
+        .interact()
+        .unwrap();
+    let mut primary_key: Vec<String> = vec!(primary_key_opts.remove(id_selection));
+
+    // get further primary key attributes for composite keys
+    while !primary_key_opts.is_empty() {
+        println!();
+        if !Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to add another attribute to the primary key (composite key)?")
+            .default(false)
+            .interact()
+            .unwrap()
+        {
+            break;
+        }
+        let id_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Primary Key Attribute:")
+            .default(0)
+            .items(&primary_key_opts)
+            .interact()
+            .unwrap();
+        primary_key.push(primary_key_opts.remove(id_selection));
+    }
+    
+    // automatically set primary key as required
+    for key_attr in &primary_key {
+        required.push(AttrName::try_from(key_attr.as_str()).unwrap());
+    }
+
+    // get primary key generator, the options depend on the key type and only single keys can be generated
+    let key_generator_opts: Vec<Option<KeyGenerator>> = [None, Some(KeyGenerator::increment), Some(KeyGenerator::uuid), Some(KeyGenerator::ulid)]
+        .into_iter()
+        .filter(|generator| match generator {
+            Some(generator) => primary_key.len() == 1 && generator.fits(attributes.get(&AttrName::try_from(primary_key[0].as_str()).unwrap()).unwrap()),
+            None => true
+        })
+        .collect();
+    let primary_key_generator: Option<KeyGenerator> = if key_generator_opts.len() > 1 {
+        println!();
+        let generator_selection: usize = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Primary Key Generation:")
+            .default(0)
+            .items(&key_generator_opts.iter().map(|generator| match generator {
+                Some(generator) => format!("{generator:?} (generated by the server)"),
+                None => "none (given by the client)".to_string()
+            }).collect::<Vec<String>>())
+            .interact()
+            .unwrap();
+        key_generator_opts[generator_selection].clone()
+    } else {
+        None
+    };
+
+    // don't allow the user to unselect the key as not required
+    required_opts.retain(|s| !primary_key.contains(s));
+
+    println!();
+
+    // set up the theme for the multi select because it may happen that the used terminal emulator
+    // doesn't show the icon for selected items with the default setting -> ASCII is safe
+    let multi_select_theme = ColorfulTheme{
+        checked_item_prefix: Style::new().green().apply_to(" [X]".to_string()),
+        unchecked_item_prefix: Style::new().red().apply_to(" [ ]".to_string()),
+        ..Default::default()
+    };
+
+    // get required attributes
+    if !required_opts.is_empty() {
+        let required_selection = MultiSelect::with_theme(&multi_select_theme)
+            .with_prompt("Set required attributes:")
+            .items(&required_opts)
+            .interact()
+            .unwrap();
+
+        for attr_index in required_selection {
+            required.push(AttrName::try_from(required_opts[attr_index].as_str()).unwrap());
+        }
+    }
+
+    // get unique attributes, the remaining key candidates since they have the same type restrictions
+    let mut unique: Vec<KeyAttrs> = vec!();
+    if !primary_key_opts.is_empty() {
+        let unique_selection = MultiSelect::with_theme(&multi_select_theme)
+            .with_prompt("Set unique attributes:")
+            .items(&primary_key_opts)
+            .interact()
+            .unwrap();
+
+        for attr_index in unique_selection {
+            unique.push(KeyAttrs::Single(AttrName::try_from(primary_key_opts[attr_index].as_str()).unwrap()));
+        }
+    }
+
+    // get hashed and write-only attributes, which must be neither part of the primary key nor unique
+    let secret_opts: Vec<String> = required_opts.iter()
+        .filter(|attr| !unique.iter().any(|unique_attrs| unique_attrs.contains(&AttrName::try_from(attr.as_str()).unwrap())))
+        .cloned()
+        .collect();
+    let hashed_opts: Vec<String> = secret_opts.iter()
+        .filter(|attr| attributes.get(&AttrName::try_from(attr.as_str()).unwrap()) == Some(&AttrType::Primitive(PrimitiveType::String)))
+        .cloned()
+        .collect();
+    let mut hashed: Vec<AttrName> = vec!();
+    if !hashed_opts.is_empty() {
+        let hashed_selection = MultiSelect::with_theme(&multi_select_theme)
+            .with_prompt("Set hashed attributes, like passwords:")
+            .items(&hashed_opts)
+            .interact()
+            .unwrap();
+
+        for attr_index in hashed_selection {
+            hashed.push(AttrName::try_from(hashed_opts[attr_index].as_str()).unwrap());
+        }
+    }
+    let write_only_opts: Vec<String> = secret_opts.into_iter()
+        .filter(|attr| !hashed.contains(&AttrName::try_from(attr.as_str()).unwrap()))
+        .collect();
+    let mut write_only: Vec<AttrName> = vec!();
+    if !write_only_opts.is_empty() {
+        let write_only_selection = MultiSelect::with_theme(&multi_select_theme)
+            .with_prompt("Set write-only attributes, which are never returned:")
+            .items(&write_only_opts)
+            .interact()
+            .unwrap();
+
+        for attr_index in write_only_selection {
+            write_only.push(AttrName::try_from(write_only_opts[attr_index].as_str()).unwrap());
+        }
+    }
+
+    // add timestamps, unless there are attributes with the same names
+    let timestamps: bool = !attributes.contains_key(&AttrName(CREATED_AT.to_string()))
+        && !attributes.contains_key(&AttrName(UPDATED_AT.to_string()))
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Do you want to add the timestamps {CREATED_AT:?} and {UPDATED_AT:?}?"))
+            .default(false)
+            .interact()
+            .unwrap();
+
+    // enable soft delete, unless there is an attribute with the name of its marker
+    let soft_delete: bool = !attributes.contains_key(&AttrName(DELETED_AT.to_string()))
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Do you want to enable soft delete, marking deleted records via {DELETED_AT:?}?"))
+            .default(false)
+            .interact()
+            .unwrap();
+
+    // add versions for optimistic concurrency, unless there is an attribute with the same name
+    let versioned: bool = !attributes.contains_key(&AttrName(VERSION.to_string()))
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Do you want to count the versions of records in {VERSION:?} to detect conflicting updates?"))
+            .default(false)
+            .interact()
+            .unwrap();
+
+    // keep a history of all changes, unless there is an attribute with the name of the history field
+    let history: bool = !attributes.contains_key(&AttrName(HISTORY.to_string()))
+        && Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to keep a history of all changes to records, allowing to read their past states?")
+            .default(false)
+            .interact()
+            .unwrap();
+
+    // let records expire after a fixed duration, unless there is an attribute with the name of its expiry, or at the time of a DateTime attribute
+    let mut expiry_attrs: Vec<String> = attributes.iter()
+        .filter(|(attr, attr_type)| **attr_type == AttrType::Primitive(PrimitiveType::DateTime) && !primary_key.contains(&attr.0))
+        .map(|(attr, _)| attr.0.clone())
+        .collect();
+    expiry_attrs.sort();
+    let mut ttl_opts: Vec<String> = vec!("never".to_string());
+    if !attributes.contains_key(&AttrName(EXPIRES_AT.to_string())) {
+        ttl_opts.push(format!("after a fixed duration, stored in {EXPIRES_AT:?}"));
+    }
+    ttl_opts.extend(expiry_attrs.iter().map(|attr| format!("at the time of {attr:?}")));
+    let ttl_selection: usize = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("When do records expire?")
+        .items(&ttl_opts)
+        .default(0)
+        .interact()
+        .unwrap();
+    let ttl: Option<Ttl> = match ttl_selection {
+        0 => None,
+        1 if ttl_opts.len() > expiry_attrs.len() + 1 => Some(Ttl::Seconds(
+            Input::<u64>::with_theme(&ColorfulTheme::default())
+                .with_prompt("Time to live in seconds:")
+                .validate_with(|seconds: &u64| -> Result<(), String> {
+                    if *seconds > 0 {
+                        return Ok(());
+                    }
+                    Err("The time to live must be positive".to_string())
+                })
+                .interact_text()
+                .unwrap()
+        )),
+        index => Some(Ttl::Attribute(AttrName::try_from(expiry_attrs[index - (ttl_opts.len() - expiry_attrs.len())].as_str()).unwrap()))
+    };
+
+    // create model definition
+    let mut created_model = ModelDefinition {
+        model_name: ModelName(AttrName::try_from(model_name.as_str()).unwrap()),
+        description: if model_description.trim().is_empty() { None } else { Some(model_description.trim().to_string()) },
+        storage_type,
+        attributes: attributes.clone(),
+        primary_key: match primary_key.len() {
+            1 => KeyAttrs::Single(AttrName::try_from(primary_key[0].as_str()).unwrap()),
+            _ => KeyAttrs::Composite(primary_key.iter().map(|key_attr| AttrName::try_from(key_attr.as_str()).unwrap()).collect())
+        },
+        primary_key_generator,
+        required,
+        write_only: if write_only.is_empty() { None } else { Some(write_only) },
+        hashed: if hashed.is_empty() { None } else { Some(hashed) },
+        unique: if unique.is_empty() { None } else { Some(unique) },
+        constraints: if constraints.is_empty() { None } else { Some(constraints) },
+        descriptions: if descriptions.is_empty() { None } else { Some(descriptions) },
+        timestamps,
+        soft_delete,
+        versioned,
+        history,
+        ttl,
+        rules: None,
+        defaults: None,
+        associations: None,
+        naming: None,
+        resolved_from: vec!()
+    };
+
+    // name the attributes in REST and GraphQL by a naming policy, unless it lets them collide
+    let namings: Vec<Naming> = [Naming::CamelCase, Naming::SnakeCase].into_iter()
+        .filter(|naming| super::validate_model_definition(&ModelDefinition { naming: Some(*naming), ..created_model.clone() }).is_ok())
+        .collect();
+    if !namings.is_empty() {
+        let naming_selection: usize = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Attribute names in REST and GraphQL:")
+            .default(0)
+            .items(&["as defined".to_string()].into_iter().chain(namings.iter().map(|naming| match naming {
+                Naming::CamelCase => "camelCase".to_string(),
+                Naming::SnakeCase => "snake_case".to_string()
+            })).collect::<Vec<String>>())
+            .interact()
+            .unwrap();
+        created_model.naming = naming_selection.checked_sub(1).map(|index| namings[index]);
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        // this should never fail because the user is not allowed to cause an invalid model
+        // so if it really fails, it is a mistake in this dialogue implementation
+        assert!(super::validate_model_definition(&created_model).is_ok(), "Invalid model definition");
+    }
+
+    // get file format
+    let formats: Vec<ModelFormat> = vec!(
+        ModelFormat::json,
+        ModelFormat::yaml,
+        ModelFormat::toml
+    );
+    let format_selection: usize = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("File Format:")
+        .default(0)
+        .items(&formats.iter().map(|format| format!("{format:?}")).collect::<Vec<String>>())
+        .interact()
+        .unwrap();
+    let format: ModelFormat = formats[format_selection];
+
+    // build file path
+    let mut modelspath = PathBuf::new();
+    modelspath.push(args.modelspath);
+    modelspath.push(model_name);
+    modelspath.set_extension(format!("{format:?}"));
+
+    let model_file_path: &Path = modelspath.as_path();
+
+    // the chosen format may not express the definition, then nothing is written
+    let model_file: String = match created_model.to_format(format) {
+        Ok(model_file) => model_file,
+        Err(err) => {
+            eprintln!("unable to write the model as {format:?}, {err}");
+            exit(1);
+        }
+    };
+
+    // try to write the definition to a file, else write it to stdout
+    if write(model_file_path, &model_file).is_err() {
+        println!("{model_file}");
+        eprintln!("unable to write file");
+    }
+}
+
+/*
+    check_models: 
+        Reports every file in the models' path which doesn't provide a model and the number of valid models.
+        Exits with a failure code if there are any issues, so it can be used in scripts.
+*/
+pub fn check_models(args: CheckModels) {
+    match diagnose_models(args.modelspath.as_path()) {
+        Ok((models, issues)) => {
+            for issue in &issues {
+                println!("{issue}");
+            }
+            println!("{count} valid model(s), {issue_count} issue(s)", count=models.len(), issue_count=issues.len());
+            if !issues.is_empty() {
+                exit(1);
+            }
+        },
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    }
+}
+
+// prints the issues of the models' path as warnings, the server starts anyway
+pub fn warn_model_issues(model_path: &Path) {
+    if let Ok((_, issues)) = diagnose_models(model_path) {
+        for issue in issues {
+            eprintln!("warning: {issue}");
+        }
+    }
+}
+
+fn define_constraint(attr_type: &AttrType) -> Option<Constraints> {
+    let constrainable: bool = matches!(attr_type, AttrType::Primitive(PrimitiveType::Integer | PrimitiveType::Float | PrimitiveType::String) | AttrType::Array(_));
+    if !constrainable || !Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Do you want to define constraints?")
+        .default(false)
+        .interact()
+        .unwrap()
+    {
+        return None;
+    }
+
+    // repeat until the constraint is valid, empty inputs leave the respective rule undefined
+    loop {
+        let constraint: Constraints = match attr_type {
+            AttrType::Primitive(PrimitiveType::Integer) => Constraints::Integer(IntConstraint {
+                min: input_optional("Minimum:"),
+                max: input_optional("Maximum:"),
+                multiple_of: input_optional("Multiple Of:")
+            }),
+            AttrType::Primitive(PrimitiveType::Float) => Constraints::Float(FloatConstraint {
+                min: input_optional("Minimum:"),
+                max: input_optional("Maximum:")
+            }),
+            AttrType::Primitive(PrimitiveType::String) => {
+                let min_length: Option<usize> = input_optional("Minimum Length:");
+                let max_length: Option<usize> = input_optional("Maximum Length:");
+                let pattern: Option<Pattern> = input_optional("Pattern (regular expression):");
+                let formats = vec!(
+                    "none",
+                    "email",
+                    "uuid",
+                    "uri"
+                );
+                let format_selection: usize = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Format:")
+                    .default(0)
+                    .items(&formats)
+                    .interact()
+                    .unwrap();
+                Constraints::String(StrConstraint {
+                    min_length,
+                    max_length,
+                    pattern,
+                    format: from_str(format!("{format:?}", format=formats[format_selection]).as_str()).ok()
+                })
+            },
+            AttrType::Array(_) => Constraints::Array(ArrayConstraint {
+                min_items: input_optional("Minimum Items:"),
+                max_items: input_optional("Maximum Items:"),
+                unique_items: Some(Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Unique Items?")
+                    .default(false)
+                    .interact()
+                    .unwrap()
+                ).filter(|unique| *unique)
+            }),
+            _ => unreachable!("only constrainable types reach this point")
+        };
+
+        match constraint.validate(attr_type) {
+            Ok(_) => return Some(constraint),
+            Err(err) => eprintln!("Invalid constraint, {err}")
+        }
+    }
+}
+
+fn input_optional<T: FromStr>(prompt: &str) -> Option<T> {
+    let input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), String> {
+            if input.is_empty() || input.parse::<T>().is_ok() {
+                return Ok(());
+            }
+            Err("Invalid value".to_string())
+        })
+        .interact_text()
+        .unwrap();
+    if input.is_empty() {
+        return None;
+    }
+    input.parse::<T>().ok()
+}
+
+struct AttrNameValidator;
+
+impl Validator<String> for AttrNameValidator {
+    type Err = String;
+
+    fn validate(&mut self, input: &String) -> Result<(), Self::Err> {
+        match AttrName::try_from(input.as_str()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(err.to_string())
+        }
+    }
+}
+
+fn split_enum_values(input: &str) -> Vec<String> {
+    input.split(',').map(|v| v.trim().to_string()).collect()
+}
+
+struct EnumValuesValidator;
+
+impl Validator<String> for EnumValuesValidator {
+    type Err = String;
+
+    fn validate(&mut self, input: &String) -> Result<(), Self::Err> {
+        match validate_enum_values(&split_enum_values(input)) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(format!("Invalid enum, {err}"))
+        }
+    }
+}
+
+struct ModelNameValidator;
+
+impl Validator<String> for ModelNameValidator {
+    type Err = String;
+
+    fn validate(&mut self, input: &String) -> Result<(), Self::Err> {
+        let mut attr_validator = AttrNameValidator;
+        attr_validator.validate(input)?;
+        if pluralize(input) == singularize(input) {
+            return Err("Name has no plural variant".to_string());
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,446 @@
+// used types
+use serde_json::{
+    Number,
+    Value
+};
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+use super::{
+    AttrName,
+    Record
+};
+
+// used functions
+use serde_json::to_value;
+
+/*
+    Expression:
+        A parsed expression of a record-level rule, like "endYear >= year" or "discount == null || price != null".
+
+        Syntax, from the lowest to the highest precedence:
+            a || b, a && b                     logical operators
+            a == b, a != b, a < b, a <= b,     comparisons of numbers, Strings and Booleans,
+            a > b, a >= b                      Dates and times are compared as Strings
+            a + b, a - b                       arithmetic, + concatenates Strings too
+            a * b, a / b
+            !a, -a                             negation
+            (a), attribute, object.attribute,  attributes, nested attributes of objects
+            1, 1.5, "text", 'text',            and literals
+            true, false, null
+
+        Null values propagate through comparisons and arithmetic,
+        logical operators treat null as unknown, so "null || true" is true and "null && true" is null.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Literal(Value),
+    Attribute(Vec<AttrName>),
+    Not(Box<Expression>),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operator {
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Literal(Value),
+    Identifier(String),
+    Operator(Operator),
+    Not,
+    Dot,
+    Open,
+    Close
+}
+
+impl Expression {
+    /*
+        parse:
+            Parses an expression with the syntax described at Expression.
+
+        returns:
+            the parsed expression or an Error describing the syntax error
+    */
+    pub fn parse(expression: &str) -> Result<Expression> {
+        let tokens: Vec<Token> = tokenize(expression)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let parsed: Expression = parser.parse_binary(0)?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(parsed),
+            Some(token) => Err(invalid(format!("unexpected {token:?} at token {position}", position=parser.position + 1)))
+        }
+    }
+
+    // all attribute paths used in the expression
+    pub fn attributes(&self) -> Vec<&Vec<AttrName>> {
+        match self {
+            Expression::Literal(_) => vec!(),
+            Expression::Attribute(path) => vec!(path),
+            Expression::Not(inner) | Expression::Negate(inner) => inner.attributes(),
+            Expression::Binary(left, _, right) => left.attributes().into_iter().chain(right.attributes()).collect()
+        }
+    }
+
+    /*
+        evaluate:
+            Evaluates the expression for the given record, missing attributes are null.
+
+        returns:
+            the resulting value or an Error if operators are applied to values of the wrong types
+    */
+    pub fn evaluate(&self, record: &Record) -> Result<Value> {
+        match self {
+            Expression::Literal(value) => Ok(value.clone()),
+            Expression::Attribute(path) => {
+                let mut value: Value = record.get(&path[0]).map(|value| to_value(value).unwrap()).unwrap_or(Value::Null);
+                for attr in &path[1..] {
+                    value = value.get(&attr.0).cloned().unwrap_or(Value::Null);
+                }
+                Ok(value)
+            },
+            Expression::Not(inner) => match inner.evaluate(record)? {
+                Value::Bool(boolean) => Ok(Value::Bool(!boolean)),
+                Value::Null => Ok(Value::Null),
+                other => Err(invalid(format!("can't negate {other}")))
+            },
+            Expression::Negate(inner) => match inner.evaluate(record)? {
+                Value::Number(number) => Ok(match number.as_i64().and_then(i64::checked_neg) {
+                    Some(integer) => Value::from(integer),
+                    None => Value::from(-number.as_f64().unwrap())
+                }),
+                Value::Null => Ok(Value::Null),
+                other => Err(invalid(format!("can't negate {other}")))
+            },
+            Expression::Binary(left, Operator::Or, right) => {
+                let left: Option<bool> = to_bool(left.evaluate(record)?)?;
+                if left == Some(true) {
+                    return Ok(Value::Bool(true));
+                }
+                match (left, to_bool(right.evaluate(record)?)?) {
+                    (_, Some(true)) => Ok(Value::Bool(true)),
+                    (Some(false), Some(false)) => Ok(Value::Bool(false)),
+                    _ => Ok(Value::Null)
+                }
+            },
+            Expression::Binary(left, Operator::And, right) => {
+                let left: Option<bool> = to_bool(left.evaluate(record)?)?;
+                if left == Some(false) {
+                    return Ok(Value::Bool(false));
+                }
+                match (left, to_bool(right.evaluate(record)?)?) {
+                    (_, Some(false)) => Ok(Value::Bool(false)),
+                    (Some(true), Some(true)) => Ok(Value::Bool(true)),
+                    _ => Ok(Value::Null)
+                }
+            },
+            Expression::Binary(left, operator, right) => apply(left.evaluate(record)?, *operator, right.evaluate(record)?)
+        }
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn to_bool(value: Value) -> Result<Option<bool>> {
+    match value {
+        Value::Bool(boolean) => Ok(Some(boolean)),
+        Value::Null => Ok(None),
+        other => Err(invalid(format!("{other} is no Boolean")))
+    }
+}
+
+// applies comparisons and arithmetic, if one of the values is null the result is null
+fn apply(left: Value, operator: Operator, right: Value) -> Result<Value> {
+    match operator {
+        Operator::Equal => return Ok(Value::Bool(equals(&left, &right))),
+        Operator::NotEqual => return Ok(Value::Bool(!equals(&left, &right))),
+        _ => ()
+    }
+    if left.is_null() || right.is_null() {
+        return Ok(Value::Null);
+    }
+    let ordering = match (&left, &right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64().unwrap().partial_cmp(&r.as_f64().unwrap()),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+        _ => None
+    };
+    let result: Option<Value> = match operator {
+        Operator::Less => ordering.map(|ordering| Value::Bool(ordering.is_lt())),
+        Operator::LessEqual => ordering.map(|ordering| Value::Bool(ordering.is_le())),
+        Operator::Greater => ordering.map(|ordering| Value::Bool(ordering.is_gt())),
+        Operator::GreaterEqual => ordering.map(|ordering| Value::Bool(ordering.is_ge())),
+        _ => match (&left, &right) {
+            (Value::String(l), Value::String(r)) if operator == Operator::Add => Some(Value::from(format!("{l}{r}"))),
+            (Value::Number(l), Value::Number(r)) => calculate(l, operator, r),
+            _ => None
+        }
+    };
+    result.ok_or(invalid(format!("can't apply {operator:?} to {left} and {right}")))
+}
+
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64() == r.as_f64(),
+        _ => left == right
+    }
+}
+
+// Integers stay Integers unless they get divided, overflows and divisions by zero have no result
+fn calculate(left: &Number, operator: Operator, right: &Number) -> Option<Value> {
+    if let (Some(l), Some(r), false) = (left.as_i64(), right.as_i64(), operator == Operator::Divide) {
+        return match operator {
+            Operator::Add => l.checked_add(r),
+            Operator::Subtract => l.checked_sub(r),
+            _ => l.checked_mul(r)
+        }.map(Value::from);
+    }
+    let (l, r) = (left.as_f64()?, right.as_f64()?);
+    let result: f64 = match operator {
+        Operator::Add => l + r,
+        Operator::Subtract => l - r,
+        Operator::Multiply => l * r,
+        _ => l / r
+    };
+    Number::from_f64(result).map(Value::Number)
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens: Vec<Token> = vec!();
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let c: char = chars[i];
+        let next: Option<char> = chars.get(i + 1).copied();
+        let (token, length): (Token, usize) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            },
+            ('|', Some('|')) => (Token::Operator(Operator::Or), 2),
+            ('&', Some('&')) => (Token::Operator(Operator::And), 2),
+            ('=', Some('=')) => (Token::Operator(Operator::Equal), 2),
+            ('!', Some('=')) => (Token::Operator(Operator::NotEqual), 2),
+            ('<', Some('=')) => (Token::Operator(Operator::LessEqual), 2),
+            ('>', Some('=')) => (Token::Operator(Operator::GreaterEqual), 2),
+            ('<', _) => (Token::Operator(Operator::Less), 1),
+            ('>', _) => (Token::Operator(Operator::Greater), 1),
+            ('+', _) => (Token::Operator(Operator::Add), 1),
+            ('-', _) => (Token::Operator(Operator::Subtract), 1),
+            ('*', _) => (Token::Operator(Operator::Multiply), 1),
+            ('/', _) => (Token::Operator(Operator::Divide), 1),
+            ('!', _) => (Token::Not, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('"' | '\'', _) => {
+                let end: usize = match chars[i + 1..].iter().position(|other| other == &c) {
+                    Some(end) => i + 1 + end,
+                    None => return Err(invalid(format!("unterminated String at character {position}", position=i + 1)))
+                };
+                (Token::Literal(Value::from(chars[i + 1..end].iter().collect::<String>())), end + 1 - i)
+            },
+            (c, _) if c.is_ascii_digit() => {
+                let length: usize = chars[i..].iter().take_while(|other| other.is_ascii_digit() || other == &&'.').count();
+                let number: String = chars[i..i + length].iter().collect();
+                let value: Value = match (number.parse::<i64>(), number.parse::<f64>()) {
+                    (Ok(integer), _) => Value::from(integer),
+                    (_, Ok(float)) => Value::from(float),
+                    _ => return Err(invalid(format!("invalid number {number}")))
+                };
+                (Token::Literal(value), length)
+            },
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let length: usize = chars[i..].iter().take_while(|other| other.is_alphanumeric() || other == &&'_').count();
+                let word: String = chars[i..i + length].iter().collect();
+                let token: Token = match word.as_str() {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    _ => Token::Identifier(word)
+                };
+                (token, length)
+            },
+            (c, _) => return Err(invalid(format!("unexpected character {c:?} at character {position}", position=i + 1)))
+        };
+        tokens.push(token);
+        i += length;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize
+}
+
+// binary operators from the lowest to the highest precedence
+const PRECEDENCE: [&[Operator]; 5] = [
+    &[Operator::Or],
+    &[Operator::And],
+    &[Operator::Equal, Operator::NotEqual, Operator::Less, Operator::LessEqual, Operator::Greater, Operator::GreaterEqual],
+    &[Operator::Add, Operator::Subtract],
+    &[Operator::Multiply, Operator::Divide]
+];
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token: Option<Token> = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+    fn parse_binary(&mut self, level: usize) -> Result<Expression> {
+        if level == PRECEDENCE.len() {
+            return self.parse_unary();
+        }
+        let mut left: Expression = self.parse_binary(level + 1)?;
+        while let Some(Token::Operator(operator)) = self.tokens.get(self.position) {
+            if !PRECEDENCE[level].contains(operator) {
+                break;
+            }
+            let operator: Operator = *operator;
+            self.position += 1;
+            let right: Expression = self.parse_binary(level + 1)?;
+            left = Expression::Binary(Box::new(left), operator, Box::new(right));
+            // comparisons can't be chained
+            if level == 2 {
+                break;
+            }
+        }
+        Ok(left)
+    }
+    fn parse_unary(&mut self) -> Result<Expression> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expression::Not(Box::new(self.parse_unary()?))),
+            Some(Token::Operator(Operator::Subtract)) => Ok(Expression::Negate(Box::new(self.parse_unary()?))),
+            Some(Token::Literal(value)) => Ok(Expression::Literal(value)),
+            Some(Token::Identifier(name)) => {
+                let mut path: Vec<AttrName> = vec!(AttrName(name));
+                while self.tokens.get(self.position) == Some(&Token::Dot) {
+                    self.position += 1;
+                    match self.next() {
+                        Some(Token::Identifier(name)) => path.push(AttrName(name)),
+                        _ => return Err(invalid(format!("expected an attribute name at token {position}", position=self.position)))
+                    }
+                }
+                Ok(Expression::Attribute(path))
+            },
+            Some(Token::Open) => {
+                let inner: Expression = self.parse_binary(0)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err(invalid(format!("expected ) at token {position}", position=self.position)))
+                }
+            },
+            Some(token) => Err(invalid(format!("unexpected {token:?} at token {position}", position=self.position))),
+            None => Err(invalid("unexpected end of the expression".to_string()))
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{
+        TruePrimitiveType,
+        TrueType,
+        NULL
+    };
+
+    #[test]
+    fn test_parse() {
+        let attr = |name: &str| Box::new(Expression::Attribute(vec!(AttrName(name.to_string()))));
+        assert_eq!(
+            Expression::parse("a || b && !c").unwrap(),
+            Expression::Binary(attr("a"), Operator::Or, Box::new(Expression::Binary(attr("b"), Operator::And, Box::new(Expression::Not(attr("c")))))),
+            "Expected && to bind stronger than ||"
+        );
+        assert_eq!(
+            Expression::parse("(a + 2) * 3 >= -b").unwrap(),
+            Expression::Binary(
+                Box::new(Expression::Binary(
+                    Box::new(Expression::Binary(attr("a"), Operator::Add, Box::new(Expression::Literal(Value::from(2))))),
+                    Operator::Multiply,
+                    Box::new(Expression::Literal(Value::from(3)))
+                )),
+                Operator::GreaterEqual,
+                Box::new(Expression::Negate(attr("b")))
+            ),
+            "Expected parentheses and precedence of arithmetic"
+        );
+        assert_eq!(
+            Expression::parse("address.city == 'Berlin'").unwrap(),
+            Expression::Binary(
+                Box::new(Expression::Attribute(vec!(AttrName("address".to_string()), AttrName("city".to_string())))),
+                Operator::Equal,
+                Box::new(Expression::Literal(Value::from("Berlin")))
+            ),
+            "Expected nested attributes and String literals"
+        );
+
+        for invalid in ["", "a ==", "(a", "a b", "a == b == c", "'text", "a # b", "1.2.3", "a.", "a & b"] {
+            assert!(Expression::parse(invalid).is_err(), "Expected Error for invalid expression {invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let record: Record = Record::from([
+            (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1999)))),
+            (AttrName("endYear".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(2003)))),
+            (AttrName("price".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Float(9.5)))),
+            (AttrName("discount".to_string()), NULL),
+            (AttrName("title".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Matrix".to_string())))),
+            (AttrName("released".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("1999-03-31".to_string())))),
+            (AttrName("sequel".to_string()), TrueType::Object(Some(Record::from([
+                (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(2003))))
+            ]))))
+        ]);
+
+        for (expression, expected) in [
+            ("endYear >= year", Value::Bool(true)),
+            ("endYear - year == 4", Value::Bool(true)),
+            ("price * 2 == 19", Value::Bool(true)),
+            ("year / 2 > 999", Value::Bool(true)),
+            ("discount == null || price != null", Value::Bool(true)),
+            ("discount < price", Value::Null),
+            ("discount < price || true", Value::Bool(true)),
+            ("discount < price && false", Value::Bool(false)),
+            ("discount + 1", Value::Null),
+            ("title + ' Reloaded'", Value::from("Matrix Reloaded")),
+            ("released < '2000-01-01'", Value::Bool(true)),
+            ("sequel.year == endYear", Value::Bool(true)),
+            ("sequel.missing == null", Value::Bool(true)),
+            ("notExisting", Value::Null),
+            ("!(year == 1999)", Value::Bool(false)),
+            ("-year < 0", Value::Bool(true))
+        ] {
+            assert_eq!(Expression::parse(expression).unwrap().evaluate(&record).unwrap(), expected, "Unexpected result of {expression:?}");
+        }
+
+        for expression in ["title > 1", "title - 'x'", "year || true", "!title", "year / 0"] {
+            assert!(Expression::parse(expression).unwrap().evaluate(&record).is_err(), "Expected Error when evaluating {expression:?}");
+        }
+    }
+}
@@ -1,137 +1,250 @@
-mod json;
-
-use crate::cli;
-
-use std::path::PathBuf;
-use std::io::{
-    ErrorKind,
-    Result,
-    Error
-};
-use json::{
-    JsonStorageHandler,
-    JsonStorageConfig
-};
-use serde_derive::{
-    Deserialize,
-    Serialize
-};
-use super::{
-    ModelDefinition,
-    TrueType,
-    Record
-};
-use dialoguer::{
-    theme::ColorfulTheme,
-    Confirm,
-    Select
-};
-
-use std::fs::read_to_string;
-use std::fs::write;
-use serde_json::{
-    to_string_pretty,
-    from_str
-};
-
-#[allow(non_camel_case_types)]
-#[derive(Deserialize, Serialize, Debug, PartialEq, Hash, Eq, Clone)]
-// if adding storage types, add them in the create-model dialogue in cli.rs too
-pub enum StorageType {
-    json
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-struct StorageConfig {
-    json: Option<JsonStorageConfig>
-}
-
-pub trait StorageHandler {
-    fn create_one(&self, record: &Record) -> Result<Record>;
-    fn read_one(&self, id: &TrueType) -> Result<Record>;
-    fn update_one(&self, record: &Record) -> Result<Record>;
-    fn delete_one(&self, id: &TrueType) -> Result<Record>;
-}
-
-pub fn get_handler(model: &ModelDefinition) -> Result<impl StorageHandler> {
-    if let Some(start) = cli::get_valid_start_args() {
-        let storage_config: StorageConfig = get_storage_configs(start.storage_definitions)?;
-        return match model.storage_type {
-            StorageType::json =>
-                Ok(
-                    JsonStorageHandler {
-                        model_name: model.model_name.clone(),
-                        key_attr: model.primary_key.clone(),
-                        config: storage_config.json.unwrap()
-                    }
-                ),
-        };
-    }
-    todo!("getting storage handlers is currently only possible when the server is running")
-}
-
-fn get_storage_configs(storage_file_path: Option<PathBuf>) -> Result<StorageConfig> {
-    let mut storage_configs = StorageConfig {
-        json: None
-    };
-    if let Some(path_buf) = storage_file_path {
-        let data: Result<String> = read_to_string(path_buf.as_path());
-        if data.is_err() {
-            return Err(Error::new(ErrorKind::PermissionDenied, "Unable to read storage definition file, make sure it's utf-8 only"))
-        }
-        match from_str::<StorageConfig>(&data.unwrap()) {
-            Ok(storages) => storage_configs = storages,
-            Err(err) => if !err.is_eof() {
-                return Err(Error::new(ErrorKind::InvalidData, "The storage definition file is invalid"));
-            }
-        }
-    }
-
-    // set defaults if None
-    if storage_configs.json.is_none() {
-        storage_configs.json = Some(
-            JsonStorageConfig {
-                storage_file: None
-            }
-        );
-    }
-
-    Ok(storage_configs)
-}
-
-pub fn configure_storages(args: cli::ConfigureStorages) {
-    let mut configs: StorageConfig = match get_storage_configs(Some(args.storage_definitions.clone())) {
-        Ok(configs) => configs,
-        Err(_) => get_storage_configs(None).unwrap()
-    };
-
-    loop {
-        let possible_storage_types = &[
-            "json"
-        ];
-        let type_selection: usize = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Storage Type:")
-            .default(0)
-            .items(possible_storage_types)
-            .interact()
-            .unwrap();
-        println!();
-        match possible_storage_types[type_selection] {
-            "json" => configs.json = Some(json::json_cli::configure_storage()),
-            _ => unreachable!("All possible storage types have to be handled here")
-        }
-        if !Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Do you want to configure another storage type?")
-            .interact()
-            .unwrap()
-        {
-            break;
-        }
-    }
-
-    // try to write the config to a file, else write it to stdout
-    if write(args.storage_definitions, to_string_pretty(&configs).unwrap()).is_err() {
-        println!("{config}", config=to_string_pretty(&configs).unwrap());
-        eprintln!("unable to write file");
-    }
+mod json;
+
+use crate::cli;
+
+use std::path::PathBuf;
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+use json::{
+    JsonStorageHandler,
+    JsonStorageConfig
+};
+use serde_derive::{
+    Deserialize,
+    Serialize
+};
+use super::{
+    TruePrimitiveType,
+    ModelDefinition,
+    AttrName,
+    TrueType,
+    KeyAttrs,
+    Naming,
+    Record,
+    NULL,
+    VERSION
+};
+use std::fmt;
+use dialoguer::{
+    theme::ColorfulTheme,
+    Confirm,
+    Select
+};
+
+use std::fs::File;
+use std::fs::read_to_string;
+use std::fs::write;
+use serde_json::{
+    to_string_pretty,
+    from_str
+};
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Hash, Eq, Clone)]
+// if adding storage types, add them in the create-model dialogue in cli.rs too
+pub enum StorageType {
+    json
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct StorageConfig {
+    json: Option<JsonStorageConfig>
+}
+
+pub trait StorageHandler {
+    fn create_one(&self, record: &Record) -> Result<Record>;
+    fn read_one(&self, key: &[TrueType]) -> Result<Record>;
+    fn read_many(&self, filter: &Record) -> Result<Vec<Record>>;
+    fn update_one(&self, record: &Record) -> Result<Record>;
+    fn delete_one(&self, key: &[TrueType], expected_version: Option<i64>) -> Result<Record>;
+    fn next_sequence(&self) -> Result<i64>;
+    // the model definition applied by the last migration, None if there wasn't any
+    fn read_definition(&self) -> Result<Option<ModelDefinition>>;
+    // replaces all records of the model by the given ones, if any, and memorizes the definition as applied
+    fn migrate(&self, definition: &ModelDefinition, records: Option<&[Record]>) -> Result<()>;
+    // locks the storage against other processes until the returned file is dropped, exclusive for migrations, shared for servers
+    fn lock_process(&self, exclusive: bool) -> Result<File>;
+}
+
+pub fn get_handler(model: &ModelDefinition) -> Result<impl StorageHandler> {
+    if let Some(start) = cli::get_valid_start_args() {
+        return get_handler_for(model, start.storage_definitions);
+    }
+    todo!("getting storage handlers is currently only possible when the server is running")
+}
+
+// like get_handler, but with the storage definitions' file of another command than start
+pub fn get_handler_for(model: &ModelDefinition, storage_definitions: Option<PathBuf>) -> Result<impl StorageHandler> {
+    let storage_config: StorageConfig = get_storage_configs(storage_definitions)?;
+    match model.storage_type {
+        StorageType::json =>
+            Ok(
+                JsonStorageHandler {
+                    model_name: model.model_name.clone(),
+                    key_attrs: model.primary_key.attrs(),
+                    unique: model.unique.iter().flatten().map(KeyAttrs::attrs).collect(),
+                    versioned: model.versioned,
+                    naming: model.naming,
+                    config: storage_config.json.unwrap()
+                }
+            ),
+    }
+}
+
+/*
+    check_unique: 
+        Checks if a record violates a unique constraint, compared to the other records of its model.
+        Every storage handler has to call it before creating or updating records,
+        while the given records must not change until the record is stored.
+        Unique attribute sets containing a null value are never violated.
+
+    returns:
+        Empty tuple if no other record has the same values, else an Error containing Conflict
+*/
+pub fn check_unique<'a>(record: &Record, others: impl Iterator<Item = &'a Record>, unique: &[Vec<AttrName>], naming: Option<&Naming>) -> Result<()> {
+    let checked: Vec<&Vec<AttrName>> = unique.iter()
+        .filter(|attrs| attrs.iter().all(|attr| record.get(attr).is_some_and(|value| value != &NULL)))
+        .collect();
+    if checked.is_empty() {
+        return Ok(());
+    }
+    for other in others {
+        for attrs in &checked {
+            if attrs.iter().all(|attr| other.get(attr) == record.get(attr)) {
+                let attr_names: Vec<String> = attrs.iter().map(|attr| attr.named(naming).0).collect();
+                return Err(Error::new(ErrorKind::AlreadyExists, Conflict(format!("A record with the same value of the unique attributes {attr_names:?} already exists"))));
+            }
+        }
+    }
+    Ok(())
+}
+
+// error of writes conflicting with other stored records than the written one
+#[derive(Debug)]
+pub struct Conflict(pub String);
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{message}", message=self.0)
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+// error of writes expecting another version of a record than the stored one, records of unversioned models have none
+#[derive(Debug)]
+pub struct VersionMismatch {
+    pub expected: i64,
+    pub stored: Option<i64>
+}
+
+impl fmt::Display for VersionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stored {
+            Some(stored) => write!(f, "Version mismatch, expected version {expected} but the stored version is {stored}", expected=self.expected),
+            None => write!(f, "Version mismatch, expected version {expected} but the model isn't versioned", expected=self.expected)
+        }
+    }
+}
+
+impl std::error::Error for VersionMismatch {}
+
+pub fn version_of(record: &Record) -> Option<i64> {
+    match record.get(&AttrName(VERSION.to_string())) {
+        Some(TrueType::Primitive(Some(TruePrimitiveType::Integer(version)))) => Some(*version),
+        _ => None
+    }
+}
+
+/*
+    next_version: 
+        Checks the expected version of a write against the stored record of a versioned model.
+        Every storage handler has to call it for versioned models before creating, updating or deleting records,
+        while the stored record must not change until the write is done.
+        Without expected version every write is accepted.
+
+    returns:
+        the version of the written record, one higher than the stored one,
+        or an Error containing VersionMismatch if the versions differ
+*/
+pub fn next_version(stored: Option<&Record>, expected_version: Option<i64>) -> Result<TrueType> {
+    let stored_version: i64 = stored.and_then(version_of).unwrap_or(0);
+    if let Some(expected) = expected_version {
+        if expected != stored_version {
+            return Err(Error::new(ErrorKind::InvalidInput, VersionMismatch {
+                expected,
+                stored: Some(stored_version)
+            }));
+        }
+    }
+    Ok(TrueType::Primitive(Some(TruePrimitiveType::Integer(stored_version + 1))))
+}
+
+fn get_storage_configs(storage_file_path: Option<PathBuf>) -> Result<StorageConfig> {
+    let mut storage_configs = StorageConfig {
+        json: None
+    };
+    if let Some(path_buf) = storage_file_path {
+        let data: Result<String> = read_to_string(path_buf.as_path());
+        if data.is_err() {
+            return Err(Error::new(ErrorKind::PermissionDenied, "Unable to read storage definition file, make sure it's utf-8 only"))
+        }
+        match from_str::<StorageConfig>(&data.unwrap()) {
+            Ok(storages) => storage_configs = storages,
+            Err(err) => if !err.is_eof() {
+                return Err(Error::new(ErrorKind::InvalidData, "The storage definition file is invalid"));
+            }
+        }
+    }
+
+    // set defaults if None
+    if storage_configs.json.is_none() {
+        storage_configs.json = Some(
+            JsonStorageConfig {
+                storage_file: None
+            }
+        );
+    }
+
+    Ok(storage_configs)
+}
+
+pub fn configure_storages(args: cli::ConfigureStorages) {
+    let mut configs: StorageConfig = match get_storage_configs(Some(args.storage_definitions.clone())) {
+        Ok(configs) => configs,
+        Err(_) => get_storage_configs(None).unwrap()
+    };
+
+    loop {
+        let possible_storage_types = &[
+            "json"
+        ];
+        let type_selection: usize = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Storage Type:")
+            .default(0)
+            .items(possible_storage_types)
+            .interact()
+            .unwrap();
+        println!();
+        match possible_storage_types[type_selection] {
+            "json" => configs.json = Some(json::json_cli::configure_storage()),
+            _ => unreachable!("All possible storage types have to be handled here")
+        }
+        if !Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to configure another storage type?")
+            .interact()
+            .unwrap()
+        {
+            break;
+        }
+    }
+
+    // try to write the config to a file, else write it to stdout
+    if write(args.storage_definitions, to_string_pretty(&configs).unwrap()).is_err() {
+        println!("{config}", config=to_string_pretty(&configs).unwrap());
+        eprintln!("unable to write file");
+    }
 }
\ No newline at end of file
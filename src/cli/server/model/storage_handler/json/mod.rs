@@ -1,325 +1,819 @@
-pub mod json_cli;
-
-// used types
-use std::collections::HashMap;
-use std::path::PathBuf;
-use serde_derive::{
-    Deserialize,
-    Serialize
-};
-use std::io::{
-    ErrorKind,
-    Result,
-    Error
-};
-use super::StorageHandler;
-use super::super::{
-    ModelName,
-    AttrName,
-    TrueType,
-    Record
-};
-use std::fs::{
-    read_to_string,
-    write
-};
-use serde_json::{
-    to_string,
-    from_str
-};
-
-const DEFAULT_STORAGE_FILE: &str = "./data.json.gus";
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct JsonStorageConfig {
-    pub storage_file: Option<PathBuf>
-}
-
-pub struct JsonStorageHandler {
-    pub key_attr: AttrName,
-    pub model_name: ModelName,
-    pub config: JsonStorageConfig
-}
-
-impl JsonStorageHandler {
-    fn read_db(&self) -> Result<HashMap<ModelName, HashMap<String, Record>>> {
-        let storage_file: &PathBuf = &self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
-        let mut db: HashMap<ModelName, HashMap<String, Record>> = HashMap::new();
-        match read_to_string(storage_file) {
-            Ok(data) => {
-                match from_str(&data) {
-                    Ok(parsed) => db = parsed,
-                    Err(err) => {
-                        if !err.is_eof() {
-                            return Err(Error::new(ErrorKind::InvalidData, "Invalid storage file"));
-                        }
-                    }
-                }
-            },
-            Err(err) => {
-                match err.kind() {
-                    ErrorKind::NotFound => (),
-                    other => return Err(Error::new(other, format!("Unable to read storage file {path}", path=storage_file.display()).as_str()))
-                }
-            }
-        }
-
-        if db.get(&self.model_name).is_none() {
-            db.insert(self.model_name.clone(), HashMap::new());
-        }
-
-        Ok(db.clone())
-    }
-    fn save(&self, db: &HashMap<ModelName, HashMap<String, Record>>) -> Result<()> {
-        let storage_file: &PathBuf = &self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
-        if write(storage_file, to_string(db).unwrap()).is_err() {
-            return Err(Error::new(ErrorKind::PermissionDenied, format!("Unable to write data to storage file {path}", path=storage_file.display()).as_str()));
-        }
-
-        Ok(())
-    }
-}
-
-impl StorageHandler for JsonStorageHandler {
-    fn create_one(&self, record: &Record) -> Result<Record> {
-        let id_string: String = to_string(record.get(&self.key_attr).unwrap()).unwrap();
-        let mut db = self.read_db()?;
-        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
-        if data.get(&id_string).is_some() {
-            return Err(Error::new(ErrorKind::AlreadyExists, "A record for the given key already exists, try to update it instead (PUT)"));
-        }
-        data.insert(id_string, record.clone());
-        db.insert(self.model_name.clone(), data);
-        self.save(&db)?;
-
-        Ok(record.clone())
-    }
-    fn read_one(&self, id: &TrueType) -> Result<Record> {
-        let id_string: &String = &to_string(id).unwrap();
-        let db = self.read_db()?;
-        let data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
-        match data.get(id_string) {
-            Some(record) => Ok(record.clone()),
-            None => Err(Error::new(ErrorKind::NotFound, format!("No record found with id: {id_string}").as_str())),
-        }
-    }
-    fn update_one(&self, record: &Record) -> Result<Record> {
-        let id_string: String = to_string(record.get(&self.key_attr).unwrap()).unwrap();
-        let mut db = self.read_db()?;
-        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
-        let mut new_record: Record;
-        if let Some(orig_record) = data.get(&id_string) {
-            new_record = orig_record.clone();
-            for (key, value) in record {
-                new_record.insert(key.clone(), value.clone());
-            }
-        } else {
-            return Err(Error::new(ErrorKind::NotFound, "No record found for the given key, try to create it instead (POST)"));
-        }
-
-        data.insert(id_string, new_record.clone());
-        db.insert(self.model_name.clone(), data);
-        self.save(&db)?;
-
-        Ok(new_record)
-    }
-    fn delete_one(&self, id: &TrueType) -> Result<Record> {
-        let id_string: String = to_string(id).unwrap();
-        let mut db = self.read_db()?;
-        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
-        let record: Option<Record> = data.remove(&id_string);
-        if record.is_none() {
-            return Err(Error::new(ErrorKind::NotFound, format!("No record found to remove with id: {id_string}").as_str()));
-        }
-        db.insert(self.model_name.clone(), data);
-        self.save(&db)?;
-
-        Ok(record.unwrap())
-    }
-}
-
-
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cli::server::model::{
-        TruePrimitiveType,
-        AttrName
-    };
-
-    use std::fs::remove_file;
-
-    fn pre_test(file_name: &str) {
-        if PathBuf::from(file_name).as_path().is_file() {
-            assert!(remove_file(file_name).is_ok(), "Storage file {file_name} already existing, unable to remove");
-        }
-    }
-
-    fn post_test(file_name: &str) {
-        if PathBuf::from(file_name).as_path().is_file() {
-            assert!(remove_file(file_name).is_ok(), "Unable to remove storage file {file_name} after test");
-        }
-    }
-
-    #[test]
-    fn test_read_db() {
-        const TEST_STORAGE_FILE: &str = "test_read_db.json";
-
-        pre_test(TEST_STORAGE_FILE);
-        let handler = JsonStorageHandler {
-            model_name: ModelName(AttrName("movie".to_string())),
-            key_attr: AttrName("id".to_string()),
-            config: JsonStorageConfig {
-                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
-            }
-        };
-
-        // storage file doesn't exist
-        let mut db: Result<HashMap<ModelName, HashMap<String, Record>>> = handler.read_db();
-        assert!(db.is_ok(), "Unexpected Error when reading not existing storage file");
-        let mut expected: HashMap<ModelName, HashMap<String, Record>> = HashMap::from([
-            (handler.model_name.clone(), HashMap::new())
-        ]);
-        assert_eq!(&db.unwrap(), &expected, "Expected HashMap {map} when reading not existing storage file", map="{<model name>: {}}");
-
-        // storage file empty
-        // create file instead of write because it is not interpreted as existing
-        assert!(write(TEST_STORAGE_FILE, "").is_ok(), "Unable to write storage file for tests");
-        db = handler.read_db();
-        assert!(db.is_ok(), "Unexpected Error after reading from empty storage file");
-        assert_eq!(&db.unwrap(), &expected, "Expected HashMap {map} when reading from empty storage file", map="{<model name>: {}}");
-
-        // storage file with data, movie missing
-        assert!(write(TEST_STORAGE_FILE, "{\"another\": {\"1\": {\"id\": 1}}}").is_ok(), "Unable to write storage file for tests");
-        db = handler.read_db();
-        assert!(db.is_ok(), "Unexpected Error after reading from valid storage file with no respective model data");
-        expected = HashMap::from([
-            (ModelName(AttrName("another".to_string())), HashMap::from([
-                    (
-                        "1".to_string(),
-                        Record::from([(AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1))))])
-                    )
-                ])
-            ),
-            (handler.model_name.clone(), HashMap::new())
-        ]);
-        assert_eq!(&db.unwrap(), &expected, "Gotten HashMap doesn't match the expected when reading from valid storage file with data but no respective model data");
-
-        // storage file not JSON
-        assert!(write(TEST_STORAGE_FILE, "i am not json {\"id\":false}").is_ok(), "Unable to write storage file for tests");
-        db = handler.read_db();
-        assert!(db.is_err(), "Expected Error after reading from invalid storage file");
-
-        post_test(TEST_STORAGE_FILE);
-    }
-
-    #[test]
-    fn test_create_one() {
-        const TEST_STORAGE_FILE: &str = "test_create_one.json";
-
-        pre_test(TEST_STORAGE_FILE);
-        let handler = JsonStorageHandler {
-            model_name: ModelName(AttrName("movie".to_string())),
-            key_attr: AttrName("id".to_string()),
-            config: JsonStorageConfig {
-                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
-            }
-        };
-        for key in ["1", "\"1\"", "true"] {
-            let record = Record::from([
-                (AttrName("id".to_string()), from_str::<TrueType>(key).unwrap()),
-                (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Natural Born Killers".to_string())))),
-                (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1994)))),
-                (AttrName("actors".to_string()), TrueType::Array(Some(vec![TruePrimitiveType::String("Woody Harrelson".to_string()), TruePrimitiveType::String("Juliette Lewis".to_string())]))),
-                (AttrName("recommended".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Boolean(true))))
-            ]);
-            assert_eq!(handler.create_one(&record).unwrap(), record, "Creating a valid new record failed");
-            assert!(handler.create_one(&record).is_err(), "Created a new record with already existing id");
-        }
-
-        post_test(TEST_STORAGE_FILE);
-    }
-
-    #[test]
-    fn test_read_one() {
-        const TEST_STORAGE_FILE: &str = "test_read_one.json";
-
-        pre_test(TEST_STORAGE_FILE);
-        let handler = JsonStorageHandler {
-            model_name: ModelName(AttrName("movie".to_string())),
-            key_attr: AttrName("id".to_string()),
-            config: JsonStorageConfig {
-                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
-            }
-        };
-        for key in ["1", "\"1\"", "true"] {
-            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":{key} }} }} }}")).is_ok(), "Unable to write storage file for tests");
-            let id: TrueType = from_str(key).unwrap();
-            let record = Record::from([
-                (AttrName("id".to_string()), id.clone())
-            ]);
-            assert_eq!(handler.read_one(&id).unwrap(), record, "Reading a valid new record failed");
-        }
-
-        assert!(handler.read_one(&from_str::<TrueType>("\"not existing\"").unwrap()).is_err(), "Expected error when reading from a not existing file");
-
-        post_test(TEST_STORAGE_FILE);
-    }
-
-    #[test]
-    fn test_update_one() {
-        const TEST_STORAGE_FILE: &str = "test_update_one.json";
-
-        pre_test(TEST_STORAGE_FILE);
-        let handler = JsonStorageHandler {
-            model_name: ModelName(AttrName("movie".to_string())),
-            key_attr: AttrName("id".to_string()),
-            config: JsonStorageConfig {
-                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
-            }
-        };
-        for key in ["1", "\"1\"", "true"] {
-            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":\"dummy\" }} }} }}")).is_ok(), "Unable to write storage file for tests");
-            let id: TrueType = from_str(key).unwrap();
-            let record = Record::from([
-                (AttrName("id".to_string()), id.clone())
-            ]);
-            assert_eq!(handler.update_one(&record).unwrap(), record, "Updating an existing record failed");
-
-            let record = Record::from([
-                (AttrName("id".to_string()), from_str::<TrueType>("\"not existing\"").unwrap())
-            ]);
-            assert!(handler.update_one(&record).is_err(), "Expected an error when updating a not existing record");
-        }
-
-        post_test(TEST_STORAGE_FILE);
-    }
-
-    #[test]
-    fn test_delete_one() {
-        const TEST_STORAGE_FILE: &str = "test_delete_one.json";
-
-        pre_test(TEST_STORAGE_FILE);
-        let handler = JsonStorageHandler {
-            model_name: ModelName(AttrName("movie".to_string())),
-            key_attr: AttrName("id".to_string()),
-            config: JsonStorageConfig {
-                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
-            }
-        };
-        for key in ["1", "\"1\"", "true"] {
-            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":{key} }} }} }}")).is_ok(), "Unable to write storage file for tests");
-            let id: TrueType = from_str(key).unwrap();
-            let record = Record::from([
-                (AttrName("id".to_string()), id.clone())
-            ]);
-            assert_eq!(handler.delete_one(&id).unwrap(), record, "Deleting a valid new record failed");
-        }
-
-        assert!(handler.delete_one(&from_str::<TrueType>("\"not existing\"").unwrap()).is_err(), "Expected error when deleting from a not existing file");
-
-        post_test(TEST_STORAGE_FILE);
-    }
-}
\ No newline at end of file
+pub mod json_cli;
+
+// used types
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{
+    MutexGuard,
+    Mutex
+};
+use serde_derive::{
+    Deserialize,
+    Serialize
+};
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+use super::StorageHandler;
+use super::super::{
+    ModelDefinition,
+    ModelName,
+    AttrName,
+    Naming,
+    TrueType,
+    Record,
+    VERSION
+};
+use std::fs::{
+    TryLockError,
+    OpenOptions,
+    File,
+    read_to_string,
+    rename,
+    write
+};
+
+// used functions
+use super::{
+    check_unique,
+    next_version,
+    version_of
+};
+use serde_json::{
+    to_string,
+    from_str
+};
+
+const DEFAULT_STORAGE_FILE: &str = "./data.json.gus";
+const META_FILE_EXTENSION: &str = "meta";
+const LOCK_FILE_EXTENSION: &str = "lock";
+
+// requests are handled concurrently, so every access to the storage files has to hold this lock
+static STORAGE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct JsonStorageConfig {
+    pub storage_file: Option<PathBuf>
+}
+
+pub struct JsonStorageHandler {
+    pub key_attrs: Vec<AttrName>,
+    pub unique: Vec<Vec<AttrName>>,
+    pub versioned: bool,
+    // only used to name the attributes in errors like they are exposed
+    pub naming: Option<Naming>,
+    pub model_name: ModelName,
+    pub config: JsonStorageConfig
+}
+
+// stored next to the storage file, holds data about the stored models instead of records
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq)]
+struct JsonStorageMeta {
+    #[serde(default)]
+    sequences: HashMap<ModelName, i64>,
+
+    #[serde(default)]
+    definitions: HashMap<ModelName, ModelDefinition>
+}
+
+/*
+    key_string: 
+        Builds the string used as key for a record in the storage file.
+        Single keys are stored as their JSON value, composite keys as JSON array of their values.
+
+    returns:
+        the key string
+*/
+fn key_string(key: &[TrueType]) -> String {
+    match key {
+        [single] => to_string(single).unwrap(),
+        composite => to_string(composite).unwrap()
+    }
+}
+
+fn lock_storage() -> MutexGuard<'static, ()> {
+    // a poisoned lock only means another request panicked, the files themselves are written at once
+    STORAGE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/*
+    replace_file:
+        Writes the data to a temporary file next to the given one and renames it afterwards,
+        so other processes reading the file never see it half-written.
+
+    returns:
+        Empty tuple if the file was replaced, else Error
+*/
+fn replace_file(file: &PathBuf, data: String) -> Result<()> {
+    let mut tmp_file = file.clone().into_os_string();
+    tmp_file.push(".tmp");
+    write(&tmp_file, data)?;
+    rename(&tmp_file, file)
+}
+
+impl JsonStorageHandler {
+    fn record_key(&self, record: &Record) -> Vec<TrueType> {
+        self.key_attrs.iter().map(|key_attr| record.get(key_attr).unwrap().clone()).collect()
+    }
+    fn read_db(&self) -> Result<HashMap<ModelName, HashMap<String, Record>>> {
+        let storage_file: &PathBuf = &self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
+        let mut db: HashMap<ModelName, HashMap<String, Record>> = HashMap::new();
+        match read_to_string(storage_file) {
+            Ok(data) => {
+                match from_str(&data) {
+                    Ok(parsed) => db = parsed,
+                    Err(err) => {
+                        if !err.is_eof() {
+                            return Err(Error::new(ErrorKind::InvalidData, "Invalid storage file"));
+                        }
+                    }
+                }
+            },
+            Err(err) => {
+                match err.kind() {
+                    ErrorKind::NotFound => (),
+                    other => return Err(Error::new(other, format!("Unable to read storage file {path}", path=storage_file.display()).as_str()))
+                }
+            }
+        }
+
+        if db.get(&self.model_name).is_none() {
+            db.insert(self.model_name.clone(), HashMap::new());
+        }
+
+        Ok(db.clone())
+    }
+    fn save(&self, db: &HashMap<ModelName, HashMap<String, Record>>) -> Result<()> {
+        let storage_file: &PathBuf = &self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
+        if replace_file(storage_file, to_string(db).unwrap()).is_err() {
+            return Err(Error::new(ErrorKind::PermissionDenied, format!("Unable to write data to storage file {path}", path=storage_file.display()).as_str()));
+        }
+
+        Ok(())
+    }
+    fn meta_file(&self) -> PathBuf {
+        let storage_file: PathBuf = self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
+        let mut meta_file = storage_file.into_os_string();
+        meta_file.push(format!(".{META_FILE_EXTENSION}"));
+        PathBuf::from(meta_file)
+    }
+    fn read_meta(&self) -> Result<JsonStorageMeta> {
+        let meta_file: &PathBuf = &self.meta_file();
+        match read_to_string(meta_file) {
+            Ok(data) => {
+                match from_str(&data) {
+                    Ok(parsed) => Ok(parsed),
+                    Err(err) => {
+                        if err.is_eof() {
+                            Ok(JsonStorageMeta::default())
+                        } else {
+                            Err(Error::new(ErrorKind::InvalidData, "Invalid storage meta file"))
+                        }
+                    }
+                }
+            },
+            Err(err) => {
+                match err.kind() {
+                    ErrorKind::NotFound => Ok(JsonStorageMeta::default()),
+                    other => Err(Error::new(other, format!("Unable to read storage meta file {path}", path=meta_file.display()).as_str()))
+                }
+            }
+        }
+    }
+    fn save_meta(&self, meta: &JsonStorageMeta) -> Result<()> {
+        let meta_file: &PathBuf = &self.meta_file();
+        if replace_file(meta_file, to_string(meta).unwrap()).is_err() {
+            return Err(Error::new(ErrorKind::PermissionDenied, format!("Unable to write data to storage meta file {path}", path=meta_file.display()).as_str()));
+        }
+
+        Ok(())
+    }
+}
+
+impl StorageHandler for JsonStorageHandler {
+    fn create_one(&self, record: &Record) -> Result<Record> {
+        let id_string: String = key_string(&self.record_key(record));
+        let _lock = lock_storage();
+        let mut db = self.read_db()?;
+        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
+        if data.get(&id_string).is_some() {
+            return Err(Error::new(ErrorKind::AlreadyExists, "A record for the given key already exists, try to update it instead (PUT)"));
+        }
+        check_unique(record, data.values(), &self.unique, self.naming.as_ref())?;
+        let mut record: Record = record.clone();
+        // records given with a version, e.g. re-created ones, continue counting from it
+        if self.versioned {
+            record.insert(AttrName(VERSION.to_string()), next_version(Some(&record), None)?);
+        }
+        data.insert(id_string, record.clone());
+        db.insert(self.model_name.clone(), data);
+        self.save(&db)?;
+
+        Ok(record)
+    }
+    fn read_one(&self, key: &[TrueType]) -> Result<Record> {
+        let id_string: &String = &key_string(key);
+        let _lock = lock_storage();
+        let db = self.read_db()?;
+        let data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
+        match data.get(id_string) {
+            Some(record) => Ok(record.clone()),
+            None => Err(Error::new(ErrorKind::NotFound, format!("No record found with id: {id_string}").as_str())),
+        }
+    }
+    fn read_many(&self, filter: &Record) -> Result<Vec<Record>> {
+        let _lock = lock_storage();
+        let db = self.read_db()?;
+        let data: &HashMap<String, Record> = db.get(&self.model_name).unwrap();
+        Ok(
+            data.values()
+                .filter(|record| filter.iter().all(|(attr, value)| record.get(attr) == Some(value)))
+                .cloned()
+                .collect()
+        )
+    }
+    fn update_one(&self, record: &Record) -> Result<Record> {
+        let id_string: String = key_string(&self.record_key(record));
+        let _lock = lock_storage();
+        let mut db = self.read_db()?;
+        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
+        let mut new_record: Record;
+        if let Some(orig_record) = data.get(&id_string) {
+            new_record = orig_record.clone();
+            for (key, value) in record {
+                new_record.insert(key.clone(), value.clone());
+            }
+            // the version of the given record is the expected one
+            if self.versioned {
+                new_record.insert(AttrName(VERSION.to_string()), next_version(Some(orig_record), version_of(record))?);
+            }
+        } else {
+            return Err(Error::new(ErrorKind::NotFound, "No record found for the given key, try to create it instead (POST)"));
+        }
+        check_unique(&new_record, data.iter().filter(|(key, _)| key != &&id_string).map(|(_, other)| other), &self.unique, self.naming.as_ref())?;
+
+        data.insert(id_string, new_record.clone());
+        db.insert(self.model_name.clone(), data);
+        self.save(&db)?;
+
+        Ok(new_record)
+    }
+    fn delete_one(&self, key: &[TrueType], expected_version: Option<i64>) -> Result<Record> {
+        let id_string: String = key_string(key);
+        let _lock = lock_storage();
+        let mut db = self.read_db()?;
+        let mut data: HashMap<String, Record> = db.get(&self.model_name).unwrap().clone();
+        let record: Option<Record> = data.remove(&id_string);
+        if record.is_none() {
+            return Err(Error::new(ErrorKind::NotFound, format!("No record found to remove with id: {id_string}").as_str()));
+        }
+        if self.versioned {
+            next_version(record.as_ref(), expected_version)?;
+        }
+        db.insert(self.model_name.clone(), data);
+        self.save(&db)?;
+
+        Ok(record.unwrap())
+    }
+    fn next_sequence(&self) -> Result<i64> {
+        let _lock = lock_storage();
+        let db = self.read_db()?;
+        let mut meta: JsonStorageMeta = self.read_meta()?;
+
+        // continue after the highest existing key, records may have been created before using a sequence
+        let highest_key: i64 = db.get(&self.model_name).unwrap().keys().filter_map(|key| from_str::<i64>(key).ok()).max().unwrap_or(0);
+        let next: i64 = meta.sequences.get(&self.model_name).copied().unwrap_or(0).max(highest_key) + 1;
+        meta.sequences.insert(self.model_name.clone(), next);
+        self.save_meta(&meta)?;
+
+        Ok(next)
+    }
+    fn read_definition(&self) -> Result<Option<ModelDefinition>> {
+        let _lock = lock_storage();
+        Ok(self.read_meta()?.definitions.remove(&self.model_name))
+    }
+    fn migrate(&self, definition: &ModelDefinition, records: Option<&[Record]>) -> Result<()> {
+        let _lock = lock_storage();
+        if let Some(records) = records {
+            let mut db = self.read_db()?;
+            // the keys are built again, the primary key may have changed
+            let mut data: HashMap<String, Record> = HashMap::new();
+            for record in records {
+                let id_string: String = key_string(&self.record_key(record));
+                if data.contains_key(&id_string) {
+                    return Err(Error::new(ErrorKind::AlreadyExists, format!("Multiple records with the same key: {id_string}")));
+                }
+                // migrations name the stored attributes
+                check_unique(record, data.values(), &self.unique, None)?;
+                data.insert(id_string, record.clone());
+            }
+            db.insert(self.model_name.clone(), data);
+            self.save(&db)?;
+        }
+        let mut meta: JsonStorageMeta = self.read_meta()?;
+        meta.definitions.insert(self.model_name.clone(), definition.clone());
+        self.save_meta(&meta)
+    }
+    fn lock_process(&self, exclusive: bool) -> Result<File> {
+        // the storage file itself gets replaced on every write, so a separate file is locked
+        let storage_file: PathBuf = self.config.storage_file.clone().unwrap_or(PathBuf::from(DEFAULT_STORAGE_FILE));
+        let mut lock_file = storage_file.clone().into_os_string();
+        lock_file.push(format!(".{LOCK_FILE_EXTENSION}"));
+        let file: File = OpenOptions::new().create(true).truncate(false).write(true).open(&lock_file)?;
+        let locked = if exclusive { file.try_lock() } else { file.try_lock_shared() };
+        match locked {
+            Ok(()) => Ok(file),
+            Err(TryLockError::WouldBlock) => Err(Error::new(ErrorKind::WouldBlock, format!("The storage file {path} is used by another process", path=storage_file.display()))),
+            Err(TryLockError::Error(err)) => Err(err)
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::server::model::{
+        TruePrimitiveType,
+        PrimitiveType,
+        Attributes,
+        AttrType,
+        AttrName,
+        KeyAttrs,
+        base_model
+    };
+    use super::super::{
+        VersionMismatch,
+        Conflict
+    };
+
+    use std::fs::remove_file;
+    use std::slice::from_ref;
+
+    fn pre_test(file_name: &str) {
+        if PathBuf::from(file_name).as_path().is_file() {
+            assert!(remove_file(file_name).is_ok(), "Storage file {file_name} already existing, unable to remove");
+        }
+    }
+
+    fn post_test(file_name: &str) {
+        if PathBuf::from(file_name).as_path().is_file() {
+            assert!(remove_file(file_name).is_ok(), "Unable to remove storage file {file_name} after test");
+        }
+    }
+
+    #[test]
+    fn test_read_db() {
+        const TEST_STORAGE_FILE: &str = "test_read_db.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+
+        // storage file doesn't exist
+        let mut db: Result<HashMap<ModelName, HashMap<String, Record>>> = handler.read_db();
+        assert!(db.is_ok(), "Unexpected Error when reading not existing storage file");
+        let mut expected: HashMap<ModelName, HashMap<String, Record>> = HashMap::from([
+            (handler.model_name.clone(), HashMap::new())
+        ]);
+        assert_eq!(&db.unwrap(), &expected, "Expected HashMap {map} when reading not existing storage file", map="{<model name>: {}}");
+
+        // storage file empty
+        // create file instead of write because it is not interpreted as existing
+        assert!(write(TEST_STORAGE_FILE, "").is_ok(), "Unable to write storage file for tests");
+        db = handler.read_db();
+        assert!(db.is_ok(), "Unexpected Error after reading from empty storage file");
+        assert_eq!(&db.unwrap(), &expected, "Expected HashMap {map} when reading from empty storage file", map="{<model name>: {}}");
+
+        // storage file with data, movie missing
+        assert!(write(TEST_STORAGE_FILE, "{\"another\": {\"1\": {\"id\": 1}}}").is_ok(), "Unable to write storage file for tests");
+        db = handler.read_db();
+        assert!(db.is_ok(), "Unexpected Error after reading from valid storage file with no respective model data");
+        expected = HashMap::from([
+            (ModelName(AttrName("another".to_string())), HashMap::from([
+                    (
+                        "1".to_string(),
+                        Record::from([(AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1))))])
+                    )
+                ])
+            ),
+            (handler.model_name.clone(), HashMap::new())
+        ]);
+        assert_eq!(&db.unwrap(), &expected, "Gotten HashMap doesn't match the expected when reading from valid storage file with data but no respective model data");
+
+        // storage file not JSON
+        assert!(write(TEST_STORAGE_FILE, "i am not json {\"id\":false}").is_ok(), "Unable to write storage file for tests");
+        db = handler.read_db();
+        assert!(db.is_err(), "Expected Error after reading from invalid storage file");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_create_one() {
+        const TEST_STORAGE_FILE: &str = "test_create_one.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        for key in ["1", "1.5", "\"1\"", "true"] {
+            let record = Record::from([
+                (AttrName("id".to_string()), from_str::<TrueType>(key).unwrap()),
+                (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Natural Born Killers".to_string())))),
+                (AttrName("year".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1994)))),
+                (AttrName("actors".to_string()), TrueType::Array(Some(vec![TruePrimitiveType::String("Woody Harrelson".to_string()), TruePrimitiveType::String("Juliette Lewis".to_string())]))),
+                (AttrName("recommended".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Boolean(true))))
+            ]);
+            assert_eq!(handler.create_one(&record).unwrap(), record, "Creating a valid new record failed");
+            assert!(handler.create_one(&record).is_err(), "Created a new record with already existing id");
+        }
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_read_one() {
+        const TEST_STORAGE_FILE: &str = "test_read_one.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        for key in ["1", "1.5", "\"1\"", "true"] {
+            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":{key} }} }} }}")).is_ok(), "Unable to write storage file for tests");
+            let id: TrueType = from_str(key).unwrap();
+            let record = Record::from([
+                (AttrName("id".to_string()), id.clone())
+            ]);
+            assert_eq!(handler.read_one(&[id]).unwrap(), record, "Reading a valid new record failed");
+        }
+
+        assert!(handler.read_one(&[from_str::<TrueType>("\"not existing\"").unwrap()]).is_err(), "Expected error when reading from a not existing file");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_read_many() {
+        const TEST_STORAGE_FILE: &str = "test_read_many.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        assert!(write(TEST_STORAGE_FILE, r#"{"movie": {"1": {"id": 1, "directorId": 1}, "2": {"id": 2, "directorId": 2}, "3": {"id": 3, "directorId": 1}}}"#).is_ok(), "Unable to write storage file for tests");
+
+        let mut records: Vec<Record> = handler.read_many(&Record::from([(AttrName("directorId".to_string()), from_str::<TrueType>("1").unwrap())])).unwrap();
+        records.sort_by_key(|record| record.get(&AttrName("id".to_string())).unwrap().to_string());
+        assert_eq!(
+            records.iter().map(|record| record.get(&AttrName("id".to_string())).unwrap().to_string()).collect::<Vec<String>>(),
+            vec!("1", "3"),
+            "Expected only the records matching the filter"
+        );
+        assert_eq!(handler.read_many(&Record::new()).unwrap().len(), 3, "Expected all records for an empty filter");
+        assert!(handler.read_many(&Record::from([(AttrName("directorId".to_string()), from_str::<TrueType>("3").unwrap())])).unwrap().is_empty(), "Expected no records for a filter without matches");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_update_one() {
+        const TEST_STORAGE_FILE: &str = "test_update_one.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        for key in ["1", "1.5", "\"1\"", "true"] {
+            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":\"dummy\" }} }} }}")).is_ok(), "Unable to write storage file for tests");
+            let id: TrueType = from_str(key).unwrap();
+            let record = Record::from([
+                (AttrName("id".to_string()), id.clone())
+            ]);
+            assert_eq!(handler.update_one(&record).unwrap(), record, "Updating an existing record failed");
+
+            let record = Record::from([
+                (AttrName("id".to_string()), from_str::<TrueType>("\"not existing\"").unwrap())
+            ]);
+            assert!(handler.update_one(&record).is_err(), "Expected an error when updating a not existing record");
+        }
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_delete_one() {
+        const TEST_STORAGE_FILE: &str = "test_delete_one.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        for key in ["1", "1.5", "\"1\"", "true"] {
+            assert!(write(TEST_STORAGE_FILE, format!("{{\"movie\": {{ {key:?} : {{ \"id\":{key} }} }} }}")).is_ok(), "Unable to write storage file for tests");
+            let id: TrueType = from_str(key).unwrap();
+            let record = Record::from([
+                (AttrName("id".to_string()), id.clone())
+            ]);
+            assert_eq!(handler.delete_one(&[id], None).unwrap(), record, "Deleting a valid new record failed");
+        }
+
+        assert!(handler.delete_one(&[from_str::<TrueType>("\"not existing\"").unwrap()], None).is_err(), "Expected error when deleting from a not existing file");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_composite_key() {
+        const TEST_STORAGE_FILE: &str = "test_composite_key.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("casting".to_string())),
+            key_attrs: vec!(AttrName("movie".to_string()), AttrName("actor".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        let key: Vec<TrueType> = vec!(from_str::<TrueType>("1").unwrap(), from_str::<TrueType>("\"Woody Harrelson\"").unwrap());
+        let record = Record::from([
+            (AttrName("movie".to_string()), key[0].clone()),
+            (AttrName("actor".to_string()), key[1].clone()),
+            (AttrName("role".to_string()), from_str::<TrueType>("\"Mickey Knox\"").unwrap())
+        ]);
+
+        assert_eq!(handler.create_one(&record).unwrap(), record, "Creating a valid new record with composite key failed");
+        assert!(handler.create_one(&record).is_err(), "Created a new record with already existing composite key");
+        assert_eq!(handler.read_db().unwrap().get(&handler.model_name).unwrap().keys().collect::<Vec<&String>>(), vec!("[1,\"Woody Harrelson\"]"), "Expected the composite key to be stored as JSON array");
+        assert_eq!(handler.read_one(&key).unwrap(), record, "Reading a record with composite key failed");
+        assert!(handler.read_one(&[key[1].clone(), key[0].clone()]).is_err(), "Expected error when reading with composite key in wrong order");
+        assert_eq!(handler.delete_one(&key, None).unwrap(), record, "Deleting a record with composite key failed");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_unique() {
+        const TEST_STORAGE_FILE: &str = "test_unique.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("user".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(vec!(AttrName("email".to_string()))),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        let user = |id: i64, email: Option<&str>| Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(id)))),
+            (AttrName("email".to_string()), TrueType::Primitive(email.map(|email| TruePrimitiveType::String(email.to_string()))))
+        ]);
+
+        assert!(handler.create_one(&user(1, Some("mickey@example.com"))).is_ok(), "Creating a record with unique value failed");
+        assert_eq!(
+            handler.create_one(&user(2, Some("mickey@example.com"))).map_err(|err| err.kind()).unwrap_err(),
+            ErrorKind::AlreadyExists,
+            "Expected conflict when creating a record with duplicate unique value"
+        );
+        assert!(
+            handler.create_one(&user(2, Some("mickey@example.com"))).unwrap_err().get_ref().is_some_and(|inner| inner.is::<Conflict>()),
+            "Expected the unique constraint violation to be a Conflict"
+        );
+        assert!(
+            !handler.create_one(&user(1, Some("minnie@example.com"))).unwrap_err().get_ref().is_some_and(|inner| inner.is::<Conflict>()),
+            "Expected an existing key not to be a Conflict"
+        );
+        assert!(handler.create_one(&user(2, None)).is_ok(), "Creating a record with null as unique value failed");
+        assert!(handler.create_one(&user(3, None)).is_ok(), "Expected null values not to violate unique constraints");
+        assert!(handler.update_one(&user(1, Some("mickey@example.com"))).is_ok(), "Expected a record not to conflict with itself when updating");
+        assert_eq!(
+            handler.update_one(&user(2, Some("mickey@example.com"))).map_err(|err| err.kind()).unwrap_err(),
+            ErrorKind::AlreadyExists,
+            "Expected conflict when updating a record to a duplicate unique value"
+        );
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_next_sequence() {
+        const TEST_STORAGE_FILE: &str = "test_next_sequence.json";
+        const TEST_META_FILE: &str = "test_next_sequence.json.meta";
+
+        pre_test(TEST_STORAGE_FILE);
+        pre_test(TEST_META_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+
+        // no storage and meta file
+        assert_eq!(handler.next_sequence().unwrap(), 1, "Expected the sequence to start at 1");
+        assert_eq!(handler.next_sequence().unwrap(), 2, "Expected the sequence to increase monotonically");
+
+        // existing records with higher keys
+        assert!(write(TEST_STORAGE_FILE, "{\"movie\": {\"7\": {\"id\": 7}}}").is_ok(), "Unable to write storage file for tests");
+        assert_eq!(handler.next_sequence().unwrap(), 8, "Expected the sequence to continue after the highest existing key");
+
+        // deleted records don't reset the sequence
+        assert!(handler.delete_one(&[TrueType::Primitive(Some(TruePrimitiveType::Integer(7)))], None).is_ok(), "Unable to delete record for tests");
+        assert_eq!(handler.next_sequence().unwrap(), 9, "Expected the sequence not to reuse keys of deleted records");
+
+        // invalid meta file
+        assert!(write(TEST_META_FILE, "i am not json").is_ok(), "Unable to write storage meta file for tests");
+        assert!(handler.next_sequence().is_err(), "Expected Error for invalid storage meta file");
+
+        post_test(TEST_STORAGE_FILE);
+        post_test(TEST_META_FILE);
+    }
+
+    #[test]
+    fn test_versions() {
+        const TEST_STORAGE_FILE: &str = "test_versions.json";
+
+        pre_test(TEST_STORAGE_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: true,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        let id = TrueType::Primitive(Some(TruePrimitiveType::Integer(1)));
+        let movie = |version: Option<i64>| {
+            let mut record = Record::from([(AttrName("id".to_string()), id.clone())]);
+            if let Some(version) = version {
+                record.insert(AttrName(VERSION.to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(version))));
+            }
+            record
+        };
+
+        assert_eq!(version_of(&handler.create_one(&movie(None)).unwrap()), Some(1), "Expected new records to start with version 1");
+        assert_eq!(version_of(&handler.update_one(&movie(None)).unwrap()), Some(2), "Expected updates without expected version to increase the version");
+        assert_eq!(version_of(&handler.update_one(&movie(Some(2))).unwrap()), Some(3), "Expected updates with matching version to increase the version");
+
+        let mismatch: Error = handler.update_one(&movie(Some(2))).unwrap_err();
+        assert!(mismatch.get_ref().is_some_and(|err| err.is::<VersionMismatch>()), "Expected version mismatch when updating an outdated version");
+        let mismatch: Error = handler.delete_one(from_ref(&id), Some(2)).unwrap_err();
+        assert!(mismatch.get_ref().is_some_and(|err| err.is::<VersionMismatch>()), "Expected version mismatch when deleting an outdated version");
+        assert_eq!(version_of(&handler.read_one(from_ref(&id)).unwrap()), Some(3), "Expected failed writes not to change the record");
+
+        assert!(handler.delete_one(from_ref(&id), Some(3)).is_ok(), "Deleting the current version failed");
+        assert_eq!(version_of(&handler.create_one(&movie(Some(3))).unwrap()), Some(4), "Expected re-created records to continue counting from the given version");
+
+        post_test(TEST_STORAGE_FILE);
+    }
+
+    #[test]
+    fn test_migrate() {
+        const TEST_STORAGE_FILE: &str = "test_migrate.json";
+        const TEST_META_FILE: &str = "test_migrate.json.meta";
+
+        pre_test(TEST_STORAGE_FILE);
+        pre_test(TEST_META_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("name".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+        let definition = ModelDefinition {
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            primary_key: KeyAttrs::Single(AttrName("name".to_string())),
+            required: vec!(AttrName("name".to_string())),
+            ..base_model()
+        };
+        let alien = Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))),
+            (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string()))))
+        ]);
+        let heat = Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(2)))),
+            (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Heat".to_string()))))
+        ]);
+        let other_alien = Record::from([
+            (AttrName("id".to_string()), TrueType::Primitive(Some(TruePrimitiveType::Integer(2)))),
+            (AttrName("name".to_string()), TrueType::Primitive(Some(TruePrimitiveType::String("Alien".to_string()))))
+        ]);
+
+        assert_eq!(handler.read_definition().unwrap(), None, "Expected no applied definition without meta file");
+        assert!(write(TEST_STORAGE_FILE, "{\"movie\": {\"1\": {\"id\": 1, \"name\": \"Alien\"}}}").is_ok(), "Unable to write storage file for tests");
+
+        // only the definition
+        assert!(handler.migrate(&definition, None).is_ok(), "Unable to memorize the definition");
+        assert_eq!(handler.read_definition().unwrap().as_ref(), Some(&definition), "Expected the memorized definition");
+        assert!(handler.read_one(&[TrueType::Primitive(Some(TruePrimitiveType::Integer(1)))]).is_ok(), "Expected the records to be unchanged");
+
+        // records get new keys
+        assert!(handler.migrate(&definition, Some(&[alien.clone(), heat.clone()])).is_ok(), "Unable to replace the records");
+        assert_eq!(handler.read_one(&[TrueType::Primitive(Some(TruePrimitiveType::String("Heat".to_string())))]).unwrap(), heat, "Expected the records to be stored by their new key");
+        assert_eq!(handler.read_many(&Record::new()).unwrap().len(), 2, "Expected the records to be replaced");
+
+        assert!(handler.migrate(&definition, Some(&[alien, other_alien])).is_err(), "Expected Error for records with the same key");
+        assert_eq!(handler.read_many(&Record::new()).unwrap().len(), 2, "Expected failed migrations not to change the records");
+
+        post_test(TEST_STORAGE_FILE);
+        post_test(TEST_META_FILE);
+    }
+
+    #[test]
+    fn test_lock_process() {
+        const TEST_STORAGE_FILE: &str = "test_lock_process.json";
+        const TEST_LOCK_FILE: &str = "test_lock_process.json.lock";
+
+        pre_test(TEST_LOCK_FILE);
+        let handler = JsonStorageHandler {
+            model_name: ModelName(AttrName("movie".to_string())),
+            key_attrs: vec!(AttrName("id".to_string())),
+            unique: vec!(),
+            versioned: false,
+            naming: None,
+            config: JsonStorageConfig {
+                storage_file: Some(PathBuf::from(TEST_STORAGE_FILE))
+            }
+        };
+
+        // running servers share the lock, migrations need it exclusively
+        let server_lock: File = handler.lock_process(false).unwrap();
+        assert!(handler.lock_process(false).is_ok(), "Expected shared locks not to block each other");
+        assert_eq!(handler.lock_process(true).map_err(|err| err.kind()).err(), Some(ErrorKind::WouldBlock), "Expected running servers to block migrations");
+        drop(server_lock);
+
+        let migration_lock: File = handler.lock_process(true).unwrap();
+        assert!(handler.lock_process(false).is_err(), "Expected migrations to block servers");
+        drop(migration_lock);
+        assert!(handler.lock_process(true).is_ok(), "Expected the lock to be released when it's dropped");
+
+        post_test(TEST_LOCK_FILE);
+    }
+}
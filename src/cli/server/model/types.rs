@@ -1,362 +1,2502 @@
-// used modules
-use serde::de;
-use std::fmt;
-
-// used traits
-use super::StorageType;
-use serde::Deserializer;
-use std::convert::TryFrom;
-use serde_json::Value;
-use std::fmt::Display;
-use serde_derive::{
-    Deserialize,
-    Serialize
-};
-
-// used types
-use std::collections::HashMap;
-use regex::Regex;
-use std::io::{
-    ErrorKind,
-    Result,
-    Error
-};
-
-// used functions
-use cruet::case::pascal::to_pascal_case as pascalize;
-use cruet::case::camel::to_camel_case as camelize;
-pub use serde_json::from_str as parse;
-use cruet::string::{
-    singularize::to_singular as singularize,
-    pluralize::to_plural as pluralize
-};
-
-pub const NULL: TrueType = TrueType::Primitive(None);
-pub type Record = HashMap<AttrName, TrueType>;
-pub type Attributes = HashMap<AttrName, AttrType>;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-#[serde(untagged)]
-pub enum AttrType {
-    Primitive(PrimitiveType),
-    Array([PrimitiveType; 1])
-}
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub enum PrimitiveType {
-    Integer,
-    String,
-    Boolean,
-    // Float
-}
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-#[serde(untagged)]
-pub enum TrueType {
-    Primitive(Option<TruePrimitiveType>),
-    Array(Option<Vec<TruePrimitiveType>>)
-}
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-#[serde(untagged)]
-pub enum TruePrimitiveType {
-    Integer(i64),
-    String(String),
-    Boolean(bool),
-    // Float(f64),
-}
-
-impl Display for TrueType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TrueType::Array(Some(arr)) => write!(f, "{array:?}", array=arr.iter().map(|p| TrueType::Primitive(Some(p.clone())).to_string()).collect::<Vec<String>>()),
-            TrueType::Primitive(Some(prim)) => match prim {
-                TruePrimitiveType::String(string) => write!(f, "{string}"),
-                TruePrimitiveType::Integer(val) => write!(f, "{val}"),
-                TruePrimitiveType::Boolean(val) => write!(f, "{val}"),
-            }
-            _ => write!(f, "null")
-        }
-    }
-}
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub enum Constraints {
-    Integer(IntConstraint),
-    String(StrConstraint),
-    Boolean(BoolConstraint),
-    Float(FloatConstraint)
-}
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct IntConstraint;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct StrConstraint;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct BoolConstraint;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct FloatConstraint;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct ArrayConstraint;
-
-#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
-pub struct ModelDefinition {
-    pub model_name: ModelName,
-    pub storage_type: StorageType,
-    pub attributes: Attributes,
-    pub primary_key: AttrName,
-    pub required: Vec<AttrName>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub constraints: Option<HashMap<AttrName, Constraints>>
-}
-
-impl TryFrom<&str> for ModelDefinition {
-    type Error = Error;
-
-    fn try_from(json: &str) -> core::result::Result<Self, Self::Error> {
-        if let Ok(model) = parse::<ModelDefinition>(json) {
-            validate_model_definition(&model)?;
-            Ok(model)
-        } else {
-            Err(Error::new(ErrorKind::InvalidData, "no valid JSON"))
-        }
-    }
-}
-
-
-/*
-    validate_model_definition: 
-        Validates a given model definition if it meets all important conditions.
-
-        What happens exactly:
-            1. validate the primary key,
-               therefore check if it is defined in the attributes,
-               also check if its type is not Array, since this is not allowed
-            2. validate the as required defined attributes,
-               therefore check if the primary key is required,
-               also check if all declared required attributes are actually defined in th attributes
-
-    returns:
-        Empty tuple if the model is valid, else Error
-*/
-pub fn validate_model_definition(definition: &ModelDefinition) -> Result<()> {
-    // validate model name inflection
-    if definition.model_name.singular() == definition.model_name.plural() {
-        return Err(Error::new(ErrorKind::InvalidData, "Name has no plural variant"));
-    }
-
-    // validate primary key
-    if let Some(ty) = definition.attributes.get(&definition.primary_key) {
-        if let AttrType::Array(_) = ty {
-            return Err(Error::new(ErrorKind::InvalidData, "invalid primary key"));
-        }
-    }
-    else {
-        return Err(Error::new(ErrorKind::InvalidData, "invalid primary key"));
-    }
-
-    // validate required attributes
-    if !definition.required.contains(&definition.primary_key) {
-        return Err(Error::new(ErrorKind::InvalidData, "primary key must be required"));
-    }
-    for attr in &definition.required {
-        if !definition.attributes.contains_key(attr) {
-            return Err(Error::new(ErrorKind::InvalidData, format!("invalid required attribute {attr:?}", attr=attr.0)));
-        }
-    }
-
-    Ok(())
-}
-
-#[derive(Deserialize, Serialize, Eq, PartialEq, Hash, Clone, Debug)]
-pub struct ModelName(pub AttrName);
-
-impl ModelName {
-    pub fn singular(&self) -> Self {
-        ModelName(AttrName(singularize(&self.0.0)))
-    }
-    pub fn assert_singularity(&self) -> Result<()> {
-        if self != &self.singular() {
-            return Err(Error::new(ErrorKind::InvalidData, "Expected singular model name, got plural variant"));
-        }
-        Ok(())
-    }
-    pub fn plural(&self) -> Self {
-        ModelName(AttrName(pluralize(&self.0.0)))
-    }
-    pub fn assert_plurality(&self) -> Result<()> {
-        if self != &self.plural() {
-            return Err(Error::new(ErrorKind::InvalidData, "Expected plural model name, got singular variant"));
-        }
-        Ok(())
-    }
-    pub fn camel(&self) -> Self {
-        ModelName(AttrName(camelize(&self.0.0)))
-    }
-    pub fn pascal(&self) -> Self {
-        ModelName(AttrName(pascalize(&self.0.0)))
-    }
-}
-
-// define AttrName with custom Deserializer that validates REST-ful Strings
-#[derive(Serialize, Eq, PartialEq, Hash, Clone, Debug)]
-pub struct AttrName(pub String);
-
-impl TryFrom<&str> for AttrName {
-    type Error = Error;
-
-    fn try_from(s: &str) -> core::result::Result<Self, Self::Error> {
-        validate_attr_name(s)?;
-        Ok(AttrName(s.to_string()))
-    }
-}
-
-struct AttrNameVisitor;
-
-impl<'de> de::Visitor<'de> for AttrNameVisitor {
-    type Value = AttrName;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("alphabetic String, snake_case or PascalCase or camelCase or spinal-case")
-    }
-
-    fn visit_str<E>(self, value: &str) -> core::result::Result<AttrName, E>
-    where
-        E: de::Error,
-    {
-        match AttrName::try_from(value) {
-            Ok(name) => Ok(name),
-            Err(err) => Err(de::Error::custom(err.to_string())),
-        }
-    }
-}
-
-impl<'de> de::Deserialize<'de> for AttrName {
-    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer.deserialize_str(AttrNameVisitor)
-    }
-}
-
-fn validate_attr_name(name: &str) -> Result<()> {
-    let regex: Vec<Regex> = vec!(
-        Regex::new(r#"^[A-Z][a-zA-Z]*$"#).unwrap(),  // PascalCase
-        Regex::new(r#"^[a-z][a-zA-Z]*$"#).unwrap(),  // camelCase
-        Regex::new(r#"^[a-z]+(_[a-z]+)*$"#).unwrap(),  // snake_case
-        Regex::new(r#"^[a-z]+(-[a-z]+)*$"#).unwrap()  // spinal-case
-    );
-
-    for r in regex {
-        if r.is_match(name) {
-            return Ok(());
-        }
-    }
-    Err(Error::new(ErrorKind::InvalidData, "Name is not alphabetic in camelCase, PascalCase, snake_case or spinal-case"))
-}
-
-pub fn to_true_prim_type(value: &Value, model_type: &PrimitiveType, is_required: bool) -> Result<Option<TruePrimitiveType>> {
-    if value.as_null().is_some() {
-        if is_required {
-            return Err(Error::new(ErrorKind::InvalidData, "it is required, got: null"));
-        } else {
-            return Ok(None);
-        }
-    }
-    match model_type {
-        PrimitiveType::Integer => {
-            match value.as_i64() {
-                Some(val) => Ok(Some(TruePrimitiveType::Integer(val))),
-                None => Err(Error::new(ErrorKind::InvalidData, "expected: Integer"))
-            }
-        },
-        PrimitiveType::String => {
-            match value.as_str() {
-                Some(val) => Ok(Some(TruePrimitiveType::String(val.to_string()))),
-                None => Err(Error::new(ErrorKind::InvalidData, "expected: String"))
-            }
-        },
-        PrimitiveType::Boolean => {
-            match value.as_bool() {
-                Some(val) => Ok(Some(TruePrimitiveType::Boolean(val))),
-                None => Err(Error::new(ErrorKind::InvalidData, "expected: Boolean"))
-            }
-        },
-        // PrimitiveType::Float => {
-        //     match value.as_f64() {
-        //         Some(val) => Ok(TruePrimitiveType::Float(val)),
-        //         None => Err(Error::new(ErrorKind::InvalidData, "expected: Float"))
-        //     }
-        // },
-    }
-}
-
-
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_validate_model_definition() {
-        // test primary key of type array
-        let model = &ModelDefinition {
-            model_name: ModelName(AttrName("Test".to_string())),
-            storage_type: StorageType::json,
-            primary_key: AttrName("id".to_string()),
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Array([PrimitiveType::String]))
-            ]),
-            required: vec!(AttrName("id".to_string())),
-            constraints: None
-        };
-        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with Array as primary key type");
-
-        // test not existing primary key attribute
-        let model = &ModelDefinition {
-            model_name: ModelName(AttrName("Test".to_string())),
-            storage_type: StorageType::json,
-            primary_key: AttrName("id".to_string()),
-            attributes: Attributes::new(),
-            required: vec!(AttrName("id".to_string())),
-            constraints: None
-        };
-        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with missing primary key attribute in attributes");
-
-        // test not required primary key
-        let model = &ModelDefinition {
-            model_name: ModelName(AttrName("Test".to_string())),
-            storage_type: StorageType::json,
-            primary_key: AttrName("id".to_string()),
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
-            ]),
-            required: vec!(),
-            constraints: None
-        };
-        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with not required primary key");
-
-        // test not existing required attribute
-        let model = &ModelDefinition {
-            model_name: ModelName(AttrName("Test".to_string())),
-            storage_type: StorageType::json,
-            primary_key: AttrName("id".to_string()),
-            attributes: Attributes::from([
-                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
-            ]),
-            required: vec!(
-                AttrName("id".to_string()),
-                AttrName("iDontExist".to_string())
-            ),
-            constraints: None
-        };
-        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with not existing required attributes");
-    }
+// used modules
+use serde::de;
+use std::fmt;
+
+// used traits
+use super::StorageType;
+use serde::Deserializer;
+use std::convert::TryFrom;
+use serde_json::Value;
+use std::fmt::Display;
+use serde_derive::{
+    Deserialize,
+    Serialize
+};
+
+// used types
+use super::rules::Expression;
+use serde_json::Map;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::str::FromStr;
+use regex::Regex;
+use uuid::Uuid;
+use chrono::{
+    SecondsFormat,
+    NaiveDate,
+    NaiveTime,
+    DateTime,
+    Utc
+};
+use std::io::{
+    ErrorKind,
+    Result,
+    Error
+};
+
+// used functions
+use cruet::case::pascal::to_pascal_case as pascalize;
+use cruet::case::camel::to_camel_case as camelize;
+use cruet::case::snake::to_snake_case as snakeize;
+pub use serde_json::from_str as parse;
+use serde_json::to_string;
+use cruet::string::{
+    singularize::to_singular as singularize,
+    pluralize::to_plural as pluralize
+};
+
+pub const NULL: TrueType = TrueType::Primitive(None);
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const TIME_FORMAT: &str = "%H:%M:%S%.f";
+pub type Record = HashMap<AttrName, TrueType>;
+pub type Attributes = HashMap<AttrName, AttrType>;
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+pub enum AttrType {
+    Primitive(PrimitiveType),
+    Array([PrimitiveType; 1]),
+    Enum(EnumDefinition),
+    Object(ObjectDefinition)
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct EnumDefinition {
+    #[serde(rename = "Enum")]
+    pub values: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptions: Option<HashMap<String, String>>
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct ObjectDefinition {
+    #[serde(rename = "Object")]
+    pub object: ObjectAttributes
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct ObjectAttributes {
+    pub attributes: Attributes,
+
+    #[serde(default)]
+    pub required: Vec<AttrName>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptions: Option<HashMap<AttrName, String>>
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub enum PrimitiveType {
+    Integer,
+    String,
+    Boolean,
+    Float,
+    Date,
+    DateTime,
+    Time
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+pub enum TrueType {
+    Primitive(Option<TruePrimitiveType>),
+    Array(Option<Vec<TruePrimitiveType>>),
+    Object(Option<Record>)
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+pub enum TruePrimitiveType {
+    Integer(i64),
+    String(String),
+    Boolean(bool),
+    Float(f64)
+}
+
+impl Display for TrueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrueType::Array(Some(arr)) => write!(f, "{array:?}", array=arr.iter().map(|p| TrueType::Primitive(Some(p.clone())).to_string()).collect::<Vec<String>>()),
+            TrueType::Primitive(Some(prim)) => match prim {
+                TruePrimitiveType::String(string) => write!(f, "{string}"),
+                TruePrimitiveType::Integer(val) => write!(f, "{val}"),
+                TruePrimitiveType::Boolean(val) => write!(f, "{val}"),
+                TruePrimitiveType::Float(val) => write!(f, "{val}"),
+            }
+            TrueType::Object(Some(record)) => write!(f, "{object}", object=to_string(record).unwrap()),
+            _ => write!(f, "null")
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub enum Constraints {
+    Integer(IntConstraint),
+    String(StrConstraint),
+    Boolean(BoolConstraint),
+    Float(FloatConstraint),
+    Array(ArrayConstraint)
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IntConstraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<i64>
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StrConstraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Pattern>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<StrFormat>
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+// if adding formats, add them in the create-model dialogue in model_cli.rs too
+pub enum StrFormat {
+    email,
+    uuid,
+    uri
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct BoolConstraint;
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+pub struct FloatConstraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrayConstraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>
+}
+
+impl StrFormat {
+    // the expressions are compiled once and reused for every check
+    fn regex(&self) -> &'static Regex {
+        static EMAIL: OnceLock<Regex> = OnceLock::new();
+        static UUID: OnceLock<Regex> = OnceLock::new();
+        static URI: OnceLock<Regex> = OnceLock::new();
+        match self {
+            StrFormat::email => EMAIL.get_or_init(|| Regex::new(r#"^[^@\s]+@[^@\s]+\.[^@\s]+$"#).unwrap()),
+            StrFormat::uuid => UUID.get_or_init(|| Regex::new(r#"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"#).unwrap()),
+            StrFormat::uri => URI.get_or_init(|| Regex::new(r#"^[a-zA-Z][a-zA-Z0-9+.-]*:[^\s]+$"#).unwrap())
+        }
+    }
+}
+
+// a regular expression of a pattern constraint, it's compiled once when the model definition is parsed
+#[derive(Clone, Debug)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{pattern}", pattern=self.0.as_str())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match Regex::new(s) {
+            Ok(regex) => Ok(Pattern(regex)),
+            Err(_) => Err(Error::new(ErrorKind::InvalidData, "pattern is no valid regular expression"))
+        }
+    }
+}
+
+impl serde::Serialize for Pattern {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Pattern {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern: String = de::Deserialize::deserialize(deserializer)?;
+        Pattern::from_str(&pattern).map_err(de::Error::custom)
+    }
+}
+
+impl Constraints {
+    /*
+        validate: 
+            Validates the constraint definition for an attribute of the given type,
+            so it has to fit the type and its bounds have to be satisfiable.
+
+        returns:
+            Empty tuple if the constraint is valid, else Error
+    */
+    pub fn validate(&self, attr_type: &AttrType) -> Result<()> {
+        let invalid = |message: &str| Err(Error::new(ErrorKind::InvalidData, message.to_string()));
+        match (self, attr_type) {
+            (Constraints::Integer(c), AttrType::Primitive(PrimitiveType::Integer)) => {
+                if let (Some(min), Some(max)) = (c.min, c.max) {
+                    if min > max {
+                        return invalid("min is greater than max");
+                    }
+                }
+                if let Some(multiple_of) = c.multiple_of {
+                    if multiple_of <= 0 {
+                        return invalid("multipleOf has to be positive");
+                    }
+                }
+            },
+            (Constraints::String(c), AttrType::Primitive(PrimitiveType::String)) => {
+                if let (Some(min), Some(max)) = (c.min_length, c.max_length) {
+                    if min > max {
+                        return invalid("minLength is greater than maxLength");
+                    }
+                }
+            },
+            (Constraints::Float(c), AttrType::Primitive(PrimitiveType::Float)) => {
+                if let (Some(min), Some(max)) = (c.min, c.max) {
+                    if min > max {
+                        return invalid("min is greater than max");
+                    }
+                }
+            },
+            (Constraints::Array(c), AttrType::Array(_)) => {
+                if let (Some(min), Some(max)) = (c.min_items, c.max_items) {
+                    if min > max {
+                        return invalid("minItems is greater than maxItems");
+                    }
+                }
+            },
+            (Constraints::Boolean(_), AttrType::Primitive(PrimitiveType::Boolean)) => (),
+            _ => return invalid("it doesn't fit to the attribute's type")
+        }
+        Ok(())
+    }
+
+    /*
+        check: 
+            Checks if the given value satisfies the constraint, null values always do.
+
+        returns:
+            Empty tuple if the value satisfies the constraint, else Error describing the violation
+    */
+    pub fn check(&self, value: &TrueType) -> Result<()> {
+        let violation = |message: String| Err(Error::new(ErrorKind::InvalidData, message));
+        match (self, value) {
+            (Constraints::Integer(c), TrueType::Primitive(Some(TruePrimitiveType::Integer(val)))) => {
+                if let Some(min) = c.min {
+                    if val < &min {
+                        return violation(format!("value must be at least {min}"));
+                    }
+                }
+                if let Some(max) = c.max {
+                    if val > &max {
+                        return violation(format!("value must be at most {max}"));
+                    }
+                }
+                if let Some(multiple_of) = c.multiple_of {
+                    if val % multiple_of != 0 {
+                        return violation(format!("value must be a multiple of {multiple_of}"));
+                    }
+                }
+            },
+            (Constraints::Float(c), TrueType::Primitive(Some(TruePrimitiveType::Float(val)))) => {
+                if let Some(min) = c.min {
+                    if val < &min {
+                        return violation(format!("value must be at least {min}"));
+                    }
+                }
+                if let Some(max) = c.max {
+                    if val > &max {
+                        return violation(format!("value must be at most {max}"));
+                    }
+                }
+            },
+            (Constraints::String(c), TrueType::Primitive(Some(TruePrimitiveType::String(val)))) => {
+                let length: usize = val.chars().count();
+                if let Some(min_length) = c.min_length {
+                    if length < min_length {
+                        return violation(format!("length must be at least {min_length}"));
+                    }
+                }
+                if let Some(max_length) = c.max_length {
+                    if length > max_length {
+                        return violation(format!("length must be at most {max_length}"));
+                    }
+                }
+                if let Some(pattern) = &c.pattern {
+                    if !pattern.is_match(val) {
+                        return violation(format!("value must match the pattern {pattern:?}", pattern=pattern.to_string()));
+                    }
+                }
+                if let Some(format) = &c.format {
+                    if !format.regex().is_match(val) {
+                        return violation(format!("value must be a valid {format:?}"));
+                    }
+                }
+            },
+            (Constraints::Array(c), TrueType::Array(Some(arr))) => {
+                if let Some(min_items) = c.min_items {
+                    if arr.len() < min_items {
+                        return violation(format!("array must contain at least {min_items} items"));
+                    }
+                }
+                if let Some(max_items) = c.max_items {
+                    if arr.len() > max_items {
+                        return violation(format!("array must contain at most {max_items} items"));
+                    }
+                }
+                if c.unique_items == Some(true) {
+                    for (index, item) in arr.iter().enumerate() {
+                        if arr[..index].contains(item) {
+                            return violation(format!("array items must be unique, got {item} multiple times", item=TrueType::Primitive(Some(item.clone()))));
+                        }
+                    }
+                }
+            },
+            _ => ()  // null values or values for other constraints, which can't occur because of the validated model definition
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct ModelDefinition {
+    pub model_name: ModelName,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    pub storage_type: StorageType,
+    pub attributes: Attributes,
+    pub primary_key: KeyAttrs,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key_generator: Option<KeyGenerator>,
+
+    pub required: Vec<AttrName>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<Vec<AttrName>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashed: Option<Vec<AttrName>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<Vec<KeyAttrs>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<HashMap<AttrName, Constraints>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<HashMap<String, String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<HashMap<AttrName, DefaultValue>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptions: Option<HashMap<AttrName, String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub associations: Option<HashMap<AttrName, Association>>,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub timestamps: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub soft_delete: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub versioned: bool,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub history: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<Ttl>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub naming: Option<Naming>,
+
+    // the base models and attribute sets a definition is resolved from, only kept to validate it
+    #[serde(skip)]
+    pub resolved_from: Vec<Origin>
+}
+
+// a base model or attribute set with the attributes it defines
+#[derive(PartialEq, Clone, Debug)]
+pub enum Origin {
+    BaseModel(ModelName, Attributes),
+    AttributeSet(String, Attributes)
+}
+
+// the model definition the tests start from, they only set the fields they exercise
+#[cfg(test)]
+pub fn base_model() -> ModelDefinition {
+    ModelDefinition {
+        model_name: ModelName(AttrName("movie".to_string())),
+        description: None,
+        storage_type: StorageType::json,
+        attributes: Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))]),
+        primary_key: KeyAttrs::Single(AttrName("id".to_string())),
+        primary_key_generator: None,
+        required: vec!(AttrName("id".to_string())),
+        write_only: None,
+        hashed: None,
+        unique: None,
+        constraints: None,
+        rules: None,
+        defaults: None,
+        descriptions: None,
+        associations: None,
+        timestamps: false,
+        soft_delete: false,
+        versioned: false,
+        history: false,
+        ttl: None,
+        naming: None,
+        resolved_from: vec!()
+    }
+}
+
+// names of the read-only DateTime attributes managed by the server if timestamps are enabled
+pub const CREATED_AT: &str = "createdAt";
+pub const UPDATED_AT: &str = "updatedAt";
+
+// name of the read-only DateTime attribute marking soft deleted records, null if the record isn't deleted
+pub const DELETED_AT: &str = "deletedAt";
+
+// name of the read-only Integer attribute increased by the storage on every write of versioned records
+pub const VERSION: &str = "version";
+
+// name of the REST segment and GraphQL field listing the changes of a record if history is enabled
+pub const HISTORY: &str = "history";
+
+// name of the read-only DateTime attribute holding the expiry of records with a fixed time to live
+pub const EXPIRES_AT: &str = "expiresAt";
+
+impl ModelDefinition {
+    pub fn timestamp_attrs(&self) -> Vec<AttrName> {
+        if self.timestamps {
+            vec!(AttrName(CREATED_AT.to_string()), AttrName(UPDATED_AT.to_string()))
+        } else {
+            vec!()
+        }
+    }
+    // all attributes managed by the server, which can't be set by clients
+    pub fn managed_attrs(&self) -> Vec<AttrName> {
+        let mut attrs: Vec<AttrName> = self.timestamp_attrs();
+        if self.soft_delete {
+            attrs.push(AttrName(DELETED_AT.to_string()));
+        }
+        if self.versioned {
+            attrs.push(AttrName(VERSION.to_string()));
+        }
+        if let Some(Ttl::Seconds(_)) = self.ttl {
+            attrs.push(AttrName(EXPIRES_AT.to_string()));
+        }
+        attrs
+    }
+
+    // the name of an attribute in REST and GraphQL, which differs from the stored one if the model has a naming policy
+    pub fn exposed_name(&self, attr: &AttrName) -> AttrName {
+        attr.named(self.naming.as_ref())
+    }
+
+    // the DateTime attribute after which records expire, null values never expire
+    pub fn expiry_attr(&self) -> Option<AttrName> {
+        match &self.ttl {
+            Some(Ttl::Seconds(_)) => Some(AttrName(EXPIRES_AT.to_string())),
+            Some(Ttl::Attribute(attr)) => Some(attr.clone()),
+            None => None
+        }
+    }
+
+    // attributes which are accepted when writing records but never returned, hashed attributes are write-only too
+    pub fn secret_attrs(&self) -> Vec<AttrName> {
+        self.write_only.iter().chain(self.hashed.iter()).flatten().cloned().collect()
+    }
+
+    /*
+        history_model: 
+            Defines the model storing the history of this model's records,
+            every entry holds the key of the changed record as Strings, the operation, the time of the change,
+            the record before and after the change, ordered by a generated sequence.
+
+        returns:
+            the history model, named like this model followed by History
+    */
+    pub fn history_model(&self) -> ModelDefinition {
+        let record_type: AttrType = AttrType::Object(ObjectDefinition {
+            object: ObjectAttributes {
+                attributes: self.attributes.clone(),
+                required: vec!(),
+                descriptions: None
+            }
+        });
+        ModelDefinition {
+            model_name: ModelName(AttrName(format!("{model}History", model=self.model_name.singular().camel().0.0))),
+            storage_type: self.storage_type.clone(),
+            attributes: Attributes::from([
+                (AttrName("sequence".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("key".to_string()), AttrType::Array([PrimitiveType::String])),
+                (AttrName("operation".to_string()), AttrType::Enum(EnumDefinition {
+                    values: vec!("create".to_string(), "update".to_string(), "delete".to_string()),
+                    descriptions: None
+                })),
+                (AttrName("at".to_string()), AttrType::Primitive(PrimitiveType::DateTime)),
+                (AttrName("previous".to_string()), record_type.clone()),
+                (AttrName("record".to_string()), record_type)
+            ]),
+            primary_key: KeyAttrs::Single(AttrName("sequence".to_string())),
+            primary_key_generator: Some(KeyGenerator::increment),
+            required: ["sequence", "key", "operation", "at"].iter().map(|attr| AttrName(attr.to_string())).collect(),
+            description: None,
+            write_only: None,
+            hashed: None,
+            unique: None,
+            constraints: None,
+            rules: None,
+            defaults: None,
+            descriptions: None,
+            associations: None,
+            timestamps: false,
+            soft_delete: false,
+            versioned: false,
+            history: false,
+            ttl: None,
+            naming: None,
+            resolved_from: vec!()
+        }
+    }
+}
+
+/*
+    Association: 
+        A reference to records of another model via a foreign key.
+        belongsTo: the foreign key is an attribute of this model, referencing the other model's primary key
+        hasMany: the foreign key is an attribute of the other model, referencing this model's primary key
+        manyToMany: the records are linked via a join model, whose foreign key references this model's primary key
+                    and whose other key references the other model's primary key,
+                    the join model is either given explicitly via through or implicitly shared by both models
+        on_delete: what happens to the records of a belongsTo association when the referenced record gets deleted
+*/
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct Association {
+    pub kind: AssociationKind,
+    pub model: ModelName,
+    pub foreign_key: AttrName,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_key: Option<AttrName>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub through: Option<ModelName>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<OnDelete>
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum AssociationKind {
+    BelongsTo,
+    HasMany,
+    ManyToMany
+}
+
+/*
+    OnDelete: 
+        The referential action applied to the referencing records when a referenced record gets deleted.
+        cascade: the referencing records get deleted too
+        restrict: the deletion is refused as long as there are referencing records
+        setNull: the foreign keys of the referencing records get cleared
+*/
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum OnDelete {
+    Cascade,
+    Restrict,
+    SetNull
+}
+
+impl Association {
+    /*
+        implicit_join_model: 
+            Builds the join model of a manyToMany association without explicit join model,
+            its name and the order of its key are independent of the side the association is defined on,
+            so both models share the same links.
+            Both models need single primary keys.
+
+        returns:
+            the model definition of the join model
+    */
+    pub fn implicit_join_model(&self, model: &ModelDefinition, other: &ModelDefinition) -> ModelDefinition {
+        // manyToMany associations always have an other key, which is ensured by the validation
+        let other_key: AttrName = self.other_key.clone().unwrap();
+        let mut sides: Vec<(ModelName, &AttrName, &ModelDefinition)> = vec!(
+            (model.model_name.singular().camel(), &self.foreign_key, model),
+            (other.model_name.singular().camel(), &other_key, other)
+        );
+        sides.sort_by(|(a, ..), (b, ..)| a.0.0.cmp(&b.0.0));
+
+        ModelDefinition {
+            model_name: ModelName(AttrName(format!("{first}{second}", first=sides[0].0.0.0, second=sides[1].0.pascal().0.0))),
+            storage_type: model.storage_type.clone(),
+            attributes: sides.iter().map(|(_, key, side)| ((*key).clone(), side.attributes.get(&side.primary_key.attrs()[0]).unwrap().clone())).collect(),
+            primary_key: KeyAttrs::Composite(sides.iter().map(|(_, key, _)| (*key).clone()).collect()),
+            required: sides.iter().map(|(_, key, _)| (*key).clone()).collect(),
+            description: None,
+            primary_key_generator: None,
+            write_only: None,
+            hashed: None,
+            unique: None,
+            constraints: None,
+            rules: None,
+            defaults: None,
+            descriptions: None,
+            associations: None,
+            timestamps: false,
+            soft_delete: false,
+            versioned: false,
+            history: false,
+            ttl: None,
+            naming: None,
+            resolved_from: vec!()
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+pub enum DefaultValue {
+    Generated {
+        generator: Generator
+    },
+    Literal(Value)
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub enum Generator {
+    now,
+    uuid
+}
+
+impl Generator {
+    /*
+        generate: 
+            Generates a new value for an attribute of the given type.
+
+        returns:
+            The generated value as JSON value,
+            the type is expected to be validated before via fits()
+    */
+    pub fn generate(&self, attr_type: &AttrType) -> Value {
+        match (self, attr_type) {
+            (Generator::now, AttrType::Primitive(PrimitiveType::Date)) => Value::String(Utc::now().format(DATE_FORMAT).to_string()),
+            (Generator::now, AttrType::Primitive(PrimitiveType::Time)) => Value::String(Utc::now().format(TIME_FORMAT).to_string()),
+            (Generator::now, _) => Value::String(Utc::now().to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            (Generator::uuid, _) => Value::String(Uuid::new_v4().to_string())
+        }
+    }
+    pub fn fits(&self, attr_type: &AttrType) -> bool {
+        match self {
+            Generator::now => matches!(attr_type, AttrType::Primitive(PrimitiveType::Date | PrimitiveType::DateTime | PrimitiveType::Time)),
+            Generator::uuid => matches!(attr_type, AttrType::Primitive(PrimitiveType::String))
+        }
+    }
+}
+
+// the time to live of records, either a fixed duration after their creation or the time given by one of their attributes
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Ttl {
+    Seconds(u64),
+    Attribute(AttrName)
+}
+
+// the casing of attribute names in REST and GraphQL, the attributes are stored with the names of the model definition
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Naming {
+    CamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase
+}
+
+impl Naming {
+    pub fn apply(&self, attr: &AttrName) -> AttrName {
+        match self {
+            Naming::CamelCase => AttrName(camelize(&attr.0)),
+            Naming::SnakeCase => AttrName(snakeize(&attr.0))
+        }
+    }
+}
+
+// a single attribute name or a list of them, used for keys consisting of multiple attributes
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+pub enum KeyAttrs {
+    Single(AttrName),
+    Composite(Vec<AttrName>)
+}
+
+impl KeyAttrs {
+    pub fn attrs(&self) -> Vec<AttrName> {
+        match self {
+            KeyAttrs::Single(attr) => vec!(attr.clone()),
+            KeyAttrs::Composite(attrs) => attrs.clone()
+        }
+    }
+    pub fn contains(&self, attr: &AttrName) -> bool {
+        match self {
+            KeyAttrs::Single(key_attr) => key_attr == attr,
+            KeyAttrs::Composite(key_attrs) => key_attrs.contains(attr)
+        }
+    }
+}
+
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub enum KeyGenerator {
+    increment,
+    uuid,
+    ulid
+}
+
+impl KeyGenerator {
+    pub fn fits(&self, attr_type: &AttrType) -> bool {
+        match self {
+            KeyGenerator::increment => matches!(attr_type, AttrType::Primitive(PrimitiveType::Integer)),
+            KeyGenerator::uuid | KeyGenerator::ulid => matches!(attr_type, AttrType::Primitive(PrimitiveType::String))
+        }
+    }
+}
+
+impl TryFrom<&str> for ModelDefinition {
+    type Error = Error;
+
+    fn try_from(json: &str) -> core::result::Result<Self, Self::Error> {
+        ModelDefinition::parse(json, ModelFormat::json)
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+// if adding formats, add them in the create-model dialogue in model_cli.rs too
+pub enum ModelFormat {
+    json,
+    yaml,
+    toml
+}
+
+impl ModelFormat {
+    // the format is chosen by the file extension, files with other extensions are read as JSON
+    pub fn from_path(path: &Path) -> ModelFormat {
+        ModelFormat::from_extension(path).unwrap_or(ModelFormat::json)
+    }
+
+    // parses data of this format to a generic value, errors contain their position
+    pub fn parse_value(self, data: &str) -> std::result::Result<Value, String> {
+        match self {
+            ModelFormat::json => parse::<Value>(data).map_err(|err| err.to_string()),
+            ModelFormat::yaml => serde_yaml_ng::from_str::<Value>(data).map_err(|err| err.to_string()),
+            ModelFormat::toml => toml::from_str::<Value>(data).map_err(|err| err.to_string().trim_end().to_string())
+        }
+    }
+
+    // None if the file extension isn't one of a model file
+    pub fn from_extension(path: &Path) -> Option<ModelFormat> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Some(ModelFormat::json),
+            Some("yaml" | "yml") => Some(ModelFormat::yaml),
+            Some("toml") => Some(ModelFormat::toml),
+            _ => None
+        }
+    }
+}
+
+impl ModelDefinition {
+    /*
+        parse: 
+            Parses a model definition in the given format and validates it,
+            so every format results in the same definitions.
+
+        returns:
+            the valid model definition or an Error if the data isn't a valid definition
+    */
+    pub fn parse(data: &str, format: ModelFormat) -> Result<ModelDefinition> {
+        // keep the parser's message, it contains the position of the error
+        let model: std::result::Result<ModelDefinition, String> = match format {
+            ModelFormat::json => parse::<ModelDefinition>(data).map_err(|err| err.to_string()),
+            // YAML is read like JSON, so enums are written as maps instead of YAML tags
+            ModelFormat::yaml => serde_yaml_ng::from_str::<serde_json::Value>(data).map_err(|err| err.to_string())
+                .and_then(|value| serde_json::from_value::<ModelDefinition>(value).map_err(|err| err.to_string())),
+            ModelFormat::toml => toml::from_str::<ModelDefinition>(data).map_err(|err| err.to_string().trim_end().to_string())
+        };
+        match model {
+            Ok(model) => {
+                validate_model_definition(&model)?;
+                Ok(model)
+            },
+            Err(err) => Err(Error::new(ErrorKind::InvalidData, format!("no valid {name}: {err}", name=format!("{format:?}").to_uppercase())))
+        }
+    }
+
+    /*
+        to_format: 
+            Serializes the model definition in the given format, like it's written to model files.
+
+        returns:
+            the serialized definition or an Error if the format can't express the definition
+    */
+    pub fn to_format(&self, format: ModelFormat) -> Result<String> {
+        match format {
+            ModelFormat::json => serde_json::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string())),
+            ModelFormat::yaml => serde_json::to_value(self)
+                .and_then(|value| serde_yaml_ng::to_string(&value).map_err(serde::ser::Error::custom))
+                .map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string())),
+            ModelFormat::toml => toml::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string()))
+        }
+    }
+}
+
+
+// name of the key identifying attribute set files, which models include via "mixins"
+pub const ATTRIBUTE_SET: &str = "attribute_set";
+// name of the key referencing the base model of a model
+pub const EXTENDS: &str = "extends";
+// name of the key listing the attribute sets included by a model
+pub const MIXINS: &str = "mixins";
+
+// lists of attribute names which are united when models get merged, other lists get replaced
+const UNITED_LISTS: [&str; 4] = ["required", "unique", "write_only", "hashed"];
+
+/*
+    resolve_model_definitions: 
+        Parses the contents of the files in a models' path, resolving base models and attribute sets.
+
+        What happens exactly:
+            1. parse every content to a generic value in its format
+            2. memorize the attribute sets by their names and the models by their plural camel-case names
+            3. parse models without base model and attribute sets as they are
+            4. resolve the other models, starting with their resolved base model without its name and description,
+               merging their attribute sets in the given order and finally the model itself,
+               maps get merged recursively, the united lists too, other values get replaced,
+               a base model which is already resolved in the chain isn't merged again
+            5. validate the resolved definitions like any other, which detects cycles and conflicting attribute types
+
+    returns:
+        for every content its model definition, None for attribute sets,
+        or an Error if it can't be parsed, resolved or validated
+*/
+pub fn resolve_model_definitions(contents: &[(String, ModelFormat)]) -> Vec<Option<Result<ModelDefinition>>> {
+    let values: Vec<std::result::Result<Value, String>> = contents.iter().map(|(data, format)| format.parse_value(data)).collect();
+
+    let mut sets: HashMap<String, usize> = HashMap::new();
+    let mut models: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, value) in values.iter().enumerate() {
+        if let Some(name) = value.as_ref().ok().and_then(|value| value.get(ATTRIBUTE_SET)).and_then(Value::as_str) {
+            sets.entry(name.to_string()).or_insert(i);
+        } else if let Some(name) = value.as_ref().ok().and_then(|value| value.get("model_name")).and_then(Value::as_str) {
+            models.entry(model_key(name)).or_default().push(i);
+        }
+    }
+
+    values.iter().enumerate().map(|(i, value)| {
+        let (data, format) = &contents[i];
+        let value: &Value = match value {
+            Ok(value) => value,
+            Err(err) => return Some(Err(Error::new(ErrorKind::InvalidData, format!("no valid {name}: {err}", name=format!("{format:?}").to_uppercase()))))
+        };
+        if value.get(ATTRIBUTE_SET).is_some() {
+            return match sets.get(value[ATTRIBUTE_SET].as_str().unwrap_or_default()) {
+                Some(first) if first != &i => Some(Err(Error::new(ErrorKind::InvalidData, format!("attribute set {name} is defined multiple times", name=value[ATTRIBUTE_SET])))),
+                _ => None
+            };
+        }
+        if value.get(EXTENDS).is_none() && value.get(MIXINS).is_none() {
+            // parsed again to keep the positions of errors
+            return Some(ModelDefinition::parse(data, *format));
+        }
+        let resolved: Result<ModelDefinition> = resolve_model(i, &values, &sets, &models, &mut vec!())
+            .map_err(|err| Error::new(ErrorKind::InvalidData, format!("no valid model: {err}")))
+            .and_then(|(resolved, origins)| to_resolved_definition(resolved, origins))
+            .map(|mut model| {
+                model.resolved_from = vec!();
+                model
+            });
+        Some(resolved)
+    }).collect()
+}
+
+fn model_key(name: &str) -> String {
+    ModelName(AttrName(name.to_string())).plural().camel().0.0
+}
+
+// the validated definition of a resolved model together with the base models and attribute sets it's resolved from
+fn to_resolved_definition(resolved: Map<String, Value>, origins: Vec<Origin>) -> Result<ModelDefinition> {
+    let mut model: ModelDefinition = serde_json::from_value(Value::Object(resolved))
+        .map_err(|err| Error::new(ErrorKind::InvalidData, format!("no valid model: {err}")))?;
+    model.resolved_from = origins;
+    validate_model_definition(&model)?;
+    Ok(model)
+}
+
+// resolves the model at the given index and lists the base models and attribute sets it's resolved from,
+// the visited models are memorized to stop at cycles
+fn resolve_model(i: usize, values: &[std::result::Result<Value, String>], sets: &HashMap<String, usize>, models: &HashMap<String, Vec<usize>>, visited: &mut Vec<String>) -> std::result::Result<(Map<String, Value>, Vec<Origin>), String> {
+    let model: &Map<String, Value> = match &values[i] {
+        Ok(Value::Object(model)) => model,
+        _ => return Err("the base model is invalid".to_string())
+    };
+    visited.push(model.get("model_name").and_then(Value::as_str).unwrap_or_default().to_string());
+
+    let mut resolved: Map<String, Value> = Map::new();
+    let mut origins: Vec<Origin> = vec!();
+    if let Some(base) = model.get(EXTENDS) {
+        let base_name: &str = base.as_str().ok_or(format!("{EXTENDS} must be a model name"))?;
+        let base_indices: &Vec<usize> = models.get(&model_key(base_name)).ok_or(format!("base model {base_name:?} not found"))?;
+        if visited.iter().any(|other| model_key(other) == model_key(base_name)) {
+            // the cycle is detected when the definition is validated
+            origins.push(Origin::BaseModel(ModelName(AttrName(base_name.to_string())), Attributes::new()));
+        } else {
+            let mut bases: Vec<(Map<String, Value>, Vec<Origin>)> = vec!();
+            for base_index in base_indices {
+                bases.push(resolve_model(*base_index, values, sets, models, &mut visited.clone())?);
+            }
+            // invalid files may use the name of the base model too, only a single valid one can be extended
+            if bases.len() > 1 {
+                bases.retain(|(base, base_origins)| to_resolved_definition(base.clone(), base_origins.clone()).is_ok());
+            }
+            let (base, base_origins): (Map<String, Value>, Vec<Origin>) = match bases.len() {
+                1 => bases.remove(0),
+                0 => return Err(format!("base model {base_name:?} is invalid")),
+                _ => return Err(format!("base model {base_name:?} is defined multiple times"))
+            };
+            let attributes: Attributes = defined_attributes(&base).map_err(|err| format!("base model {base_name:?} is invalid, {err}"))?;
+            origins.extend(base_origins);
+            origins.push(Origin::BaseModel(ModelName(AttrName(base_name.to_string())), attributes));
+            resolved = base;
+            resolved.remove("model_name");
+            resolved.remove("description");
+        }
+    }
+    for set in model.get(MIXINS).map(|mixins| mixins.as_array().ok_or(format!("{MIXINS} must be a list of attribute set names"))).transpose()?.into_iter().flatten() {
+        let set_name: &str = set.as_str().ok_or(format!("{MIXINS} must be a list of attribute set names"))?;
+        let set: &Map<String, Value> = match sets.get(set_name).map(|index| &values[*index]) {
+            Some(Ok(Value::Object(set))) => set,
+            _ => return Err(format!("attribute set {set_name:?} not found"))
+        };
+        let attributes: Attributes = defined_attributes(set).map_err(|err| format!("attribute set {set_name:?} is invalid, {err}"))?;
+        origins.push(Origin::AttributeSet(set_name.to_string(), attributes));
+        merge_definitions(&mut resolved, set);
+    }
+    merge_definitions(&mut resolved, model);
+    for key in [ATTRIBUTE_SET, EXTENDS, MIXINS] {
+        resolved.remove(key);
+    }
+    Ok((resolved, origins))
+}
+
+// the attributes defined in a generic definition
+fn defined_attributes(definition: &Map<String, Value>) -> std::result::Result<Attributes, String> {
+    match definition.get("attributes") {
+        Some(attributes) => serde_json::from_value(attributes.clone()).map_err(|err| err.to_string()),
+        None => Ok(Attributes::new())
+    }
+}
+
+// merges the definition into the resolved one, the attributes and other maps like the constraints get merged recursively,
+// lists of the united lists get united, other values get replaced
+fn merge_definitions(resolved: &mut Map<String, Value>, definition: &Map<String, Value>) {
+    for (key, value) in definition {
+        match (key.as_str(), resolved.get_mut(key), value) {
+            ("attributes", Some(Value::Object(attributes)), Value::Object(added)) => merge_attributes(attributes, added),
+            (key, Some(Value::Array(list)), Value::Array(added)) if UNITED_LISTS.contains(&key) => {
+                for item in added {
+                    if !list.contains(item) {
+                        list.push(item.clone());
+                    }
+                }
+            },
+            (_, Some(Value::Object(map)), Value::Object(added)) => merge_maps(map, added),
+            _ => {
+                resolved.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// merges attribute types, objects get merged like definitions, the types of other attributes get replaced
+fn merge_attributes(attributes: &mut Map<String, Value>, added: &Map<String, Value>) {
+    for (attr, attr_type) in added {
+        match (attributes.get_mut(attr).and_then(|resolved| resolved.get_mut("Object")), attr_type.get("Object")) {
+            (Some(Value::Object(object)), Some(Value::Object(added))) => merge_definitions(object, added),
+            _ => {
+                attributes.insert(attr.clone(), attr_type.clone());
+            }
+        }
+    }
+}
+
+// merges maps recursively, other values get replaced
+fn merge_maps(map: &mut Map<String, Value>, added: &Map<String, Value>) {
+    for (key, value) in added {
+        match (map.get_mut(key), value) {
+            (Some(Value::Object(nested)), Value::Object(added)) => merge_maps(nested, added),
+            _ => {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// whether the type of an attribute in a base model or attribute set fits to its resolved type,
+// objects fit if their nested attributes do, since they get merged
+fn fits_resolved(defined: &AttrType, resolved: &AttrType) -> bool {
+    match (defined, resolved) {
+        (AttrType::Object(defined), AttrType::Object(resolved)) => defined.object.attributes.iter()
+            .all(|(attr, defined)| resolved.object.attributes.get(attr).is_some_and(|resolved| fits_resolved(defined, resolved))),
+        (AttrType::Enum(defined), AttrType::Enum(resolved)) => defined.values == resolved.values,
+        _ => defined == resolved
+    }
+}
+
+/*
+    validate_model_definition: 
+        Validates a given model definition if it meets all important conditions.
+
+        What happens exactly:
+            1. validate the base models and attribute sets of a resolved definition,
+               therefore check if the chain of base models has no cycle
+               and the attributes they define fit to the resolved attribute types
+            2. validate the primary key,
+               therefore check if all of its attributes are defined in the attributes and unique,
+               also check if their types are not Array, since this is not allowed,
+               and if a key generator is set, check if the key is single and fits to its type
+            3. validate the as required defined attributes,
+               therefore check if the primary key attributes are required,
+               also check if all declared required attributes are actually defined in th attributes
+            4. validate the unique attribute sets,
+               therefore check if they aren't empty and consist of defined attributes which are not Array or Object
+            5. validate the constraints,
+               therefore check if they belong to defined attributes and fit to their types
+            6. validate the default values,
+               therefore check if they belong to optional attributes and fit to their types,
+               also check if the descriptions belong to defined attributes or associations
+            7. validate the associations,
+               therefore check if their names don't collide with attributes and their foreign keys are valid,
+               manyToMany associations need an other key and a single primary key,
+               referential actions are only allowed for belongsTo associations and setNull only for optional foreign keys,
+               the associated models themselves are checked when they are used
+            8. validate the managed attributes of timestamps, soft delete and versions,
+               therefore check if they don't collide with attributes or associations,
+               the same applies to the history field if history is enabled
+            9. validate the write-only and hashed attributes,
+               therefore check if they are defined and neither part of the primary key, unique or a foreign key,
+               hashed attributes also need to be String
+            10. validate the rules,
+               therefore check if their expressions can be parsed and only use defined or managed attributes
+            11. validate the time to live,
+               therefore check if a fixed duration is positive or the expiry attribute is a DateTime outside the primary key,
+               which may be required or optional, records without expiry time never expire
+            12. validate the naming policy,
+               therefore check if the renamed attributes, including nested and managed ones, and the renamed associations
+               don't collide with each other or the history field, whose name is kept
+
+    returns:
+        Empty tuple if the model is valid, else Error
+*/
+pub fn validate_model_definition(definition: &ModelDefinition) -> Result<()> {
+    // validate model name inflection
+    if definition.model_name.singular() == definition.model_name.plural() {
+        return Err(Error::new(ErrorKind::InvalidData, "Name has no plural variant"));
+    }
+
+    // validate base models and attribute sets
+    let mut chain: Vec<&ModelName> = vec!(&definition.model_name);
+    for origin in definition.resolved_from.iter().rev() {
+        let (name, attributes): (String, &Attributes) = match origin {
+            Origin::BaseModel(base_name, attributes) => {
+                let is_cyclic: bool = chain.iter().any(|name| name.plural().camel() == base_name.plural().camel());
+                chain.push(base_name);
+                if is_cyclic {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("cyclic base models {cycle}", cycle=chain.iter().map(|name| name.0.0.as_str()).collect::<Vec<&str>>().join(" -> "))));
+                }
+                (format!("base model {base:?}", base=base_name.0.0), attributes)
+            },
+            Origin::AttributeSet(set_name, attributes) => (format!("attribute set {set_name:?}"), attributes)
+        };
+        for (attr, attr_type) in attributes {
+            if definition.attributes.get(attr).is_some_and(|resolved| !fits_resolved(attr_type, resolved)) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("conflicting types of attribute {attr:?} in the {name} and the resolved model", attr=attr.0)));
+            }
+        }
+    }
+
+    // validate primary key
+    let key_attrs: Vec<AttrName> = definition.primary_key.attrs();
+    if key_attrs.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidData, "invalid primary key, it needs at least one attribute"));
+    }
+    for (index, key_attr) in key_attrs.iter().enumerate() {
+        if key_attrs[..index].contains(key_attr) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid primary key, attribute {attr:?} is used multiple times", attr=key_attr.0)));
+        }
+        if let Some(ty) = definition.attributes.get(key_attr) {
+            if let AttrType::Array(_) | AttrType::Enum(_) | AttrType::Object(_) = ty {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid primary key"));
+            }
+            // floats have no exact textual representation, so their keys can't be looked up reliably
+            if let AttrType::Primitive(PrimitiveType::Float) = ty {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid primary key, Float can't be used as key type"));
+            }
+            if let Some(generator) = &definition.primary_key_generator {
+                if key_attrs.len() > 1 {
+                    return Err(Error::new(ErrorKind::InvalidData, "invalid primary key generator, composite keys can't be generated"));
+                }
+                if !generator.fits(ty) {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid primary key generator, {generator:?} doesn't fit to the key type")));
+                }
+            }
+        }
+        else {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid primary key"));
+        }
+    }
+
+    // validate required attributes
+    if key_attrs.iter().any(|key_attr| !definition.required.contains(key_attr)) {
+        return Err(Error::new(ErrorKind::InvalidData, "primary key must be required"));
+    }
+
+    validate_attributes(&definition.attributes, &definition.required)?;
+
+    // validate unique attributes
+    for unique_attrs in definition.unique.iter().flatten().map(KeyAttrs::attrs) {
+        if unique_attrs.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid unique constraint, it needs at least one attribute"));
+        }
+        for attr in &unique_attrs {
+            match definition.attributes.get(attr) {
+                Some(AttrType::Array(_) | AttrType::Object(_)) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid unique constraint, attribute {attr:?} must not be Array or Object", attr=attr.0))),
+                Some(_) => (),
+                None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid unique constraint for not existing attribute {attr:?}", attr=attr.0)))
+            }
+        }
+    }
+
+    // validate constraints
+    if let Some(constraints) = &definition.constraints {
+        for (attr, constraint) in constraints {
+            match definition.attributes.get(attr) {
+                Some(ty) => {
+                    if let Err(err) = constraint.validate(ty) {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("invalid constraint for attribute {attr:?}, {err}", attr=attr.0)));
+                    }
+                },
+                None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid constraint for not existing attribute {attr:?}", attr=attr.0)))
+            }
+        }
+    }
+
+    // validate default values
+    if let Some(defaults) = &definition.defaults {
+        for (attr, default) in defaults {
+            let attr_type: &AttrType = match definition.attributes.get(attr) {
+                Some(ty) => ty,
+                None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid default for not existing attribute {attr:?}", attr=attr.0)))
+            };
+            if definition.required.contains(attr) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("invalid default for attribute {attr:?}, defaults are only allowed for optional attributes", attr=attr.0)));
+            }
+            match default {
+                DefaultValue::Generated { generator } => {
+                    if !generator.fits(attr_type) {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("invalid default for attribute {attr:?}, generator {generator:?} doesn't fit to its type", attr=attr.0)));
+                    }
+                },
+                DefaultValue::Literal(value) => {
+                    if let Err(err) = super::parse_attributes(HashMap::from([(attr.clone(), value.clone())]), &definition.attributes, &[], None) {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("invalid default for attribute {attr:?}, {err}", attr=attr.0)));
+                    }
+                }
+            }
+        }
+    }
+
+    // validate descriptions
+    for attr in definition.descriptions.iter().flat_map(HashMap::keys) {
+        if !definition.attributes.contains_key(attr) && !definition.associations.iter().flatten().any(|(name, _)| name == attr) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid description for not existing attribute or association {attr:?}", attr=attr.0)));
+        }
+    }
+
+    // validate associations
+    if let Some(associations) = &definition.associations {
+        for (name, association) in associations {
+            if definition.attributes.contains_key(name) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, there is already an attribute with this name", name=name.0)));
+            }
+            match association.kind {
+                AssociationKind::BelongsTo => {
+                    match definition.attributes.get(&association.foreign_key) {
+                        Some(AttrType::Primitive(_)) => (),
+                        Some(_) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, the foreign key has to be of primitive type", name=name.0))),
+                        None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, the foreign key {key:?} doesn't exist", name=name.0, key=association.foreign_key.0)))
+                    }
+                },
+                AssociationKind::HasMany => {
+                    if definition.primary_key.attrs().len() > 1 {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, models with composite primary key can't be referenced", name=name.0)));
+                    }
+                },
+                AssociationKind::ManyToMany => {
+                    if definition.primary_key.attrs().len() > 1 {
+                        return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, models with composite primary key can't be linked", name=name.0)));
+                    }
+                    match &association.other_key {
+                        Some(other_key) if other_key == &association.foreign_key => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, the foreign key and the other key must differ", name=name.0))),
+                        // the other key is used as argument beside the primary key when linking records
+                        Some(other_key) if definition.primary_key.contains(other_key) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, the other key must not be named like the primary key", name=name.0))),
+                        Some(_) => (),
+                        None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, manyToMany associations need an other_key", name=name.0)))
+                    }
+                }
+            }
+            if association.kind != AssociationKind::ManyToMany && (association.other_key.is_some() || association.through.is_some()) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, other_key and through are only allowed for manyToMany associations", name=name.0)));
+            }
+            match &association.on_delete {
+                Some(_) if association.kind != AssociationKind::BelongsTo => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, on_delete is only allowed for belongsTo associations", name=name.0))),
+                Some(OnDelete::SetNull) if definition.required.contains(&association.foreign_key) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid association {name:?}, setNull needs an optional foreign key", name=name.0))),
+                _ => ()
+            }
+        }
+    }
+
+    // validate managed attributes
+    for attr in definition.managed_attrs() {
+        if definition.attributes.contains_key(&attr) || definition.associations.iter().flatten().any(|(name, _)| name == &attr) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid managed attribute, there is already an attribute or association named {attr:?}", attr=attr.0)));
+        }
+    }
+    let history: &AttrName = &AttrName(HISTORY.to_string());
+    if definition.history && (definition.attributes.contains_key(history) || definition.associations.iter().flatten().any(|(name, _)| name == history)) {
+        return Err(Error::new(ErrorKind::InvalidData, format!("invalid history, there is already an attribute or association named {HISTORY:?}")));
+    }
+
+    // validate write-only and hashed attributes
+    for attr in definition.secret_attrs() {
+        // errors name the list the attribute comes from
+        let is_hashed: bool = definition.hashed.iter().flatten().any(|hashed| hashed == &attr);
+        let list: &str = if is_hashed { "hashed" } else { "write-only" };
+        match definition.attributes.get(&attr) {
+            Some(AttrType::Primitive(PrimitiveType::String)) => (),
+            Some(_) if !is_hashed => (),
+            Some(_) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid hashed attribute {attr:?}, it must be String", attr=attr.0))),
+            None => return Err(Error::new(ErrorKind::InvalidData, format!("invalid {list} attribute {attr:?}, it doesn't exist", attr=attr.0)))
+        }
+        if key_attrs.contains(&attr)
+            || definition.unique.iter().flatten().any(|unique_attrs| unique_attrs.contains(&attr))
+            || definition.associations.iter().flatten().any(|(_, association)| association.kind == AssociationKind::BelongsTo && association.foreign_key == attr) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid {list} attribute {attr:?}, it must not be part of the primary key, unique or a foreign key", attr=attr.0)));
+        }
+    }
+
+    // validate rules
+    for (name, expression) in definition.rules.iter().flatten() {
+        if name.trim().is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid rule without name"));
+        }
+        let parsed: Expression = match Expression::parse(expression) {
+            Ok(parsed) => parsed,
+            Err(err) => return Err(Error::new(ErrorKind::InvalidData, format!("invalid rule {name:?}, {err}")))
+        };
+        for path in parsed.attributes() {
+            let mut attributes: &Attributes = &definition.attributes;
+            let managed: bool = path.len() == 1 && definition.managed_attrs().contains(&path[0]);
+            for (i, attr) in path.iter().enumerate() {
+                match attributes.get(attr) {
+                    Some(AttrType::Object(obj_def)) => attributes = &obj_def.object.attributes,
+                    Some(_) if i == path.len() - 1 => (),
+                    _ if managed => (),
+                    _ => return Err(Error::new(ErrorKind::InvalidData, format!(
+                        "invalid rule {name:?}, attribute {path:?} doesn't exist",
+                        path=path.iter().map(|attr| attr.0.as_str()).collect::<Vec<&str>>().join(".")
+                    )))
+                }
+            }
+        }
+    }
+
+    // validate time to live
+    match &definition.ttl {
+        Some(Ttl::Seconds(0)) => return Err(Error::new(ErrorKind::InvalidData, "invalid ttl, the duration must be positive")),
+        Some(Ttl::Attribute(attr)) => {
+            if definition.attributes.get(attr) != Some(&AttrType::Primitive(PrimitiveType::DateTime)) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("invalid ttl, the expiry attribute {attr:?} must be an existing DateTime", attr=attr.0)));
+            }
+            if key_attrs.contains(attr) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("invalid ttl, the expiry attribute {attr:?} must not be part of the primary key", attr=attr.0)));
+            }
+        },
+        _ => ()
+    }
+
+    // validate naming policy
+    if let Some(naming) = &definition.naming {
+        let mut kept: Vec<AttrName> = vec!();
+        if definition.history {
+            kept.push(AttrName(HISTORY.to_string()));
+        }
+        let managed: Vec<AttrName> = definition.managed_attrs();
+        let associations = definition.associations.iter().flatten().map(|(name, _)| name);
+        validate_naming(definition.attributes.keys().chain(managed.iter()).chain(associations).collect(), &definition.attributes, kept, naming)?;
+    }
+
+    Ok(())
+}
+
+/*
+    validate_attributes: 
+        Validates attributes and their as required declared ones,
+        nested object attributes get validated recursively.
+
+    returns:
+        Empty tuple if the attributes are valid, else Error
+*/
+fn validate_attributes(attributes: &Attributes, required: &[AttrName]) -> Result<()> {
+    for attr in required {
+        if !attributes.contains_key(attr) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid required attribute {attr:?}", attr=attr.0)));
+        }
+    }
+
+    for (attr, ty) in attributes {
+        match ty {
+            AttrType::Enum(enum_def) => {
+                if let Err(err) = validate_enum_values(&enum_def.values) {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid enum attribute {attr:?}, {err}", attr=attr.0)));
+                }
+                if let Some(value) = enum_def.descriptions.iter().flat_map(HashMap::keys).find(|value| !enum_def.values.contains(value)) {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid enum attribute {attr:?}, description for not existing value {value:?}", attr=attr.0)));
+                }
+            },
+            AttrType::Object(obj_def) => {
+                if obj_def.object.attributes.is_empty() {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid object attribute {attr:?}, it has no attributes", attr=attr.0)));
+                }
+                if let Err(err) = validate_attributes(&obj_def.object.attributes, &obj_def.object.required) {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid object attribute {attr:?}, {err}", attr=attr.0)));
+                }
+                if let Some(nested) = obj_def.object.descriptions.iter().flat_map(HashMap::keys).find(|nested| !obj_def.object.attributes.contains_key(nested)) {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("invalid object attribute {attr:?}, description for not existing attribute {nested:?}", attr=attr.0, nested=nested.0)));
+                }
+            },
+            _ => ()
+        }
+    }
+
+    Ok(())
+}
+
+/*
+    validate_enum_values: 
+        Validates the values of an enum attribute, so that they are usable as GraphQL enum values.
+
+    returns:
+        Empty tuple if the values are valid, else Error
+*/
+pub fn validate_enum_values(values: &[String]) -> Result<()> {
+    let regex = Regex::new(r#"^[_A-Za-z][_0-9A-Za-z]*$"#).unwrap();
+    if values.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidData, "it has no values"));
+    }
+    for (index, value) in values.iter().enumerate() {
+        if !regex.is_match(value) || ["true", "false", "null"].contains(&value.as_str()) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid value {value:?}")));
+        }
+        if values[..index].contains(value) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("duplicate value {value:?}")));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize, Eq, PartialEq, Hash, Clone, Debug)]
+pub struct ModelName(pub AttrName);
+
+impl ModelName {
+    pub fn singular(&self) -> Self {
+        ModelName(AttrName(singularize(&self.0.0)))
+    }
+    pub fn assert_singularity(&self) -> Result<()> {
+        if self != &self.singular() {
+            return Err(Error::new(ErrorKind::InvalidData, "Expected singular model name, got plural variant"));
+        }
+        Ok(())
+    }
+    pub fn plural(&self) -> Self {
+        ModelName(AttrName(pluralize(&self.0.0)))
+    }
+    pub fn assert_plurality(&self) -> Result<()> {
+        if self != &self.plural() {
+            return Err(Error::new(ErrorKind::InvalidData, "Expected plural model name, got singular variant"));
+        }
+        Ok(())
+    }
+    pub fn camel(&self) -> Self {
+        ModelName(AttrName(camelize(&self.0.0)))
+    }
+    pub fn pascal(&self) -> Self {
+        ModelName(AttrName(pascalize(&self.0.0)))
+    }
+}
+
+// define AttrName with custom Deserializer that validates REST-ful Strings
+#[derive(Serialize, Eq, PartialEq, Hash, Clone, Debug)]
+pub struct AttrName(pub String);
+
+impl AttrName {
+    pub fn pascal(&self) -> Self {
+        AttrName(pascalize(&self.0))
+    }
+    pub fn named(&self, naming: Option<&Naming>) -> Self {
+        match naming {
+            Some(naming) => naming.apply(self),
+            None => self.clone()
+        }
+    }
+}
+
+impl TryFrom<&str> for AttrName {
+    type Error = Error;
+
+    fn try_from(s: &str) -> core::result::Result<Self, Self::Error> {
+        validate_attr_name(s)?;
+        Ok(AttrName(s.to_string()))
+    }
+}
+
+struct AttrNameVisitor;
+
+impl<'de> de::Visitor<'de> for AttrNameVisitor {
+    type Value = AttrName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("alphanumeric String starting with a letter, snake_case or PascalCase or camelCase or spinal-case")
+    }
+
+    fn visit_str<E>(self, value: &str) -> core::result::Result<AttrName, E>
+    where
+        E: de::Error,
+    {
+        match AttrName::try_from(value) {
+            Ok(name) => Ok(name),
+            Err(err) => Err(de::Error::custom(err.to_string())),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for AttrName {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(AttrNameVisitor)
+    }
+}
+
+// digits are allowed anywhere but at the start, like in GraphQL names
+fn validate_attr_name(name: &str) -> Result<()> {
+    let regex: Vec<Regex> = vec!(
+        Regex::new(r#"^[A-Z][a-zA-Z0-9]*$"#).unwrap(),  // PascalCase
+        Regex::new(r#"^[a-z][a-zA-Z0-9]*$"#).unwrap(),  // camelCase
+        Regex::new(r#"^[a-z][a-z0-9]*(_[a-z0-9]+)*$"#).unwrap(),  // snake_case
+        Regex::new(r#"^[a-z][a-z0-9]*(-[a-z0-9]+)*$"#).unwrap()  // spinal-case
+    );
+
+    for r in regex {
+        if r.is_match(name) {
+            return Ok(());
+        }
+    }
+    Err(Error::new(ErrorKind::InvalidData, "Name is not alphanumeric starting with a letter in camelCase, PascalCase, snake_case or spinal-case"))
+}
+
+/*
+    validate_naming: 
+        Checks if the given attributes of the same level are still distinguishable after renaming them with the naming policy,
+        the attributes of nested objects are checked separately.
+
+    returns:
+        Empty tuple if there are no collisions, else Error
+*/
+fn validate_naming(attrs: Vec<&AttrName>, attributes: &Attributes, kept: Vec<AttrName>, naming: &Naming) -> Result<()> {
+    let mut exposed: Vec<(AttrName, &AttrName)> = vec!();
+    for attr in attrs {
+        let name: AttrName = naming.apply(attr);
+        if let Some((_, other)) = exposed.iter().find(|(other_name, _)| other_name == &name) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid naming, the attributes {other:?} and {attr:?} are both named {name:?}", other=other.0, attr=attr.0, name=name.0)));
+        }
+        if kept.contains(&name) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("invalid naming, the attribute {attr:?} is named like the field {name:?}", attr=attr.0, name=name.0)));
+        }
+        if let Some(AttrType::Object(obj_def)) = attributes.get(attr) {
+            validate_naming(obj_def.object.attributes.keys().collect(), &obj_def.object.attributes, vec!(), naming)?;
+        }
+        exposed.push((name, attr));
+    }
+    Ok(())
+}
+
+pub fn to_true_enum_value(value: &Value, enum_def: &EnumDefinition, is_required: bool) -> Result<Option<TruePrimitiveType>> {
+    let expected = || Error::new(ErrorKind::InvalidData, format!("expected one of: {values}", values=enum_def.values.join(", ")));
+    match to_true_prim_type(value, &PrimitiveType::String, is_required) {
+        Ok(Some(TruePrimitiveType::String(val))) => {
+            if enum_def.values.contains(&val) {
+                Ok(Some(TruePrimitiveType::String(val)))
+            } else {
+                Err(expected())
+            }
+        },
+        Ok(_) => Ok(None),
+        Err(err) if value.is_null() => Err(err),
+        Err(_) => Err(expected())
+    }
+}
+
+pub fn to_true_prim_type(value: &Value, model_type: &PrimitiveType, is_required: bool) -> Result<Option<TruePrimitiveType>> {
+    if value.as_null().is_some() {
+        if is_required {
+            return Err(Error::new(ErrorKind::InvalidData, "it is required, got: null"));
+        } else {
+            return Ok(None);
+        }
+    }
+    match model_type {
+        PrimitiveType::Integer => {
+            match value.as_i64() {
+                Some(val) => Ok(Some(TruePrimitiveType::Integer(val))),
+                None => Err(Error::new(ErrorKind::InvalidData, "expected: Integer"))
+            }
+        },
+        PrimitiveType::String => {
+            match value.as_str() {
+                Some(val) => Ok(Some(TruePrimitiveType::String(val.to_string()))),
+                None => Err(Error::new(ErrorKind::InvalidData, "expected: String"))
+            }
+        },
+        PrimitiveType::Boolean => {
+            match value.as_bool() {
+                Some(val) => Ok(Some(TruePrimitiveType::Boolean(val))),
+                None => Err(Error::new(ErrorKind::InvalidData, "expected: Boolean"))
+            }
+        },
+        PrimitiveType::Float => {
+            match value.as_f64() {
+                Some(val) => Ok(Some(TruePrimitiveType::Float(val))),
+                None => Err(Error::new(ErrorKind::InvalidData, "expected: Float"))
+            }
+        },
+
+        // temporal values are stored as normalized Strings
+        PrimitiveType::Date => {
+            match value.as_str().map(|val| NaiveDate::parse_from_str(val, DATE_FORMAT)) {
+                Some(Ok(date)) => Ok(Some(TruePrimitiveType::String(date.format(DATE_FORMAT).to_string()))),
+                _ => Err(Error::new(ErrorKind::InvalidData, "expected: Date, formatted as YYYY-MM-DD"))
+            }
+        },
+        PrimitiveType::DateTime => {
+            match value.as_str().map(DateTime::parse_from_rfc3339) {
+                Some(Ok(date_time)) => Ok(Some(TruePrimitiveType::String(date_time.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::AutoSi, true)))),
+                _ => Err(Error::new(ErrorKind::InvalidData, "expected: DateTime, formatted as RFC 3339 with timezone"))
+            }
+        },
+        PrimitiveType::Time => {
+            match value.as_str().map(|val| NaiveTime::parse_from_str(val, TIME_FORMAT)) {
+                Some(Ok(time)) => Ok(Some(TruePrimitiveType::String(time.format(TIME_FORMAT).to_string()))),
+                _ => Err(Error::new(ErrorKind::InvalidData, "expected: Time, formatted as hh:mm:ss"))
+            }
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_model_definition() {
+        // test primary key of type array
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Array([PrimitiveType::String]))
+            ]),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with Array as primary key type");
+
+        // test primary key of type float
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Float))
+            ]),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with Float as primary key type");
+
+        // test not existing primary key attribute
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::new(),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with missing primary key attribute in attributes");
+
+        // test not required primary key
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            required: vec!(),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with not required primary key");
+
+        // test not existing required attribute
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))
+            ]),
+            required: vec!(
+                AttrName("id".to_string()),
+                AttrName("iDontExist".to_string())
+            ),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with not existing required attributes");
+
+        // test invalid enum values
+        for values in [vec!(), vec!("RELEASED", "RELEASED"), vec!("in production"), vec!("1st"), vec!("null")] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("status".to_string()), AttrType::Enum(EnumDefinition {
+                        values: values.iter().map(|v| v.to_string()).collect(),
+                        descriptions: None
+                    }))
+                ]),
+                ..base_model()
+            };
+            assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with invalid enum values {values:?}");
+        }
+
+        // test invalid object attributes
+        let address = |attributes: Attributes, required: Vec<AttrName>| AttrType::Object(ObjectDefinition {
+            object: ObjectAttributes {
+                attributes,
+                required,
+                descriptions: None
+            }
+        });
+        for attr_type in [
+            address(Attributes::new(), vec!()),
+            address(Attributes::from([(AttrName("street".to_string()), AttrType::Primitive(PrimitiveType::String))]), vec!(AttrName("zip".to_string()))),
+            address(Attributes::from([(AttrName("geo".to_string()), address(Attributes::new(), vec!()))]), vec!())
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("address".to_string()), attr_type)
+                ]),
+                ..base_model()
+            };
+            assert!(validate_model_definition(model).is_err(), "Expected Error for model definitions with invalid object attributes");
+        }
+
+        // test default values
+        let defaults_model = |defaults: Vec<(&str, DefaultValue)>| ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("released".to_string()), AttrType::Primitive(PrimitiveType::Date))
+            ]),
+            defaults: Some(defaults.into_iter().map(|(attr, default)| (AttrName(attr.to_string()), default)).collect()),
+            ..base_model()
+        };
+        for defaults in [
+            vec!(("year", DefaultValue::Literal(Value::from(2000)))),
+            vec!(("released", DefaultValue::Generated { generator: Generator::now })),
+            vec!(("released", DefaultValue::Literal(Value::from("2000-01-01"))))
+        ] {
+            assert!(validate_model_definition(&defaults_model(defaults.clone())).is_ok(), "Expected valid model definition with defaults {defaults:?}");
+        }
+        for defaults in [
+            vec!(("id", DefaultValue::Generated { generator: Generator::uuid })),
+            vec!(("iDontExist", DefaultValue::Literal(Value::from(1)))),
+            vec!(("year", DefaultValue::Literal(Value::from("2000")))),
+            vec!(("year", DefaultValue::Generated { generator: Generator::now })),
+            vec!(("released", DefaultValue::Generated { generator: Generator::uuid })),
+            vec!(("released", DefaultValue::Literal(Value::from("01.01.2000"))))
+        ] {
+            assert!(validate_model_definition(&defaults_model(defaults.clone())).is_err(), "Expected Error for model definitions with invalid defaults {defaults:?}");
+        }
+
+        // test primary key generators
+        for (key_type, generator, is_valid) in [
+            (PrimitiveType::Integer, KeyGenerator::increment, true),
+            (PrimitiveType::String, KeyGenerator::uuid, true),
+            (PrimitiveType::String, KeyGenerator::ulid, true),
+            (PrimitiveType::String, KeyGenerator::increment, false),
+            (PrimitiveType::Integer, KeyGenerator::uuid, false),
+            (PrimitiveType::Float, KeyGenerator::increment, false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                primary_key_generator: Some(generator.clone()),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(key_type.clone()))
+                ]),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for key generator {generator:?} on key type {key_type:?}");
+        }
+
+        // test unique attributes
+        for (unique, is_valid) in [
+            (vec!(KeyAttrs::Single(AttrName("name".to_string()))), true),
+            (vec!(KeyAttrs::Composite(vec!(AttrName("name".to_string()), AttrName("year".to_string())))), true),
+            (vec!(KeyAttrs::Composite(vec!())), false),
+            (vec!(KeyAttrs::Single(AttrName("iDontExist".to_string()))), false),
+            (vec!(KeyAttrs::Single(AttrName("actors".to_string()))), false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String]))
+                ]),
+                unique: Some(unique.clone()),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for unique attributes {unique:?}");
+        }
+
+        // test associations
+        for (associations, is_valid) in [
+            (vec!(("director", AssociationKind::BelongsTo, "director", "directorId")), true),
+            (vec!(("reviews", AssociationKind::HasMany, "review", "movieId")), true),
+            (vec!(("director", AssociationKind::BelongsTo, "director", "iDontExist")), false),
+            (vec!(("director", AssociationKind::BelongsTo, "director", "actors")), false),
+            (vec!(("actors", AssociationKind::HasMany, "actor", "movieId")), false),
+            (vec!(("actors", AssociationKind::ManyToMany, "actor", "movieId")), false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("directorId".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("actors".to_string()), AttrType::Array([PrimitiveType::String]))
+                ]),
+                associations: Some(associations.iter().map(|(name, kind, model, foreign_key)| (AttrName(name.to_string()), Association {
+                    kind: kind.clone(),
+                    model: ModelName(AttrName(model.to_string())),
+                    foreign_key: AttrName(foreign_key.to_string()),
+                    other_key: None,
+                    through: None,
+                    on_delete: None
+                })).collect()),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for associations {associations:?}");
+        }
+
+        // test referential actions
+        for (kind, on_delete, foreign_key, is_valid) in [
+            (AssociationKind::BelongsTo, OnDelete::Cascade, "id", true),
+            (AssociationKind::BelongsTo, OnDelete::Restrict, "directorId", true),
+            (AssociationKind::BelongsTo, OnDelete::SetNull, "directorId", true),
+            (AssociationKind::BelongsTo, OnDelete::SetNull, "id", false),
+            (AssociationKind::HasMany, OnDelete::Cascade, "testId", false)
+        ] {
+            let association = Association {
+                kind,
+                model: ModelName(AttrName("director".to_string())),
+                foreign_key: AttrName(foreign_key.to_string()),
+                other_key: None,
+                through: None,
+                on_delete: Some(on_delete)
+            };
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("directorId".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+                ]),
+                associations: Some(HashMap::from([(AttrName("director".to_string()), association.clone())])),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for association {association:?}");
+        }
+
+        // test timestamps, soft delete and versions
+        for (attr_name, timestamps, soft_delete, versioned, is_valid) in [
+            ("title", true, true, true, true),
+            ("createdAt", false, true, true, true),
+            ("createdAt", true, false, false, false),
+            ("updatedAt", true, false, false, false),
+            ("deletedAt", true, false, true, true),
+            ("deletedAt", false, true, false, false),
+            ("version", true, true, false, true),
+            ("version", false, false, true, false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName(attr_name.to_string()), AttrType::Primitive(PrimitiveType::DateTime))
+                ]),
+                timestamps,
+                soft_delete,
+                versioned,
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for managed attributes with attribute {attr_name:?}");
+        }
+
+        // test descriptions of attributes, associations, enum values and nested attributes
+        for (attr_name, value, nested_name, is_valid) in [
+            ("title", "PLANNED", "city", true),
+            ("director", "PLANNED", "city", true),
+            ("iDontExist", "PLANNED", "city", false),
+            ("title", "CANCELLED", "city", false),
+            ("title", "PLANNED", "iDontExist", false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                description: Some("A model for testing".to_string()),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("title".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("directorId".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("status".to_string()), AttrType::Enum(EnumDefinition {
+                        values: vec!("RELEASED".to_string(), "PLANNED".to_string()),
+                        descriptions: Some(HashMap::from([(value.to_string(), "The test isn't released yet".to_string())]))
+                    })),
+                    (AttrName("studio".to_string()), AttrType::Object(ObjectDefinition {
+                        object: ObjectAttributes {
+                            attributes: Attributes::from([(AttrName("city".to_string()), AttrType::Primitive(PrimitiveType::String))]),
+                            required: vec!(),
+                            descriptions: Some(HashMap::from([(AttrName(nested_name.to_string()), "The city of the studio".to_string())]))
+                        }
+                    }))
+                ]),
+                descriptions: Some(HashMap::from([(AttrName(attr_name.to_string()), "The \"title\" of the test".to_string())])),
+                associations: Some(HashMap::from([(AttrName("director".to_string()), Association {
+                    kind: AssociationKind::BelongsTo,
+                    model: ModelName(AttrName("director".to_string())),
+                    foreign_key: AttrName("directorId".to_string()),
+                    other_key: None,
+                    through: None,
+                    on_delete: None
+                })])),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for the descriptions of {attr_name:?}, the enum value {value:?} and the nested attribute {nested_name:?}");
+        }
+
+        // test rules
+        for (expression, is_valid) in [
+            ("endYear >= year", true),
+            ("address.city != null || updatedAt == null", true),
+            ("address == null", true),
+            ("endYear >=", false),
+            ("iDontExist > 1", false),
+            ("address.iDontExist == null", false),
+            ("year.value == 1", false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("endYear".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("address".to_string()), AttrType::Object(ObjectDefinition {
+                        object: ObjectAttributes {
+                            attributes: Attributes::from([(AttrName("city".to_string()), AttrType::Primitive(PrimitiveType::String))]),
+                            required: vec!(),
+                            descriptions: None
+                        }
+                    }))
+                ]),
+                rules: Some(HashMap::from([("testRule".to_string(), expression.to_string())])),
+                timestamps: true,
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for rule {expression:?}");
+        }
+
+        // test time to live
+        for (ttl, is_valid) in [
+            (Ttl::Seconds(3600), true),
+            (Ttl::Seconds(0), false),
+            (Ttl::Attribute(AttrName("validUntil".to_string())), true),
+            (Ttl::Attribute(AttrName("year".to_string())), false),
+            (Ttl::Attribute(AttrName("iDontExist".to_string())), false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("year".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("validUntil".to_string()), AttrType::Primitive(PrimitiveType::DateTime))
+                ]),
+                ttl: Some(ttl.clone()),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for ttl {ttl:?}");
+        }
+
+        // test naming policy
+        for (attr_names, naming, is_valid) in [
+            (vec!("release_year", "title2"), Naming::CamelCase, true),
+            (vec!("releaseYear", "release_year"), Naming::CamelCase, false),
+            (vec!("releaseYear", "release_year"), Naming::SnakeCase, false),
+            (vec!("created_at"), Naming::CamelCase, false),
+            (vec!("movie_cast"), Naming::CamelCase, false),
+            (vec!("movie_cast"), Naming::SnakeCase, false),
+            (vec!("releaseYear", "sha256"), Naming::SnakeCase, true)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from_iter(
+                    attr_names.iter().map(|attr| (AttrName(attr.to_string()), AttrType::Primitive(PrimitiveType::Integer)))
+                        .chain([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))])
+                ),
+                associations: Some(HashMap::from([(AttrName("movieCast".to_string()), Association {
+                    kind: AssociationKind::HasMany,
+                    model: ModelName(AttrName("casting".to_string())),
+                    foreign_key: AttrName("movieId".to_string()),
+                    other_key: None,
+                    through: None,
+                    on_delete: None
+                })])),
+                timestamps: true,
+                naming: Some(naming),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for attributes {attr_names:?} with naming {naming:?}");
+        }
+        let nested_model = &ModelDefinition {
+            model_name: ModelName(AttrName("Test".to_string())),
+            attributes: Attributes::from([
+                (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                (AttrName("address".to_string()), AttrType::Object(ObjectDefinition {
+                    object: ObjectAttributes {
+                        attributes: Attributes::from([
+                            (AttrName("zip_code".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                            (AttrName("zipCode".to_string()), AttrType::Primitive(PrimitiveType::String))
+                        ]),
+                        required: vec!(),
+                        descriptions: None
+                    }
+                }))
+            ]),
+            naming: Some(Naming::SnakeCase),
+            ..base_model()
+        };
+        assert!(validate_model_definition(nested_model).is_err(), "Expected Error for colliding nested attributes with naming policy");
+
+        // test write-only and hashed attributes
+        for (write_only, hashed, expected) in [
+            (vec!("secret"), vec!("password"), Ok(())),
+            (vec!("secret", "password"), vec!("password"), Ok(())),
+            (vec!("id"), vec!(), Err("invalid write-only attribute \"id\"")),
+            (vec!(), vec!("id"), Err("invalid hashed attribute \"id\"")),
+            (vec!("iDontExist"), vec!(), Err("invalid write-only attribute \"iDontExist\"")),
+            (vec!(), vec!("iDontExist"), Err("invalid hashed attribute \"iDontExist\"")),
+            (vec!(), vec!("secret"), Err("invalid hashed attribute \"secret\"")),
+            (vec!("email"), vec!(), Err("invalid write-only attribute \"email\""))
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("email".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("password".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("secret".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+                ]),
+                write_only: Some(write_only.iter().map(|attr| AttrName(attr.to_string())).collect()),
+                hashed: Some(hashed.iter().map(|attr| AttrName(attr.to_string())).collect()),
+                unique: Some(vec!(KeyAttrs::Single(AttrName("email".to_string())))),
+                ..base_model()
+            };
+            let result: Result<()> = validate_model_definition(model);
+            assert_eq!(result.is_ok(), expected.is_ok(), "Unexpected validation result for write-only attributes {write_only:?} and hashed attributes {hashed:?}");
+            if let (Err(err), Err(prefix)) = (result, expected) {
+                assert!(err.to_string().starts_with(prefix), "Expected the error {err:?} to start with {prefix:?}");
+            }
+        }
+
+        // test history
+        for (attr_name, history, is_valid) in [
+            ("title", true, true),
+            ("history", false, true),
+            ("history", true, false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName(attr_name.to_string()), AttrType::Primitive(PrimitiveType::String))
+                ]),
+                history,
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for history with attribute {attr_name:?}");
+        }
+
+        // test many to many associations
+        for (kind, other_key, through, is_valid) in [
+            (AssociationKind::ManyToMany, Some("actorId"), None, true),
+            (AssociationKind::ManyToMany, Some("actorId"), Some("casting"), true),
+            (AssociationKind::ManyToMany, Some("movieId"), None, false),
+            (AssociationKind::ManyToMany, Some("id"), None, false),
+            (AssociationKind::HasMany, Some("actorId"), None, false),
+            (AssociationKind::BelongsTo, None, Some("casting"), false)
+        ] {
+            let association = Association {
+                kind,
+                model: ModelName(AttrName("actor".to_string())),
+                foreign_key: AttrName("movieId".to_string()),
+                other_key: other_key.map(|key| AttrName(key.to_string())),
+                through: through.map(|model| ModelName(AttrName(model.to_string()))),
+                on_delete: None
+            };
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                attributes: Attributes::from([
+                    (AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("movieId".to_string()), AttrType::Primitive(PrimitiveType::Integer))
+                ]),
+                associations: Some(HashMap::from([(AttrName("actors".to_string()), association.clone())])),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for association {association:?}");
+        }
+
+        // test composite primary keys
+        for (key_attrs, generator, is_valid) in [
+            (vec!("movie", "actor"), None, true),
+            (vec!("movie"), None, true),
+            (vec!(), None, false),
+            (vec!("movie", "movie"), None, false),
+            (vec!("movie", "iDontExist"), None, false),
+            (vec!("movie", "role"), None, false),
+            (vec!("movie", "actor"), Some(KeyGenerator::increment), false)
+        ] {
+            let model = &ModelDefinition {
+                model_name: ModelName(AttrName("Test".to_string())),
+                primary_key: KeyAttrs::Composite(key_attrs.iter().map(|attr| AttrName(attr.to_string())).collect()),
+                primary_key_generator: generator,
+                attributes: Attributes::from([
+                    (AttrName("movie".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("actor".to_string()), AttrType::Primitive(PrimitiveType::Integer)),
+                    (AttrName("role".to_string()), AttrType::Primitive(PrimitiveType::String))
+                ]),
+                required: vec!(AttrName("movie".to_string()), AttrName("actor".to_string())),
+                ..base_model()
+            };
+            assert_eq!(validate_model_definition(model).is_ok(), is_valid, "Unexpected validation result for composite primary key {key_attrs:?}");
+        }
+    }
+
+    #[test]
+    fn test_implicit_join_model() {
+        let movie = ModelDefinition {
+            attributes: Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))]),
+            ..base_model()
+        };
+        let actor = ModelDefinition {
+            model_name: ModelName(AttrName("actor".to_string())),
+            primary_key: KeyAttrs::Single(AttrName("name".to_string())),
+            attributes: Attributes::from([(AttrName("name".to_string()), AttrType::Primitive(PrimitiveType::String))]),
+            required: vec!(AttrName("name".to_string())),
+            ..base_model()
+        };
+        let actors = Association {
+            kind: AssociationKind::ManyToMany,
+            model: ModelName(AttrName("actor".to_string())),
+            foreign_key: AttrName("movieId".to_string()),
+            other_key: Some(AttrName("actorName".to_string())),
+            through: None,
+            on_delete: None
+        };
+        let movies = Association {
+            kind: AssociationKind::ManyToMany,
+            model: ModelName(AttrName("movie".to_string())),
+            foreign_key: AttrName("actorName".to_string()),
+            other_key: Some(AttrName("movieId".to_string())),
+            through: None,
+            on_delete: None
+        };
+
+        let join_model: ModelDefinition = actors.implicit_join_model(&movie, &actor);
+        assert_eq!(join_model.model_name, ModelName(AttrName("actorMovie".to_string())), "Unexpected name of the join model");
+        assert_eq!(join_model.primary_key, KeyAttrs::Composite(vec!(AttrName("actorName".to_string()), AttrName("movieId".to_string()))), "Unexpected key of the join model");
+        assert_eq!(join_model.attributes.get(&AttrName("movieId".to_string())), Some(&AttrType::Primitive(PrimitiveType::Integer)), "Unexpected type of the foreign key");
+        assert_eq!(join_model.attributes.get(&AttrName("actorName".to_string())), Some(&AttrType::Primitive(PrimitiveType::String)), "Unexpected type of the other key");
+        assert!(validate_model_definition(&join_model).is_ok(), "The join model should be valid");
+        assert_eq!(join_model, movies.implicit_join_model(&actor, &movie), "Both sides of the association should share the join model");
+    }
+
+    #[test]
+    fn test_resolve_model_definitions() {
+        let base = r#"{
+            "model_name": "movie",
+            "description": "A movie",
+            "storage_type": "json",
+            "attributes": {"id": "Integer", "name": "String", "location": {"Object": {"attributes": {"city": "String"}, "required": ["city"]}}},
+            "primary_key": "id",
+            "required": ["id"],
+            "constraints": {"name": {"String": {"minLength": 1}}}
+        }"#;
+        let audit = "attribute_set: audit\nattributes:\n  createdBy: String\nrequired: [createdBy]\nconstraints:\n  name:\n    String:\n      maxLength: 100";
+
+        let resolved = resolve_model_definitions(&[
+            (base.to_string(), ModelFormat::json),
+            (audit.to_string(), ModelFormat::yaml),
+            (r#"{"model_name": "documentary", "extends": "movie", "mixins": ["audit"], "attributes": {"topic": "String", "location": {"Object": {"attributes": {"country": "String"}, "required": ["country"]}}}, "required": ["topic"]}"#.to_string(), ModelFormat::json)
+        ]);
+        assert!(resolved[0].as_ref().is_some_and(|base| base.is_ok()), "Expected the base model to be valid on its own");
+        assert!(resolved[1].is_none(), "Expected no model for the attribute set");
+        let documentary: &ModelDefinition = resolved[2].as_ref().unwrap().as_ref().unwrap();
+        let mut attrs: Vec<&str> = documentary.attributes.keys().map(|attr| attr.0.as_str()).collect();
+        attrs.sort();
+        assert_eq!(attrs, vec!("createdBy", "id", "location", "name", "topic"), "Expected the attributes of the base model, the attribute set and the model");
+        assert_eq!(
+            documentary.required,
+            vec!(AttrName("id".to_string()), AttrName("createdBy".to_string()), AttrName("topic".to_string())),
+            "Expected the required attributes to be united"
+        );
+        assert_eq!(documentary.primary_key, KeyAttrs::Single(AttrName("id".to_string())), "Expected the primary key of the base model");
+        assert_eq!(documentary.description, None, "Expected the description of the base model not to be inherited");
+        assert_eq!(documentary.resolved_from, vec!(), "Expected the origins to be dropped after the validation");
+
+        // nested objects and maps get merged
+        assert_eq!(
+            documentary.attributes[&AttrName("location".to_string())],
+            AttrType::Object(ObjectDefinition { object: ObjectAttributes {
+                attributes: Attributes::from([
+                    (AttrName("city".to_string()), AttrType::Primitive(PrimitiveType::String)),
+                    (AttrName("country".to_string()), AttrType::Primitive(PrimitiveType::String))
+                ]),
+                required: vec!(AttrName("city".to_string()), AttrName("country".to_string())),
+                descriptions: None
+            }}),
+            "Expected the nested attributes of the object to be merged"
+        );
+        assert_eq!(
+            documentary.constraints,
+            Some(HashMap::from([(AttrName("name".to_string()), Constraints::String(StrConstraint { min_length: Some(1), max_length: Some(100), pattern: None, format: None }))])),
+            "Expected the constraints to be merged"
+        );
+
+        for (model, reason) in [
+            (r#"{"model_name": "documentary", "extends": "series", "attributes": {}, "required": []}"#, "not existing base model"),
+            (r#"{"model_name": "documentary", "extends": "movie", "mixins": ["ownership"], "attributes": {}, "required": []}"#, "not existing attribute set"),
+            (r#"{"model_name": "documentary", "extends": "movie", "attributes": {}, "required": ["iDontExist"]}"#, "invalid resolved model")
+        ] {
+            let resolved = resolve_model_definitions(&[
+                (base.to_string(), ModelFormat::json),
+                (audit.to_string(), ModelFormat::yaml),
+                (model.to_string(), ModelFormat::json)
+            ]);
+            assert!(resolved[2].as_ref().is_some_and(|model| model.is_err()), "Expected Error for {reason}");
+        }
+
+        // conflicts and cycles are detected by the validation
+        for (model, expected) in [
+            (r#"{"model_name": "documentary", "extends": "movie", "attributes": {"id": "String"}, "required": []}"#, r#"conflicting types of attribute "id" in the base model "movie""#),
+            (r#"{"model_name": "documentary", "extends": "movie", "mixins": ["audit"], "attributes": {"createdBy": "Integer"}, "required": []}"#, r#"conflicting types of attribute "createdBy" in the attribute set "audit""#),
+            (r#"{"model_name": "documentary", "extends": "movie", "attributes": {"location": {"Object": {"attributes": {"city": "Integer"}}}}, "required": []}"#, r#"conflicting types of attribute "location" in the base model "movie""#),
+            (r#"{"model_name": "documentary", "extends": "documentary", "storage_type": "json", "attributes": {"id": "Integer"}, "primary_key": "id", "required": ["id"]}"#, "cyclic base models documentary -> documentary")
+        ] {
+            let resolved = resolve_model_definitions(&[
+                (base.to_string(), ModelFormat::json),
+                (audit.to_string(), ModelFormat::yaml),
+                (model.to_string(), ModelFormat::json)
+            ]);
+            let err: String = resolved[2].as_ref().unwrap().as_ref().unwrap_err().to_string();
+            assert!(err.contains(expected), "Expected Error containing {expected:?}, got {err}");
+        }
+        let resolved = resolve_model_definitions(&[
+            (r#"{"model_name": "documentary", "extends": "series", "storage_type": "json", "attributes": {"id": "Integer"}, "primary_key": "id", "required": ["id"]}"#.to_string(), ModelFormat::json),
+            (r#"{"model_name": "series", "extends": "documentary", "storage_type": "json", "attributes": {"id": "Integer"}, "primary_key": "id", "required": ["id"]}"#.to_string(), ModelFormat::json)
+        ]);
+        let err: String = resolved[0].as_ref().unwrap().as_ref().unwrap_err().to_string();
+        assert!(err.contains("cyclic base models documentary -> series -> documentary"), "Expected Error naming the cycle, got {err}");
+        let err: String = resolved[1].as_ref().unwrap().as_ref().unwrap_err().to_string();
+        assert!(err.contains("cyclic base models series -> documentary -> series"), "Expected Error naming the cycle, got {err}");
+
+        // the origins are validated with the definition
+        let model = &ModelDefinition {
+            model_name: ModelName(AttrName("documentary".to_string())),
+            attributes: Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))]),
+            resolved_from: vec!(
+                Origin::BaseModel(ModelName(AttrName("movie".to_string())), Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))])),
+                Origin::AttributeSet("audit".to_string(), Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::String))]))
+            ),
+            ..base_model()
+        };
+        assert!(validate_model_definition(model).is_err(), "Expected Error for an attribute set conflicting with the model");
+
+        // attribute sets must have unique names
+        let resolved = resolve_model_definitions(&[
+            (base.to_string(), ModelFormat::json),
+            (audit.to_string(), ModelFormat::yaml),
+            (audit.to_string(), ModelFormat::yaml)
+        ]);
+        assert!(resolved[2].as_ref().is_some_and(|set| set.is_err()), "Expected Error for an attribute set with the same name");
+    }
+
+    #[test]
+    fn test_model_formats() {
+        let yaml = r#"
+            # comments are allowed in YAML
+            model_name: movie
+            storage_type: json
+            attributes:
+                id: Integer
+                genre:
+                    Enum: [drama, comedy]
+                actors: [String]
+            primary_key: id
+            required: [id]
+            constraints:
+                actors:
+                    Array:
+                        maxItems: 5
+        "#;
+        let toml = r#"
+            # comments are allowed in TOML
+            model_name = "movie"
+            storage_type = "json"
+            primary_key = "id"
+            required = ["id"]
+
+            [attributes]
+            id = "Integer"
+            genre = { Enum = ["drama", "comedy"] }
+            actors = ["String"]
+
+            [constraints.actors.Array]
+            maxItems = 5
+        "#;
+        let yaml_model: ModelDefinition = ModelDefinition::parse(yaml, ModelFormat::yaml).unwrap();
+        let toml_model: ModelDefinition = ModelDefinition::parse(toml, ModelFormat::toml).unwrap();
+        assert_eq!(yaml_model, toml_model, "YAML and TOML should result in the same model definition");
+
+        // every format can be read again after writing it
+        for format in [ModelFormat::json, ModelFormat::yaml, ModelFormat::toml] {
+            let written: String = yaml_model.to_format(format).unwrap();
+            assert_eq!(ModelDefinition::parse(&written, format).ok().as_ref(), Some(&yaml_model), "Unable to read the written {format:?} model definition");
+        }
+
+        // the same validation applies to every format
+        assert!(ModelDefinition::parse("model_name: movie\nstorage_type: json\nattributes:\n  id: Integer\nprimary_key: id\nrequired: []", ModelFormat::yaml).is_err(), "Expected Error for an invalid YAML model definition");
+        assert!(ModelDefinition::parse(yaml, ModelFormat::toml).is_err(), "Expected Error for YAML read as TOML");
+
+        for (path, format) in [("movie.json", ModelFormat::json), ("movie.yaml", ModelFormat::yaml), ("movie.yml", ModelFormat::yaml), ("movie.toml", ModelFormat::toml), ("movie", ModelFormat::json)] {
+            assert_eq!(ModelFormat::from_path(Path::new(path)), format, "Unexpected format of {path:?}");
+        }
+    }
+
+    #[test]
+    fn test_history_model() {
+        let movie = ModelDefinition {
+            model_name: ModelName(AttrName("movies".to_string())),
+            attributes: Attributes::from([(AttrName("id".to_string()), AttrType::Primitive(PrimitiveType::Integer))]),
+            history: true,
+            ..base_model()
+        };
+
+        let history_model: ModelDefinition = movie.history_model();
+        assert_eq!(history_model.model_name, ModelName(AttrName("movieHistory".to_string())), "Unexpected name of the history model");
+        assert_eq!(history_model.primary_key_generator, Some(KeyGenerator::increment), "History entries should be ordered by a sequence");
+        assert!(validate_model_definition(&history_model).is_ok(), "The history model should be valid");
+    }
+
+    #[test]
+    fn test_validate_attr_name() {
+        for (name, is_valid) in [
+            ("title", true),
+            ("releaseYear", true),
+            ("ReleaseYear", true),
+            ("release_year", true),
+            ("release-year", true),
+            ("address2", true),
+            ("sha256", true),
+            ("x_1", true),
+            ("line2-b", true),
+            ("2nd", false),
+            ("_id", false),
+            ("x__1", false),
+            ("release_Year", false),
+            ("rélease", false),
+            ("", false)
+        ] {
+            assert_eq!(validate_attr_name(name).is_ok(), is_valid, "Unexpected validation result for name {name:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_true_prim_type() {
+        // test Float
+        assert_eq!(to_true_prim_type(&parse::<Value>("1.5").unwrap(), &PrimitiveType::Float, true).unwrap(), Some(TruePrimitiveType::Float(1.5)));
+        assert_eq!(to_true_prim_type(&parse::<Value>("2").unwrap(), &PrimitiveType::Float, true).unwrap(), Some(TruePrimitiveType::Float(2.0)), "Expected Integer values to be accepted as Float");
+        assert!(to_true_prim_type(&parse::<Value>("\"1.5\"").unwrap(), &PrimitiveType::Float, true).is_err(), "Expected Error for parsing String-Value to Float");
+        assert!(to_true_prim_type(&parse::<Value>("1.5").unwrap(), &PrimitiveType::Integer, true).is_err(), "Expected Error for parsing Float-Value to Integer");
+
+        // test Date, DateTime and Time normalization
+        assert_eq!(to_true_prim_type(&parse::<Value>("\"1994-8-26\"").unwrap(), &PrimitiveType::Date, true).unwrap(), Some(TruePrimitiveType::String("1994-08-26".to_string())));
+        assert_eq!(to_true_prim_type(&parse::<Value>("\"1994-08-26T20:15:00+02:00\"").unwrap(), &PrimitiveType::DateTime, true).unwrap(), Some(TruePrimitiveType::String("1994-08-26T18:15:00Z".to_string())));
+        assert_eq!(to_true_prim_type(&parse::<Value>("\"20:15:00.500\"").unwrap(), &PrimitiveType::Time, true).unwrap(), Some(TruePrimitiveType::String("20:15:00.500".to_string())));
+        assert_eq!(to_true_prim_type(&parse::<Value>("\"20:15:00\"").unwrap(), &PrimitiveType::Time, true).unwrap(), Some(TruePrimitiveType::String("20:15:00".to_string())));
+        assert!(to_true_prim_type(&parse::<Value>("\"1994-02-30\"").unwrap(), &PrimitiveType::Date, true).is_err(), "Expected Error for parsing an invalid Date");
+        assert!(to_true_prim_type(&parse::<Value>("\"1994-08-26T20:15:00\"").unwrap(), &PrimitiveType::DateTime, true).is_err(), "Expected Error for parsing a DateTime without timezone");
+        assert!(to_true_prim_type(&parse::<Value>("\"25:00:00\"").unwrap(), &PrimitiveType::Time, true).is_err(), "Expected Error for parsing an invalid Time");
+        assert!(to_true_prim_type(&parse::<Value>("19940826").unwrap(), &PrimitiveType::Date, true).is_err(), "Expected Error for parsing Integer-Value to Date");
+
+        // test null
+        assert_eq!(to_true_prim_type(&Value::Null, &PrimitiveType::Float, false).unwrap(), None);
+        assert!(to_true_prim_type(&Value::Null, &PrimitiveType::Float, true).is_err(), "Expected Error for null-valued required attributes");
+    }
+
+    #[test]
+    fn test_validate_constraints() {
+        let integer = AttrType::Primitive(PrimitiveType::Integer);
+        let string = AttrType::Primitive(PrimitiveType::String);
+
+        assert!(Constraints::Integer(IntConstraint { min: Some(1), max: Some(2), multiple_of: Some(1) }).validate(&integer).is_ok());
+        assert!(Constraints::Integer(IntConstraint { min: Some(2), max: Some(1), multiple_of: None }).validate(&integer).is_err(), "Expected Error for min greater than max");
+        assert!(Constraints::Integer(IntConstraint { multiple_of: Some(0), ..Default::default() }).validate(&integer).is_err(), "Expected Error for multipleOf 0");
+        assert!(Constraints::Integer(IntConstraint::default()).validate(&string).is_err(), "Expected Error for Integer constraint on String attribute");
+        assert!("(".parse::<Pattern>().is_err(), "Expected Error for invalid pattern");
+        assert!(parse::<StrConstraint>(r#"{"pattern": "("}"#).is_err(), "Expected Error for parsing an invalid pattern");
+        assert!(Constraints::Array(ArrayConstraint::default()).validate(&AttrType::Array([PrimitiveType::String])).is_ok());
+    }
+
+    #[test]
+    fn test_check_constraints() {
+        let int = |val: i64| TrueType::Primitive(Some(TruePrimitiveType::Integer(val)));
+        let string = |val: &str| TrueType::Primitive(Some(TruePrimitiveType::String(val.to_string())));
+
+        let constraint = Constraints::Integer(IntConstraint { min: Some(1888), max: Some(2100), multiple_of: Some(2) });
+        assert!(constraint.check(&int(1994)).is_ok());
+        assert!(constraint.check(&NULL).is_ok(), "Unexpected Error for checking null");
+        for val in [1887, 2102, 1995] {
+            assert!(constraint.check(&int(val)).is_err(), "Expected Error for integer {val} violating the constraint");
+        }
+
+        let constraint = Constraints::String(StrConstraint { min_length: Some(2), max_length: Some(5), pattern: Some("^[a-z]+$".parse().unwrap()), format: None });
+        assert!(constraint.check(&string("abc")).is_ok());
+        for val in ["a", "abcdef", "ABC"] {
+            assert!(constraint.check(&string(val)).is_err(), "Expected Error for string {val:?} violating the constraint");
+        }
+
+        for (format, valid, invalid) in [
+            (StrFormat::email, "info@example.com", "info@example"),
+            (StrFormat::uuid, "67e55044-10b1-426f-9247-bb680e5fe0c8", "67e55044-10b1-426f-9247"),
+            (StrFormat::uri, "https://example.com/path", "example.com")
+        ] {
+            let constraint = Constraints::String(StrConstraint { format: Some(format), ..Default::default() });
+            assert!(constraint.check(&string(valid)).is_ok(), "Unexpected Error for valid string {valid:?}");
+            assert!(constraint.check(&string(invalid)).is_err(), "Expected Error for invalid string {invalid:?}");
+        }
+
+        let constraint = Constraints::Array(ArrayConstraint { min_items: Some(1), max_items: Some(2), unique_items: Some(true) });
+        let arr = |vals: Vec<&str>| TrueType::Array(Some(vals.iter().map(|v| TruePrimitiveType::String(v.to_string())).collect()));
+        assert!(constraint.check(&arr(vec!("a", "b"))).is_ok());
+        for vals in [vec!(), vec!("a", "b", "c"), vec!("a", "a")] {
+            assert!(constraint.check(&arr(vals.clone())).is_err(), "Expected Error for array {vals:?} violating the constraint");
+        }
+    }
+
+    #[test]
+    fn test_to_true_enum_value() {
+        let enum_def = &EnumDefinition {
+            values: vec!("RELEASED".to_string(), "PLANNED".to_string()),
+            descriptions: None
+        };
+        assert_eq!(to_true_enum_value(&parse::<Value>("\"PLANNED\"").unwrap(), enum_def, true).unwrap(), Some(TruePrimitiveType::String("PLANNED".to_string())));
+        assert_eq!(to_true_enum_value(&Value::Null, enum_def, false).unwrap(), None);
+        assert!(to_true_enum_value(&parse::<Value>("\"planned\"").unwrap(), enum_def, true).is_err(), "Expected Error for values that are not in the enum");
+        assert!(to_true_enum_value(&parse::<Value>("1").unwrap(), enum_def, true).is_err(), "Expected Error for parsing Integer-Value to Enum");
+        assert!(to_true_enum_value(&Value::Null, enum_def, true).is_err(), "Expected Error for null-valued required attributes");
+    }
 }
\ No newline at end of file